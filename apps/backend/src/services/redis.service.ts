@@ -140,6 +140,8 @@ export interface SpectreSignalPayload {
   // - Standard momentum (ratio 1.5-2.0 + price +5-10%): 700,000 lamports (0.0007 SOL)
   // - Falls back to config default if not provided
   priorityFeeLamports?: number;
+  // Explicit buy size in SOL - overrides SPECTRE's sizing policy (min/max caps still apply)
+  amountSol?: number;
 }
 
 /**
@@ -169,6 +171,7 @@ export interface SpectrePreSignalPayload {
     label: string | null;
     score: number | null;
   }>;
+  amountSol?: number;               // Explicit buy size in SOL (same as on the signal)
 }
 
 // Singleton instance
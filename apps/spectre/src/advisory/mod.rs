@@ -0,0 +1,299 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, info};
+
+use crate::monitor::{execute_exit, exit_label};
+use crate::position::ExitReason;
+use crate::pumpportal::WalletSell;
+use crate::redis::{Delivery, ResultPublisher};
+use crate::trader::SpectreTrader;
+
+/// Exit advisory from the backend (its exit signals / position wallet activity)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitAdvisory {
+    pub token_mint: String,
+    #[serde(rename = "type")]
+    pub kind: AdvisoryKind,
+    /// Backend's own suggestion: "hold", "partial_exit_50", "full_exit", ...
+    #[serde(default)]
+    pub recommendation: Option<String>,
+    #[serde(default)]
+    pub wallets_exited_count: Option<u32>,
+    #[serde(default)]
+    pub wallets_holding_count: Option<u32>,
+    #[serde(default)]
+    pub trigger_reason: Option<String>,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+}
+
+/// Advisory types spectre acts on (the backend's exit signal `type`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvisoryKind {
+    /// Trigger wallets are selling
+    WalletExit,
+    /// The buy consensus reversed
+    ConsensusReversed,
+    /// Anything else (price-based types spectre already handles itself)
+    #[serde(other)]
+    Other,
+}
+
+/// What to do about an advisory
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdvisoryResponse {
+    Ignore,
+    /// Sell the whole position
+    Full,
+    /// Sell this percent of the position
+    Partial(f64),
+    /// Raise the stop to this percent below the current price
+    TightenStop(f64),
+    /// Follow the advisory's `recommendation` (hold / partial_exit_N / full_exit)
+    Recommended,
+}
+
+impl FromStr for AdvisoryResponse {
+    type Err = anyhow::Error;
+
+    /// `ignore`, `full`, `partial:50`, `tighten:10`, `recommended`
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+        let (kind, value) = match s.split_once(':') {
+            Some((kind, value)) => (kind, Some(value)),
+            None => (s.as_str(), None),
+        };
+        let percent = || -> Result<f64> {
+            value
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|p| AdvisoryResponse::is_sell_percent(*p))
+                .ok_or_else(|| anyhow!("Advisory response '{}' needs a percent in (0, 100], e.g. {}:50", s, kind))
+        };
+
+        match kind {
+            "ignore" => Ok(AdvisoryResponse::Ignore),
+            "full" => Ok(AdvisoryResponse::Full),
+            "partial" => Ok(AdvisoryResponse::Partial(percent()?)),
+            "tighten" => Ok(AdvisoryResponse::TightenStop(percent()?)),
+            "recommended" => Ok(AdvisoryResponse::Recommended),
+            other => Err(anyhow!("Unknown advisory response: {} (ignore, full, partial:N, tighten:N, recommended)", other)),
+        }
+    }
+}
+
+impl AdvisoryResponse {
+    /// Percent to sell for the backend's recommendation (None = hold / unknown / out of range)
+    fn recommended_percent(recommendation: Option<&str>) -> Option<f64> {
+        match recommendation? {
+            "full_exit" => Some(100.0),
+            other => other
+                .strip_prefix("partial_exit_")?
+                .parse::<f64>()
+                .ok()
+                .filter(|p| Self::is_sell_percent(*p)),
+        }
+    }
+
+    /// In (0, 100] (false for NaN)
+    fn is_sell_percent(percent: f64) -> bool {
+        percent > 0.0 && percent <= 100.0
+    }
+}
+
+/// Response per advisory type
+#[derive(Debug, Clone)]
+pub struct AdvisoryPolicy {
+    pub wallet_exit: AdvisoryResponse,
+    pub consensus_reversed: AdvisoryResponse,
+}
+
+impl Default for AdvisoryPolicy {
+    fn default() -> Self {
+        Self {
+            wallet_exit: AdvisoryResponse::Full,
+            consensus_reversed: AdvisoryResponse::TightenStop(10.0),
+        }
+    }
+}
+
+impl AdvisoryPolicy {
+    pub fn describe(&self) -> String {
+        format!("wallet_exit={:?} | consensus_reversed={:?}", self.wallet_exit, self.consensus_reversed)
+    }
+}
+
+/// Copy-exit: follow the trigger wallets out once enough of them sold
+#[derive(Debug, Clone)]
+pub struct CopyExitPolicy {
+    /// Trigger wallets that must have sold (capped at the position's count, 0 = off)
+    pub min_wallets: usize,
+    pub response: AdvisoryResponse,
+}
+
+impl CopyExitPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.min_wallets > 0
+    }
+
+    pub fn describe(&self) -> String {
+        if !self.is_enabled() {
+            return "off".to_string();
+        }
+        format!("{} trigger wallet(s) sold -> {:?}", self.min_wallets, self.response)
+    }
+}
+
+/// What an advisory did to its position
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdvisoryAction {
+    /// No position, ignored type/response, or a hold recommendation
+    None,
+    /// Exit ran with this reason
+    Exit(ExitReason),
+    /// Stop moved from the first to the second price
+    StopTightened(f64, f64),
+}
+
+/// Act on one advisory according to the policy
+pub async fn handle_advisory(
+    trader: &SpectreTrader,
+    publisher: &dyn ResultPublisher,
+    policy: &AdvisoryPolicy,
+    advisory: &ExitAdvisory,
+) -> AdvisoryAction {
+    let token_mint = &advisory.token_mint;
+    let short_mint = &token_mint[..16.min(token_mint.len())];
+
+    let response = match advisory.kind {
+        AdvisoryKind::WalletExit => policy.wallet_exit,
+        AdvisoryKind::ConsensusReversed => policy.consensus_reversed,
+        AdvisoryKind::Other => {
+            debug!("Exit advisory for {} ignored - type not handled", short_mint);
+            return AdvisoryAction::None;
+        }
+    };
+
+    if !trader.position_manager().has_position(token_mint).await {
+        debug!("Exit advisory {:?} for {} ignored - no position", advisory.kind, short_mint);
+        return AdvisoryAction::None;
+    }
+
+    let sell_percent = match response {
+        AdvisoryResponse::Ignore => return AdvisoryAction::None,
+        AdvisoryResponse::Full => 100.0,
+        AdvisoryResponse::Partial(percent) => percent,
+        AdvisoryResponse::Recommended => match AdvisoryResponse::recommended_percent(advisory.recommendation.as_deref()) {
+            Some(percent) => percent,
+            None => {
+                info!("📨 Exit advisory {:?} for {}: holding ({})", advisory.kind, short_mint, advisory.recommendation.as_deref().unwrap_or("no recommendation"));
+                return AdvisoryAction::None;
+            }
+        },
+        AdvisoryResponse::TightenStop(percent) => {
+            return match trader.position_manager().tighten_stop(token_mint, percent).await {
+                Some((old, new)) => AdvisoryAction::StopTightened(old, new),
+                None => AdvisoryAction::None,
+            };
+        }
+    };
+
+    let exit_reason = match advisory.kind {
+        AdvisoryKind::ConsensusReversed => ExitReason::ConsensusReversed { sell_percent },
+        _ => ExitReason::SmartWalletExit { sell_percent },
+    };
+    info!(
+        "🚨 {} triggered for {} by exit advisory ({})",
+        exit_label(&exit_reason),
+        short_mint,
+        advisory.trigger_reason.as_deref().unwrap_or("no reason given")
+    );
+    execute_exit(trader, publisher, token_mint, exit_reason).await;
+    AdvisoryAction::Exit(exit_reason)
+}
+
+/// Record a trigger wallet's sell and copy the exit when it brings the sellers to the policy's N of M
+/// (runs as a `wallet_exit` advisory, so it ends in a SmartWalletExit)
+pub async fn handle_wallet_sell(
+    trader: &SpectreTrader,
+    publisher: &dyn ResultPublisher,
+    policy: &CopyExitPolicy,
+    sell: &WalletSell,
+) -> AdvisoryAction {
+    let Some((sold, total)) = trader.position_manager().record_trigger_sell(&sell.token_mint, &sell.wallet).await else {
+        return AdvisoryAction::None;
+    };
+    info!(
+        "👀 Trigger wallet {} sold {} ({}/{} trigger wallets out)",
+        &sell.wallet[..8.min(sell.wallet.len())],
+        &sell.token_mint[..16.min(sell.token_mint.len())],
+        sold,
+        total
+    );
+
+    // Only the sell that reaches the threshold acts - later ones would repeat a partial exit
+    if !policy.is_enabled() || sold != policy.min_wallets.min(total) {
+        return AdvisoryAction::None;
+    }
+
+    let advisory = ExitAdvisory {
+        token_mint: sell.token_mint.clone(),
+        kind: AdvisoryKind::WalletExit,
+        recommendation: None,
+        wallets_exited_count: Some(sold as u32),
+        wallets_holding_count: Some((total - sold) as u32),
+        trigger_reason: Some(format!("{} of {} trigger wallets sold", sold, total)),
+        timestamp: None,
+    };
+    let advisory_policy = AdvisoryPolicy { wallet_exit: policy.response, ..AdvisoryPolicy::default() };
+    handle_advisory(trader, publisher, &advisory_policy, &advisory).await
+}
+
+/// Background task acting on exit advisories (each in its own task, like price-triggered exits, acked once done)
+pub async fn advisory_listener(
+    trader: Arc<SpectreTrader>,
+    publisher: Arc<dyn ResultPublisher>,
+    policy: AdvisoryPolicy,
+    mut advisory_rx: mpsc::UnboundedReceiver<Delivery<ExitAdvisory>>,
+) {
+    info!("📨 Exit advisory handler started ({})", policy.describe());
+    let policy = Arc::new(policy);
+
+    while let Some(advisory) = advisory_rx.recv().await {
+        let trader = trader.clone();
+        let publisher = publisher.clone();
+        let policy = policy.clone();
+        tokio::spawn(async move {
+            handle_advisory(&trader, publisher.as_ref(), &policy, &advisory).await;
+            advisory.ack().await;
+        });
+    }
+
+    info!("📨 Exit advisory handler stopped");
+}
+
+/// Background task following trigger wallets' sells (PumpPortal account trades)
+pub async fn copy_exit_listener(
+    trader: Arc<SpectreTrader>,
+    publisher: Arc<dyn ResultPublisher>,
+    policy: CopyExitPolicy,
+    mut sell_rx: mpsc::UnboundedReceiver<WalletSell>,
+) {
+    info!("👀 Copy-exit handler started ({})", policy.describe());
+    let policy = Arc::new(policy);
+
+    while let Some(sell) = sell_rx.recv().await {
+        let trader = trader.clone();
+        let publisher = publisher.clone();
+        let policy = policy.clone();
+        tokio::spawn(async move {
+            handle_wallet_sell(&trader, publisher.as_ref(), &policy, &sell).await;
+        });
+    }
+
+    info!("👀 Copy-exit handler stopped");
+}
@@ -0,0 +1,617 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::paper::FillModel;
+use crate::position::{ExitReason, Position};
+use crate::pumpportal::PriceUpdate;
+use crate::strategy::ExitStrategies;
+
+/// Simulated tokens use pump.fun's 6 decimals (PnL doesn't depend on it)
+const DECIMALS: u8 = 6;
+
+const USAGE: &str = "usage: spectre backtest --ticks <file|dir>... --entries <file> \
+[--strategies <file>]... [--amount-sol X] [--sol-usd X] [--slippage-bps N] [--fee-bps N] \
+[--network-fee-sol X] [--check-interval-secs N] [--trades] [--verbose]";
+
+/// Entry to simulate - a `SpectreSignal` capture line works as-is
+#[derive(Debug, Clone, Deserialize)]
+struct BacktestEntry {
+    #[serde(alias = "tokenMint")]
+    token_mint: String,
+    #[serde(default, alias = "tokenSymbol")]
+    token_symbol: String,
+    #[serde(alias = "entryTime", alias = "timestamp")]
+    entry_time: DateTime<Utc>,
+    #[serde(default, alias = "signalType")]
+    signal_type: String,
+    #[serde(default)]
+    strength: String,
+    #[serde(default = "default_stop_loss", alias = "stopLossPercent")]
+    stop_loss_percent: f64,
+    #[serde(default = "default_take_profit", alias = "takeProfitPercent")]
+    take_profit_percent: f64,
+    #[serde(default, alias = "amountSol")]
+    amount_sol: Option<f64>,
+}
+
+// Same defaults as STOP_LOSS_PERCENT / TAKE_PROFIT_PERCENT
+fn default_stop_loss() -> f64 {
+    25.0
+}
+
+fn default_take_profit() -> f64 {
+    100000.0
+}
+
+/// Tick line in a JSONL capture (token_mint may come from the file name instead)
+#[derive(Debug, Deserialize)]
+struct TickLine {
+    #[serde(default, alias = "tokenMint")]
+    token_mint: Option<String>,
+    #[serde(alias = "priceUsd", alias = "price")]
+    price_usd: f64,
+    #[serde(default, alias = "marketCapUsd")]
+    market_cap_usd: f64,
+    timestamp: i64,
+}
+
+struct Options {
+    tick_paths: Vec<PathBuf>,
+    entries_path: PathBuf,
+    strategy_files: Vec<String>,
+    amount_sol: f64,
+    sol_usd: f64,
+    fill_model: FillModel,
+    network_fee_sol: f64,
+    check_interval_secs: i64,
+    show_trades: bool,
+    verbose: bool,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut options = Options {
+            tick_paths: Vec::new(),
+            entries_path: PathBuf::new(),
+            strategy_files: Vec::new(),
+            amount_sol: 0.1,
+            sol_usd: 200.0,
+            fill_model: FillModel { slippage_bps: 100, fee_bps: 100 },
+            network_fee_sol: 0.0005,
+            check_interval_secs: 5,
+            show_trades: false,
+            verbose: false,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().cloned().ok_or_else(|| anyhow!("{} expects a value\n{}", arg, USAGE));
+            match arg.as_str() {
+                "--ticks" => options.tick_paths.push(PathBuf::from(value()?)),
+                "--entries" => options.entries_path = PathBuf::from(value()?),
+                "--strategies" => options.strategy_files.push(value()?),
+                "--amount-sol" => options.amount_sol = value()?.parse()?,
+                "--sol-usd" => options.sol_usd = value()?.parse()?,
+                "--slippage-bps" => options.fill_model.slippage_bps = value()?.parse()?,
+                "--fee-bps" => options.fill_model.fee_bps = value()?.parse()?,
+                "--network-fee-sol" => options.network_fee_sol = value()?.parse()?,
+                "--check-interval-secs" => options.check_interval_secs = value()?.parse()?,
+                "--trades" => options.show_trades = true,
+                "--verbose" => options.verbose = true,
+                other => bail!("Unknown backtest option: {}\n{}", other, USAGE),
+            }
+        }
+
+        if options.tick_paths.is_empty() || options.entries_path.as_os_str().is_empty() {
+            bail!("--ticks and --entries are required\n{}", USAGE);
+        }
+        if options.check_interval_secs <= 0 {
+            bail!("--check-interval-secs must be > 0");
+        }
+
+        Ok(options)
+    }
+}
+
+/// Outcome of one simulated position
+#[derive(Debug, Clone)]
+pub struct TradeOutcome {
+    pub token_symbol: String,
+    pub strategy: String,
+    pub entry_time: DateTime<Utc>,
+    pub exit_time: DateTime<Utc>,
+    pub entry_price: f64,
+    pub sol_spent: f64,
+    pub sol_received: f64,
+    /// Exit reason codes in order ("scaled_take_profit", "stop_loss", "end_of_data", ...)
+    pub exits: Vec<String>,
+    /// Worst unrealized PnL % while the position was open
+    pub worst_pnl_percent: f64,
+}
+
+impl TradeOutcome {
+    pub fn pnl_sol(&self) -> f64 {
+        self.sol_received - self.sol_spent
+    }
+
+    pub fn pnl_percent(&self) -> f64 {
+        if self.sol_spent <= 0.0 {
+            return 0.0;
+        }
+        self.pnl_sol() / self.sol_spent * 100.0
+    }
+}
+
+/// Aggregate stats over all simulated trades
+#[derive(Debug, Default, Clone)]
+pub struct BacktestSummary {
+    pub trades: usize,
+    pub wins: usize,
+    pub total_pnl_sol: f64,
+    pub avg_pnl_percent: f64,
+    /// Largest peak-to-trough drop of cumulative realized PnL (SOL, by exit time)
+    pub max_drawdown_sol: f64,
+}
+
+impl BacktestSummary {
+    pub fn from_trades(trades: &[TradeOutcome]) -> Self {
+        if trades.is_empty() {
+            return Self::default();
+        }
+
+        let mut by_exit: Vec<&TradeOutcome> = trades.iter().collect();
+        by_exit.sort_by_key(|t| t.exit_time);
+
+        let (mut equity, mut peak, mut max_drawdown) = (0.0_f64, 0.0_f64, 0.0_f64);
+        for trade in by_exit {
+            equity += trade.pnl_sol();
+            peak = peak.max(equity);
+            max_drawdown = max_drawdown.max(peak - equity);
+        }
+
+        Self {
+            trades: trades.len(),
+            wins: trades.iter().filter(|t| t.pnl_sol() > 0.0).count(),
+            total_pnl_sol: equity,
+            avg_pnl_percent: trades.iter().map(|t| t.pnl_percent()).sum::<f64>() / trades.len() as f64,
+            max_drawdown_sol: max_drawdown,
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.trades as f64 * 100.0
+    }
+}
+
+/// Replays recorded price ticks through the same exit logic as `position_monitor`
+pub struct Backtester {
+    strategies: ExitStrategies,
+    fill_model: FillModel,
+    sol_usd: f64,
+    network_fee_sol: f64,
+    check_interval: chrono::Duration,
+}
+
+impl Backtester {
+    /// Simulate one entry against the mint's ticks (sorted by time)
+    /// Returns None if there is no tick at or after the entry time
+    fn simulate(&self, entry: &BacktestEntry, amount_sol: f64, ticks: &[PriceUpdate]) -> Option<TradeOutcome> {
+        let start = ticks.iter().position(|t| t.timestamp >= entry.entry_time.timestamp())?;
+        let entry_tick = &ticks[start];
+        let entry_time = tick_time(entry_tick);
+
+        let buy = self.fill_model.buy_at_price(amount_sol, self.network_fee_sol, entry_tick.price_usd, self.sol_usd, DECIMALS);
+
+        let mut position = Position::new_with_signal_type(
+            entry.token_mint.clone(),
+            entry.token_symbol.clone(),
+            buy.price_usd,
+            buy.amount_tokens,
+            amount_sol,
+            entry.stop_loss_percent,
+            entry.take_profit_percent,
+            "backtest".to_string(),
+            true,
+            entry.signal_type.to_lowercase(),
+        );
+        if let Some(strategy) = self.strategies.resolve(&entry.signal_type, &entry.strength) {
+            position = position.with_exit_strategy(strategy);
+        }
+        position.apply_fill(buy.amount_tokens, DECIMALS, buy.price_usd);
+        position.entry_time = entry_time;
+
+        let mut outcome = TradeOutcome {
+            token_symbol: entry.token_symbol.clone(),
+            strategy: position.effective_strategy().map(|s| s.name).unwrap_or_else(|| "sl/tp".to_string()),
+            entry_time,
+            exit_time: entry_time,
+            entry_price: buy.price_usd,
+            sol_spent: buy.amount_sol,
+            sol_received: 0.0,
+            exits: Vec::new(),
+            worst_pnl_percent: 0.0,
+        };
+
+        let mut last_price = entry_tick.price_usd;
+        let mut last_time = entry_time;
+
+        for tick in &ticks[start + 1..] {
+            let now = tick_time(tick);
+
+            // Periodic branch: re-check with the last known price between ticks
+            let mut check_at = last_time + self.check_interval;
+            while check_at < now {
+                if let Some(reason) = position.check_exit_at(last_price, check_at) {
+                    if self.exit(&mut position, &mut outcome, reason, last_price, check_at) {
+                        return Some(outcome);
+                    }
+                }
+                check_at += self.check_interval;
+            }
+
+            // Tick branch: exit is checked on the snapshot taken before recording the price
+            let snapshot = position.clone();
+            position.record_price(tick.price_usd, now);
+            outcome.worst_pnl_percent = outcome.worst_pnl_percent.min(position.calculate_pnl(tick.price_usd).pnl_percent);
+
+            if let Some(reason) = snapshot.check_exit_at(tick.price_usd, now) {
+                if self.exit(&mut position, &mut outcome, reason, tick.price_usd, now) {
+                    return Some(outcome);
+                }
+            }
+
+            last_price = tick.price_usd;
+            last_time = now;
+        }
+
+        // Still open when the data ends - mark to market at the last price
+        let fill = self.fill_model.sell_at_price(position.amount_tokens, DECIMALS, last_price, self.sol_usd, self.network_fee_sol);
+        outcome.sol_received += fill.amount_sol;
+        outcome.exit_time = last_time;
+        outcome.exits.push("end_of_data".to_string());
+        Some(outcome)
+    }
+
+    /// Simulate a sell like `SpectreTrader::execute_sell`, returns true if the position is closed
+    fn exit(&self, position: &mut Position, outcome: &mut TradeOutcome, reason: ExitReason, price: f64, now: DateTime<Utc>) -> bool {
+        let (tokens_to_sell, full_exit) = match reason {
+            ExitReason::ScaledTakeProfit { sell_percent, .. } => position.scaled_exit_amount(sell_percent),
+            _ => (position.amount_tokens, true),
+        };
+
+        let fill = self.fill_model.sell_at_price(tokens_to_sell, DECIMALS, price, self.sol_usd, self.network_fee_sol);
+        outcome.sol_received += fill.amount_sol;
+        outcome.exit_time = now;
+        outcome.exits.push(reason.code().to_string());
+
+        if let ExitReason::ScaledTakeProfit { stage, sell_percent, .. } = reason {
+            position.advance_scaled_exit(stage, sell_percent);
+            return full_exit || position.is_fully_closed();
+        }
+        true
+    }
+}
+
+fn tick_time(tick: &PriceUpdate) -> DateTime<Utc> {
+    Utc.timestamp_opt(tick.timestamp, 0).single().unwrap_or_default()
+}
+
+/// Load ticks from JSONL (`PriceUpdate` lines) or CSV (`timestamp,price_usd[,token_mint]` header)
+/// Files without a token_mint column/field use the file name as the mint
+fn load_ticks(paths: &[PathBuf]) -> Result<HashMap<String, Vec<PriceUpdate>>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            for entry in std::fs::read_dir(path)? {
+                let file = entry?.path();
+                if matches!(file.extension().and_then(|e| e.to_str()), Some("jsonl" | "csv")) {
+                    files.push(file);
+                }
+            }
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    let mut ticks: HashMap<String, Vec<PriceUpdate>> = HashMap::new();
+    for file in files {
+        let default_mint = file.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+        let parsed = match file.extension().and_then(|e| e.to_str()) {
+            Some("csv") => parse_csv(&file, &default_mint)?,
+            _ => parse_jsonl(&file, &default_mint)?,
+        };
+        for tick in parsed {
+            ticks.entry(tick.token_mint.clone()).or_default().push(tick);
+        }
+    }
+
+    for series in ticks.values_mut() {
+        series.sort_by_key(|t| t.timestamp);
+    }
+
+    Ok(ticks)
+}
+
+/// Capture timestamps may be in milliseconds
+fn normalize_timestamp(timestamp: i64) -> i64 {
+    if timestamp > 10_000_000_000 {
+        timestamp / 1000
+    } else {
+        timestamp
+    }
+}
+
+fn parse_jsonl(path: &Path, default_mint: &str) -> Result<Vec<PriceUpdate>> {
+    let reader = BufReader::new(std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?);
+    let mut ticks = Vec::new();
+
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let tick: TickLine = serde_json::from_str(&line)
+            .with_context(|| format!("{}:{}: invalid tick", path.display(), line_no + 1))?;
+        ticks.push(PriceUpdate {
+            token_mint: tick.token_mint.unwrap_or_else(|| default_mint.to_string()),
+            price_usd: tick.price_usd,
+            market_cap_usd: tick.market_cap_usd,
+            timestamp: normalize_timestamp(tick.timestamp),
+        });
+    }
+
+    Ok(ticks)
+}
+
+fn parse_csv(path: &Path, default_mint: &str) -> Result<Vec<PriceUpdate>> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+
+    let header: Vec<String> = lines
+        .next()
+        .ok_or_else(|| anyhow!("{}: empty CSV", path.display()))?
+        .split(',')
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+    let column = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+
+    let ts_col = column(&["timestamp", "ts", "time"]).ok_or_else(|| anyhow!("{}: no timestamp column", path.display()))?;
+    let price_col = column(&["price_usd", "price"]).ok_or_else(|| anyhow!("{}: no price_usd column", path.display()))?;
+    let mint_col = column(&["token_mint", "mint"]);
+
+    let mut ticks = Vec::new();
+    for (line_no, line) in lines.enumerate() {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let field = |col: usize| fields.get(col).copied().unwrap_or_default();
+        let timestamp: i64 = field(ts_col)
+            .parse()
+            .with_context(|| format!("{}:{}: bad timestamp", path.display(), line_no + 2))?;
+        let price_usd: f64 = field(price_col)
+            .parse()
+            .with_context(|| format!("{}:{}: bad price", path.display(), line_no + 2))?;
+
+        ticks.push(PriceUpdate {
+            token_mint: mint_col.map(|c| field(c).to_string()).unwrap_or_else(|| default_mint.to_string()),
+            price_usd,
+            market_cap_usd: 0.0,
+            timestamp: normalize_timestamp(timestamp),
+        });
+    }
+
+    Ok(ticks)
+}
+
+/// Entries as a JSON array or JSON lines
+fn load_entries(path: &Path) -> Result<Vec<BacktestEntry>> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    if content.trim_start().starts_with('[') {
+        return Ok(serde_json::from_str(&content)?);
+    }
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| serde_json::from_str(l).with_context(|| format!("{}:{}: invalid entry", path.display(), i + 1)))
+        .collect()
+}
+
+/// `spectre backtest ...` - run every strategy config over the same entries and ticks
+pub fn run(args: &[String]) -> Result<()> {
+    let options = Options::parse(args)?;
+
+    if options.verbose {
+        tracing_subscriber::fmt().with_target(false).compact().init();
+    }
+
+    let ticks = load_ticks(&options.tick_paths)?;
+    let entries = load_entries(&options.entries_path)?;
+    println!(
+        "👻 SPECTRE backtest - {} entries, {} mints with ticks, slippage {}bps, fee {}bps",
+        entries.len(),
+        ticks.len(),
+        options.fill_model.slippage_bps,
+        options.fill_model.fee_bps
+    );
+
+    let configs: Vec<(String, ExitStrategies)> = if options.strategy_files.is_empty() {
+        vec![("built-in".to_string(), ExitStrategies::builtin())]
+    } else {
+        options
+            .strategy_files
+            .iter()
+            .map(|f| Ok((f.clone(), ExitStrategies::from_file(f)?)))
+            .collect::<Result<_>>()?
+    };
+
+    for (name, strategies) in configs {
+        let backtester = Backtester {
+            strategies,
+            fill_model: options.fill_model,
+            sol_usd: options.sol_usd,
+            network_fee_sol: options.network_fee_sol,
+            check_interval: chrono::Duration::seconds(options.check_interval_secs),
+        };
+
+        let mut trades = Vec::new();
+        let mut skipped = 0;
+        for entry in &entries {
+            let amount_sol = entry.amount_sol.unwrap_or(options.amount_sol);
+            match ticks.get(&entry.token_mint).and_then(|t| backtester.simulate(entry, amount_sol, t)) {
+                Some(trade) => trades.push(trade),
+                None => skipped += 1,
+            }
+        }
+
+        println!();
+        println!("━━━ {} ━━━", name);
+
+        if options.show_trades {
+            println!(
+                "{:<12}  {:<16}  {:<20}  {:>14}  {:>8}  {:>9}  {:>8}  {:>8}  exits",
+                "token", "strategy", "entry", "entry $", "held", "pnl SOL", "pnl %", "worst %"
+            );
+            for trade in &trades {
+                println!(
+                    "{:<12}  {:<16}  {:<20}  {:>14.10}  {:>7}m  {:>+9.4}  {:>+7.1}%  {:>+7.1}%  {}",
+                    trade.token_symbol,
+                    trade.strategy,
+                    trade.entry_time.format("%Y-%m-%d %H:%M:%S"),
+                    trade.entry_price,
+                    (trade.exit_time - trade.entry_time).num_minutes(),
+                    trade.pnl_sol(),
+                    trade.pnl_percent(),
+                    trade.worst_pnl_percent,
+                    trade.exits.join(" > ")
+                );
+            }
+        }
+
+        let summary = BacktestSummary::from_trades(&trades);
+        println!(
+            "trades: {} (skipped {} without ticks) | win rate: {:.1}% | avg PnL: {:+.2}% | total: {:+.4} SOL | max drawdown: {:.4} SOL",
+            summary.trades,
+            skipped,
+            summary.win_rate(),
+            summary.avg_pnl_percent,
+            summary.total_pnl_sol,
+            summary.max_drawdown_sol
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+    }
+
+    /// Trade spending 1 SOL that exits `exit_minute` minutes into the run with `pnl_sol`
+    fn trade(exit_minute: i64, pnl_sol: f64) -> TradeOutcome {
+        let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        TradeOutcome {
+            token_symbol: "TEST".into(),
+            strategy: "sl/tp".into(),
+            entry_time: start,
+            exit_time: start + chrono::Duration::minutes(exit_minute),
+            entry_price: 1.0,
+            sol_spent: 1.0,
+            sol_received: 1.0 + pnl_sol,
+            exits: Vec::new(),
+            worst_pnl_percent: 0.0,
+        }
+    }
+
+    #[test]
+    fn empty_run_has_zeroed_stats() {
+        let summary = BacktestSummary::from_trades(&[]);
+        assert_eq!(summary.trades, 0);
+        assert_eq!(summary.win_rate(), 0.0);
+        assert_eq!(summary.max_drawdown_sol, 0.0);
+    }
+
+    #[test]
+    fn drawdown_is_the_largest_peak_to_trough_drop() {
+        // Equity: 1.0, 0.5, 0.7, -0.3, 1.7 - peak 1.0 to trough -0.3
+        let trades = [trade(1, 1.0), trade(2, -0.5), trade(3, 0.2), trade(4, -1.0), trade(5, 2.0)];
+        let summary = BacktestSummary::from_trades(&trades);
+        assert_close(summary.max_drawdown_sol, 1.3);
+        assert_close(summary.total_pnl_sol, 1.7);
+        assert_close(summary.avg_pnl_percent, 34.0);
+    }
+
+    #[test]
+    fn drawdown_follows_exit_time_not_input_order() {
+        // By exit time: +0.5, -0.4, -0.4, +1.0 - drawdown 0.8 from the first peak
+        let trades = [trade(4, 1.0), trade(2, -0.4), trade(1, 0.5), trade(3, -0.4)];
+        assert_close(BacktestSummary::from_trades(&trades).max_drawdown_sol, 0.8);
+    }
+
+    #[test]
+    fn losses_from_the_start_count_as_drawdown() {
+        let trades = [trade(1, -0.25), trade(2, -0.25), trade(3, 0.1)];
+        assert_close(BacktestSummary::from_trades(&trades).max_drawdown_sol, 0.5);
+    }
+
+    #[test]
+    fn monotonic_gains_have_no_drawdown() {
+        let trades = [trade(1, 0.1), trade(2, 0.2), trade(3, 0.3)];
+        assert_eq!(BacktestSummary::from_trades(&trades).max_drawdown_sol, 0.0);
+    }
+
+    #[test]
+    fn win_rate_counts_only_profitable_trades() {
+        // Break-even is not a win
+        let trades = [trade(1, 0.5), trade(2, -0.2), trade(3, 0.0), trade(4, 0.01)];
+        let summary = BacktestSummary::from_trades(&trades);
+        assert_eq!(summary.trades, 4);
+        assert_eq!(summary.wins, 2);
+        assert_close(summary.win_rate(), 50.0);
+    }
+
+    #[test]
+    fn simulated_stop_loss_realizes_the_loss() {
+        let backtester = Backtester {
+            strategies: ExitStrategies::default(),
+            fill_model: FillModel { slippage_bps: 0, fee_bps: 0 },
+            sol_usd: 100.0,
+            network_fee_sol: 0.0,
+            check_interval: chrono::Duration::seconds(5),
+        };
+        let entry: BacktestEntry = serde_json::from_value(serde_json::json!({
+            "tokenMint": "MINT",
+            "tokenSymbol": "TEST",
+            "entryTime": "2023-11-14T22:13:20Z",
+        }))
+        .unwrap();
+        let ticks: Vec<PriceUpdate> = [1.0, 0.9, 0.7, 2.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &price_usd)| PriceUpdate {
+                token_mint: "MINT".into(),
+                price_usd,
+                market_cap_usd: 0.0,
+                timestamp: 1_700_000_000 + i as i64,
+            })
+            .collect();
+
+        let outcome = backtester.simulate(&entry, 1.0, &ticks).expect("entry tick");
+
+        // -25% stop hit at 0.7, the later pump is never seen
+        assert_eq!(outcome.exits, vec!["stop_loss".to_string()]);
+        assert_close(outcome.sol_received, 0.7);
+        assert_close(outcome.pnl_percent(), -30.0);
+        assert_close(outcome.worst_pnl_percent, -30.0);
+    }
+}
@@ -1,10 +1,14 @@
 use anyhow::{anyhow, Result};
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
 use tracing::{debug, warn};
 
-const BIRDEYE_API_URL: &str = "https://public-api.birdeye.so";
-const DEXSCREENER_API_URL: &str = "https://api.dexscreener.com/latest/dex/tokens";
+use crate::capture::{self, CaptureEvent};
+
+pub const BIRDEYE_API_URL: &str = "https://public-api.birdeye.so";
+pub const DEXSCREENER_API_URL: &str = "https://api.dexscreener.com/latest/dex/tokens";
 
 #[derive(Debug, Deserialize)]
 struct BirdeyeResponse<T> {
@@ -28,36 +32,73 @@ struct DexScreenerResponse {
 struct DexScreenerPair {
     chain_id: String,
     price_usd: Option<String>,
-    #[serde(default)]
-    liquidity: Option<DexScreenerLiquidity>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct DexScreenerLiquidity {
-    usd: Option<f64>,
 }
 
 pub struct BirdeyeClient {
     client: Client,
     api_key: Option<String>,
+    dexscreener_url: String,
+    birdeye_url: String,
+    /// Replay mode: last recorded price response per mint (no HTTP requests)
+    replay_prices: Option<RwLock<HashMap<String, Result<f64, String>>>>,
 }
 
 impl BirdeyeClient {
     pub fn new(api_key: Option<String>) -> Self {
+        Self::with_urls(api_key, DEXSCREENER_API_URL, BIRDEYE_API_URL)
+    }
+
+    /// Client on custom DexScreener (`.../tokens`) and Birdeye API bases
+    pub fn with_urls(api_key: Option<String>, dexscreener_url: &str, birdeye_url: &str) -> Self {
         Self {
             client: Client::builder()
                 .timeout(std::time::Duration::from_secs(5))
                 .build()
                 .expect("Failed to create HTTP client"),
             api_key,
+            dexscreener_url: dexscreener_url.trim_end_matches('/').to_string(),
+            birdeye_url: birdeye_url.trim_end_matches('/').to_string(),
+            replay_prices: None,
+        }
+    }
+
+    /// Client answering from recorded price responses (see `set_replay_price`)
+    pub fn replaying() -> Self {
+        Self {
+            replay_prices: Some(RwLock::new(HashMap::new())),
+            ..Self::new(None)
+        }
+    }
+
+    /// Replay mode: make `get_price` return this recorded response from now on
+    pub async fn set_replay_price(&self, token_mint: &str, response: Result<f64, String>) {
+        if let Some(ref replay) = self.replay_prices {
+            replay.write().await.insert(token_mint.to_string(), response);
         }
     }
 
     /// Get current price in USD for a token
+    pub async fn get_price(&self, token_mint: &str) -> Result<f64> {
+        if let Some(ref replay) = self.replay_prices {
+            return match replay.read().await.get(token_mint) {
+                Some(Ok(price)) => Ok(*price),
+                Some(Err(e)) => Err(anyhow!("{}", e)),
+                None => Err(anyhow!("No recorded price for {}", token_mint)),
+            };
+        }
+
+        let result = self.fetch_price(token_mint).await;
+        capture::record(CaptureEvent::PriceResponse {
+            token_mint: token_mint.to_string(),
+            price_usd: result.as_ref().ok().copied(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+        result
+    }
+
     /// First tries DexScreener (no rate limit, works for all DEX tokens),
     /// falls back to Birdeye for edge cases
-    pub async fn get_price(&self, token_mint: &str) -> Result<f64> {
+    async fn fetch_price(&self, token_mint: &str) -> Result<f64> {
         // 1. Try DexScreener first (no rate limit, works for all Solana DEX tokens)
         if let Ok(price) = self.get_price_from_dexscreener(token_mint).await {
             return Ok(price);
@@ -69,7 +110,7 @@ impl BirdeyeClient {
 
     /// Get price from DexScreener API (no rate limit)
     async fn get_price_from_dexscreener(&self, token_mint: &str) -> Result<f64> {
-        let url = format!("{}/{}", DEXSCREENER_API_URL, token_mint);
+        let url = format!("{}/{}", self.dexscreener_url, token_mint);
 
         let response = self.client
             .get(&url)
@@ -102,7 +143,7 @@ impl BirdeyeClient {
 
     /// Get price from Birdeye API (fallback, has rate limits)
     async fn get_price_from_birdeye(&self, token_mint: &str) -> Result<f64> {
-        let url = format!("{}/defi/price?address={}", BIRDEYE_API_URL, token_mint);
+        let url = format!("{}/defi/price?address={}", self.birdeye_url, token_mint);
 
         let mut request = self.client.get(&url)
             .header("accept", "application/json")
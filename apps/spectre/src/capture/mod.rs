@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, LineWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tracing::{info, warn};
+
+use crate::pumpportal::TradeEvent;
+use crate::redis::{SpectrePreSignal, SpectreSignal, TradeResult};
+
+/// Inbound input or trade outcome seen by a running session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CaptureEvent {
+    /// Signal popped from the Redis queue
+    Signal { signal: SpectreSignal },
+    /// Pre-signal popped from the Redis queue
+    PreSignal { pre_signal: SpectrePreSignal },
+    /// Raw PumpPortal trade event (before price calculation)
+    TradeEvent { event: TradeEvent },
+    /// DexScreener/Birdeye price lookup (price or error)
+    PriceResponse {
+        token_mint: String,
+        price_usd: Option<f64>,
+        error: Option<String>,
+    },
+    /// Result of a buy or sell
+    TradeResult { result: Box<TradeResult> },
+}
+
+/// One line of a capture file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureRecord {
+    pub ts: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: CaptureEvent,
+}
+
+enum Sink {
+    File(LineWriter<File>),
+    /// Replay keeps the trade results in memory to compare them with the capture
+    Memory(Vec<CaptureRecord>),
+}
+
+static RECORDER: OnceLock<Mutex<Sink>> = OnceLock::new();
+
+/// Start recording to a new timestamped capture file in `dir`
+pub fn start_file(dir: &str) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create capture dir {}", dir))?;
+    let path = Path::new(dir).join(format!("capture-{}.jsonl", Utc::now().format("%Y%m%d-%H%M%S")));
+    let file = File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+
+    if RECORDER.set(Mutex::new(Sink::File(LineWriter::new(file)))).is_err() {
+        warn!("⚠️ Capture recorder already started");
+    }
+    info!("🎥 Recording inputs to {}", path.display());
+    Ok(path)
+}
+
+/// Record into memory (read back with `recorded_results`)
+pub fn start_memory() {
+    let _ = RECORDER.set(Mutex::new(Sink::Memory(Vec::new())));
+}
+
+/// Record an event (no-op unless a recorder was started)
+pub fn record(event: CaptureEvent) {
+    let Some(recorder) = RECORDER.get() else {
+        return;
+    };
+    let record = CaptureRecord { ts: Utc::now(), event };
+
+    let mut sink = match recorder.lock() {
+        Ok(sink) => sink,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    match &mut *sink {
+        Sink::File(writer) => {
+            let line = match serde_json::to_string(&record) {
+                Ok(line) => line,
+                Err(e) => {
+                    warn!("⚠️ Failed to serialize capture event: {}", e);
+                    return;
+                }
+            };
+            if let Err(e) = writeln!(writer, "{}", line) {
+                warn!("⚠️ Failed to write capture event: {}", e);
+            }
+        }
+        Sink::Memory(records) => records.push(record),
+    }
+}
+
+/// Trade results recorded in memory so far
+pub fn recorded_results() -> Vec<TradeResult> {
+    let Some(recorder) = RECORDER.get() else {
+        return Vec::new();
+    };
+    let sink = match recorder.lock() {
+        Ok(sink) => sink,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    match &*sink {
+        Sink::Memory(records) => trade_results(records),
+        Sink::File(_) => Vec::new(),
+    }
+}
+
+/// Trade results in a list of records, in order
+pub fn trade_results(records: &[CaptureRecord]) -> Vec<TradeResult> {
+    records
+        .iter()
+        .filter_map(|r| match &r.event {
+            CaptureEvent::TradeResult { result } => Some(result.as_ref().clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Load a capture file (sorted by time)
+pub fn load(path: &Path) -> Result<Vec<CaptureRecord>> {
+    let reader = BufReader::new(File::open(path).with_context(|| format!("Failed to open {}", path.display()))?);
+    let mut records = Vec::new();
+
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: CaptureRecord = serde_json::from_str(&line)
+            .with_context(|| format!("{}:{}: invalid capture record", path.display(), line_no + 1))?;
+        records.push(record);
+    }
+
+    records.sort_by_key(|r| r.ts);
+    Ok(records)
+}
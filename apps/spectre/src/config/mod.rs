@@ -1,7 +1,17 @@
 use anyhow::Result;
 use solana_sdk::signature::{Keypair, Signer};
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::advisory::{AdvisoryPolicy, AdvisoryResponse, CopyExitPolicy};
+use crate::birdeye::{BIRDEYE_API_URL, DEXSCREENER_API_URL};
+use crate::paper::FillModel;
+use crate::pumpfun_trade::PUMPPORTAL_API_URL;
+use crate::pumpportal::PUMPPORTAL_WS_URL;
+use crate::risk::RiskLimits;
+use crate::sizing::{SizingMode, SizingPolicy, DEFAULT_MAX_MULTIPLE};
+use crate::strategy::ExitStrategies;
+
 #[derive(Clone)]
 pub struct Config {
     // RPC endpoints
@@ -13,10 +23,21 @@ pub struct Config {
 
     // Trading parameters
     pub trade_amount_sol: f64,      // 0.1 SOL
+    pub sizing: SizingPolicy,       // per-signal buy size (trade_amount_sol is its base)
     pub slippage_bps: u16,          // 1500 = 15%
     pub stop_loss_percent: f64,     // -25%
     pub take_profit_percent: f64,   // +50%
 
+    // Portfolio risk limits (0 = no limit)
+    pub max_open_positions: usize,
+    pub max_deployed_sol: f64,        // total cost basis of open positions
+    pub daily_loss_limit_sol: f64,    // halt new entries once today's realized loss reaches this
+    pub min_sol_reserve: f64,         // SOL kept in the wallet for fees
+
+    // Exit strategies (TP ladders / SL per signal type + strength)
+    pub exit_strategies_file: Option<String>,
+    pub exit_strategies: ExitStrategies,
+
     // Jito
     pub jito_tip_lamports: u64,      // Tip for BUY Jito bundle (e.g., 1000000 = 0.001 SOL)
     pub jito_tip_sell_lamports: u64, // Tip for SELL Jito bundle (lower, e.g., 350000 = 0.00035 SOL)
@@ -24,21 +45,69 @@ pub struct Config {
     // Redis
     pub redis_url: String,
     pub redis_channel: String,
+    pub redis_max_downtime_secs: u64, // give up (exit) when Redis stays unavailable this long (0 = retry forever)
+    pub control_queue: String,       // operator commands (sell, close-all, pause, ...)
+    pub control_reply_key: String,   // where command replies go unless the command names its own
+
+    // Exit advisories from the backend (wallet exits, consensus reversal)
+    pub advisory_queue: String,
+    pub advisory_policy: AdvisoryPolicy,
+
+    // Copy-exits: follow the trigger wallets' own sells (PumpPortal account trades)
+    pub copy_exit: CopyExitPolicy,
 
     // Jupiter API
     pub jupiter_api_key: Option<String>,
+    pub jupiter_api_url: Option<String>,  // base for /quote + /swap (None = picked from the API key)
 
     // Birdeye API (for price monitoring)
     pub birdeye_api_key: Option<String>,
 
+    // Service endpoints (overridable for self-hosted proxies and local test servers)
+    pub pumpportal_api_url: String,
+    pub pumpportal_ws_url: String,
+    pub dexscreener_api_url: String,
+    pub birdeye_api_url: String,
+
+    // Signal execution
+    pub max_concurrent_signals: usize,  // buys running at the same time (one per mint)
+    pub max_signal_age_secs: u64,       // skip signals older than this (0 = no limit)
+    pub max_signal_age_by_type: HashMap<String, u64>,  // per signal type, from MAX_SIGNAL_AGE_SECS_<TYPE>
+
     // Position monitoring
     pub position_check_interval_secs: u64,
+
+    // Position journal (survives restarts)
+    pub positions_file: String,
+
+    // Trade journal (SQLite audit trail + PnL reports)
+    pub journal_db: String,
+
+    // Paper trading (SPECTRE_MODE=paper or --paper): quotes are real, fills are simulated
+    pub paper_mode: bool,
+    pub paper_slippage_bps: u16,    // 100 = fills 1% worse than the quote
+    pub paper_fee_bps: u16,         // 100 = 1% venue fee (pump.fun), not applied to Jupiter quotes
+
+    // Record-and-replay: capture every input + trade result to a JSONL file in this dir
+    pub capture_dir: Option<String>,
+
+    // Wallet reconciliation (on-chain holdings vs tracked positions)
+    pub reconcile_adopt_orphans: bool,    // adopt untracked holdings as positions (false = only flag them)
+    pub reconcile_min_value_usd: f64,     // ignore dust holdings below this value
+    pub reconcile_interval_secs: u64,     // 0 = only at startup
 }
 
 impl Config {
-    pub fn from_env() -> Result<Self> {
+    /// Load config from env; `force_paper` comes from the `--paper` CLI flag
+    pub fn from_env(force_paper: bool) -> Result<Self> {
         dotenvy::dotenv().ok();
 
+        let paper_mode = force_paper
+            || std::env::var("SPECTRE_MODE").map(|v| v.eq_ignore_ascii_case("paper")).unwrap_or(false);
+
+        // Paper positions/journal never mix with the live ones
+        let data_dir = if paper_mode { "data/paper" } else { "data" };
+
         // Load wallet from private key (base58 or byte array)
         let private_key = std::env::var("WALLET_PRIVATE_KEY")
             .expect("WALLET_PRIVATE_KEY must be set");
@@ -54,6 +123,69 @@ impl Config {
             Keypair::from_bytes(&bytes)?
         };
 
+        let exit_strategies_file = std::env::var("EXIT_STRATEGIES_FILE").ok();
+        let exit_strategies = match exit_strategies_file {
+            Some(ref path) => ExitStrategies::from_file(path)?,
+            None => ExitStrategies::builtin(),
+        };
+
+        let trade_amount_sol: f64 = std::env::var("TRADE_AMOUNT_SOL")
+            .unwrap_or_else(|_| "0.1".to_string())
+            .parse()
+            .unwrap_or(0.1);
+
+        let sizing = SizingPolicy {
+            mode: std::env::var("SIZING_MODE")
+                .map(|mode| mode.parse::<SizingMode>())
+                .unwrap_or(Ok(SizingMode::Fixed))?,
+            base_sol: trade_amount_sol,
+            strong_sol: std::env::var("SIZING_STRONG_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(trade_amount_sol * 1.5),
+            medium_sol: std::env::var("SIZING_MEDIUM_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(trade_amount_sol),
+            weak_sol: std::env::var("SIZING_WEAK_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(trade_amount_sol * 0.5),
+            score_reference: std::env::var("SIZING_SCORE_REFERENCE")
+                .unwrap_or_else(|_| "50".to_string())
+                .parse()
+                .unwrap_or(50.0),
+            min_sol: std::env::var("SIZING_MIN_SOL")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0.0),
+            max_sol: std::env::var("SIZING_MAX_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(trade_amount_sol * DEFAULT_MAX_MULTIPLE),
+        };
+        sizing.validate()?;
+
+        let default_advisory_policy = AdvisoryPolicy::default();
+        let advisory_policy = AdvisoryPolicy {
+            wallet_exit: std::env::var("ADVISORY_WALLET_EXIT")
+                .map(|response| response.parse::<AdvisoryResponse>())
+                .unwrap_or(Ok(default_advisory_policy.wallet_exit))?,
+            consensus_reversed: std::env::var("ADVISORY_CONSENSUS_REVERSED")
+                .map(|response| response.parse::<AdvisoryResponse>())
+                .unwrap_or(Ok(default_advisory_policy.consensus_reversed))?,
+        };
+
+        let copy_exit = CopyExitPolicy {
+            min_wallets: std::env::var("COPY_EXIT_WALLETS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0),
+            response: std::env::var("COPY_EXIT_RESPONSE")
+                .map(|response| response.parse::<AdvisoryResponse>())
+                .unwrap_or(Ok(AdvisoryResponse::Full))?,
+        };
+
         Ok(Config {
             rpc_url: std::env::var("RPC_URL")
                 .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string()),
@@ -63,10 +195,8 @@ impl Config {
 
             wallet: Arc::new(wallet),
 
-            trade_amount_sol: std::env::var("TRADE_AMOUNT_SOL")
-                .unwrap_or_else(|_| "0.1".to_string())
-                .parse()
-                .unwrap_or(0.1),
+            trade_amount_sol,
+            sizing,
 
             slippage_bps: std::env::var("SLIPPAGE_BPS")
                 .unwrap_or_else(|_| "1500".to_string())
@@ -83,6 +213,29 @@ impl Config {
                 .parse()
                 .unwrap_or(100000.0),
 
+            max_open_positions: std::env::var("MAX_OPEN_POSITIONS")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+
+            max_deployed_sol: std::env::var("MAX_DEPLOYED_SOL")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0.0),
+
+            daily_loss_limit_sol: std::env::var("DAILY_LOSS_LIMIT_SOL")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0.0),
+
+            min_sol_reserve: std::env::var("MIN_SOL_RESERVE")
+                .unwrap_or_else(|_| "0.05".to_string())
+                .parse()
+                .unwrap_or(0.05),
+
+            exit_strategies_file,
+            exit_strategies,
+
             // Note: These are actually priority fees sent to PumpPortal, not Jito tips
             // Jito bundling provides MEV protection regardless of tip amount
             jito_tip_lamports: std::env::var("JITO_TIP_LAMPORTS")
@@ -101,18 +254,129 @@ impl Config {
             redis_channel: std::env::var("REDIS_CHANNEL")
                 .unwrap_or_else(|_| "ninja_signals".to_string()),
 
+            redis_max_downtime_secs: std::env::var("REDIS_MAX_DOWNTIME_SECS")
+                .unwrap_or_else(|_| "120".to_string())
+                .parse()
+                .unwrap_or(120),
+
+            control_queue: std::env::var("CONTROL_QUEUE")
+                .unwrap_or_else(|_| "spectre_commands".to_string()),
+
+            control_reply_key: std::env::var("CONTROL_REPLY_KEY")
+                .unwrap_or_else(|_| "spectre_command_replies".to_string()),
+
+            advisory_queue: std::env::var("ADVISORY_QUEUE")
+                .unwrap_or_else(|_| "spectre_exit_advisories".to_string()),
+
+            advisory_policy,
+
+            copy_exit,
+
             jupiter_api_key: std::env::var("JUPITER_API_KEY").ok(),
 
+            jupiter_api_url: std::env::var("JUPITER_API_URL").ok().filter(|u| !u.is_empty()),
+
             birdeye_api_key: std::env::var("BIRDEYE_API_KEY").ok(),
 
+            pumpportal_api_url: std::env::var("PUMPPORTAL_API_URL")
+                .unwrap_or_else(|_| PUMPPORTAL_API_URL.to_string()),
+
+            pumpportal_ws_url: std::env::var("PUMPPORTAL_WS_URL")
+                .unwrap_or_else(|_| PUMPPORTAL_WS_URL.to_string()),
+
+            dexscreener_api_url: std::env::var("DEXSCREENER_API_URL")
+                .unwrap_or_else(|_| DEXSCREENER_API_URL.to_string()),
+
+            birdeye_api_url: std::env::var("BIRDEYE_API_URL")
+                .unwrap_or_else(|_| BIRDEYE_API_URL.to_string()),
+
+            max_concurrent_signals: std::env::var("MAX_CONCURRENT_SIGNALS")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .unwrap_or(4),
+
+            max_signal_age_secs: std::env::var("MAX_SIGNAL_AGE_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+
+            max_signal_age_by_type: std::env::vars()
+                .filter_map(|(key, value)| {
+                    let signal_type = key.strip_prefix("MAX_SIGNAL_AGE_SECS_")?;
+                    Some((signal_type.to_lowercase(), value.parse().ok()?))
+                })
+                .collect(),
+
             position_check_interval_secs: std::env::var("POSITION_CHECK_INTERVAL_SECS")
                 .unwrap_or_else(|_| "5".to_string())
                 .parse()
                 .unwrap_or(5),
+
+            positions_file: std::env::var(if paper_mode { "PAPER_POSITIONS_FILE" } else { "POSITIONS_FILE" })
+                .unwrap_or_else(|_| format!("{}/positions.jsonl", data_dir)),
+
+            journal_db: std::env::var(if paper_mode { "PAPER_JOURNAL_DB" } else { "JOURNAL_DB" })
+                .unwrap_or_else(|_| format!("{}/journal.db", data_dir)),
+
+            paper_mode,
+
+            paper_slippage_bps: std::env::var("PAPER_SLIPPAGE_BPS")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .unwrap_or(100),
+
+            paper_fee_bps: std::env::var("PAPER_FEE_BPS")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .unwrap_or(100),
+
+            capture_dir: std::env::var("CAPTURE_DIR").ok().filter(|d| !d.is_empty()),
+
+            reconcile_adopt_orphans: std::env::var("RECONCILE_ADOPT_ORPHANS")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+
+            reconcile_min_value_usd: std::env::var("RECONCILE_MIN_VALUE_USD")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .unwrap_or(1.0),
+
+            reconcile_interval_secs: std::env::var("RECONCILE_INTERVAL_SECS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0),
         })
     }
 
     pub fn wallet_pubkey(&self) -> solana_sdk::pubkey::Pubkey {
         self.wallet.pubkey()
     }
+
+    /// Max age of a signal of this type in seconds (None = no limit)
+    pub fn max_signal_age_secs(&self, signal_type: &str) -> Option<u64> {
+        let max_age = self
+            .max_signal_age_by_type
+            .get(&signal_type.to_lowercase())
+            .copied()
+            .unwrap_or(self.max_signal_age_secs);
+        (max_age > 0).then_some(max_age)
+    }
+
+    /// Limits the risk manager checks every buy against
+    pub fn risk_limits(&self) -> RiskLimits {
+        RiskLimits {
+            max_open_positions: self.max_open_positions,
+            max_deployed_sol: self.max_deployed_sol,
+            daily_loss_limit_sol: self.daily_loss_limit_sol,
+            min_sol_reserve: self.min_sol_reserve,
+        }
+    }
+
+    /// Slippage/fee model for simulated fills
+    pub fn fill_model(&self) -> FillModel {
+        FillModel {
+            slippage_bps: self.paper_slippage_bps,
+            fee_bps: self.paper_fee_bps,
+        }
+    }
 }
@@ -0,0 +1,297 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::error::SpectreError;
+use crate::jito::{BundleStatus, JitoClient};
+
+// How often to poll getSignatureStatuses
+const POLL_INTERVAL_MS: u64 = 400;
+
+// Check blockhash validity every N polls (it's a separate RPC call)
+const BLOCKHASH_CHECK_EVERY: u32 = 5;
+
+// Hard cap in case RPC keeps answering "valid" or erroring (blockhash lifetime is ~60-90s)
+const MAX_WAIT_SECS: u64 = 120;
+
+/// Final state of a submitted transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum TxOutcome {
+    /// Landed and reached `confirmed` commitment without error
+    Confirmed { slot: u64 },
+    /// Landed on-chain but the transaction itself failed (slippage, insufficient funds, ...)
+    Failed { error: String },
+    /// Blockhash expired before the transaction landed - it can never land now
+    Expired,
+    /// Gave up waiting before the blockhash was reported invalid - it may still land
+    Unknown,
+}
+
+impl std::fmt::Display for TxOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TxOutcome::Confirmed { slot } => write!(f, "Confirmed (slot {})", slot),
+            TxOutcome::Failed { error } => write!(f, "Failed on-chain: {}", error),
+            TxOutcome::Expired => write!(f, "Expired (blockhash no longer valid)"),
+            TxOutcome::Unknown => write!(f, "Unknown (gave up waiting, blockhash not reported invalid)"),
+        }
+    }
+}
+
+impl TxOutcome {
+    /// Why the transaction didn't confirm (None if it did)
+    pub fn error(&self) -> Option<SpectreError> {
+        match self {
+            TxOutcome::Confirmed { .. } => None,
+            TxOutcome::Failed { error } => Some(
+                SpectreError::from_transaction_error(error)
+                    .unwrap_or_else(|| SpectreError::TxFailed(error.clone())),
+            ),
+            TxOutcome::Expired => Some(SpectreError::BlockhashExpired("transaction did not land in time".to_string())),
+            TxOutcome::Unknown => Some(SpectreError::TxUnknown(format!("not confirmed after {}s", MAX_WAIT_SECS))),
+        }
+    }
+}
+
+/// Real signature of a signed transaction (first signature = fee payer = our wallet)
+/// This is what shows up on explorers, unlike the Jito bundle ID
+pub fn transaction_signature(transaction: &VersionedTransaction) -> Signature {
+    transaction.signatures.first().copied().unwrap_or_default()
+}
+
+/// Tracks submitted transactions until they confirm, fail, or their blockhash expires
+pub struct TxConfirmer {
+    rpc_client: Arc<RpcClient>,
+    poll_interval: Duration,
+    max_wait: Duration,
+}
+
+impl TxConfirmer {
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self {
+            rpc_client,
+            poll_interval: Duration::from_millis(POLL_INTERVAL_MS),
+            max_wait: Duration::from_secs(MAX_WAIT_SECS),
+        }
+    }
+
+    /// Wait for a transaction to reach a final state
+    /// If `bundle_id` is given, Jito's in-flight status is used to fail fast on dropped bundles
+    pub async fn confirm(
+        &self,
+        signature: &Signature,
+        recent_blockhash: &Hash,
+        bundle_id: Option<&str>,
+        jito: &JitoClient,
+    ) -> TxOutcome {
+        let start = std::time::Instant::now();
+        let mut polls: u32 = 0;
+
+        loop {
+            tokio::time::sleep(self.poll_interval).await;
+            polls += 1;
+
+            if let Some(outcome) = self.check_signature(signature).await {
+                info!("🔎 TX {} -> {} (took: {:?})", signature, outcome, start.elapsed());
+                return outcome;
+            }
+
+            if !polls.is_multiple_of(BLOCKHASH_CHECK_EVERY) {
+                continue;
+            }
+
+            // Jito reports dropped bundles long before the blockhash expires
+            if let Some(bundle_id) = bundle_id {
+                match jito.get_inflight_bundle_status(bundle_id).await {
+                    Ok(BundleStatus::Failed) => {
+                        // One last look - the tx could have landed outside the bundle
+                        if let Some(outcome) = self.check_signature(signature).await {
+                            return outcome;
+                        }
+                        warn!("🔎 Jito bundle {} failed, TX {} did not land", bundle_id, signature);
+                        return TxOutcome::Failed { error: "Jito bundle failed".to_string() };
+                    }
+                    Ok(status) => debug!("Jito bundle {} status: {}", bundle_id, status),
+                    Err(e) => debug!("Jito bundle status check failed: {}", e),
+                }
+            }
+
+            // Only a blockhash positively reported invalid means the tx can never land
+            match self
+                .rpc_client
+                .is_blockhash_valid(recent_blockhash, CommitmentConfig::processed())
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => {
+                    // Blockhash is dead; a final status check covers the race with landing
+                    if let Some(outcome) = self.check_signature(signature).await {
+                        return outcome;
+                    }
+                    warn!("🔎 TX {} expired without landing (took: {:?})", signature, start.elapsed());
+                    return TxOutcome::Expired;
+                }
+                Err(e) => debug!("isBlockhashValid failed: {}", e), // RPC hiccup - keep waiting
+            }
+
+            if start.elapsed() >= self.max_wait {
+                if let Some(outcome) = self.check_signature(signature).await {
+                    return outcome;
+                }
+                // Still landable as far as we know - resending could fill twice
+                warn!("🔎 TX {} still unconfirmed, giving up (took: {:?})", signature, start.elapsed());
+                return TxOutcome::Unknown;
+            }
+        }
+    }
+
+    /// Single getSignatureStatuses lookup - None while still pending
+    async fn check_signature(&self, signature: &Signature) -> Option<TxOutcome> {
+        let statuses = match self.rpc_client.get_signature_statuses(&[*signature]).await {
+            Ok(response) => response.value,
+            Err(e) => {
+                debug!("getSignatureStatuses failed: {}", e);
+                return None;
+            }
+        };
+
+        let status = statuses.into_iter().next().flatten()?;
+
+        if let Some(err) = status.err {
+            return Some(TxOutcome::Failed { error: err.to_string() });
+        }
+
+        if status.satisfies_commitment(CommitmentConfig::confirmed()) {
+            return Some(TxOutcome::Confirmed { slot: status.slot });
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use wiremock::{matchers::method, Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    /// Blockhash check answer of the mock node
+    #[derive(Clone, Copy)]
+    enum Blockhash {
+        Valid,
+        Invalid,
+        RpcError,
+    }
+
+    /// JSON-RPC node where the tx shows up after `lands_after` status lookups (never if None)
+    struct Node {
+        lands_after: Option<usize>,
+        blockhash: Blockhash,
+        lookups: AtomicUsize,
+    }
+
+    impl Respond for Node {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let result = match (body["method"].as_str().unwrap_or_default(), self.blockhash) {
+                ("getVersion", _) => json!({ "solana-core": "1.18.26", "feature-set": 4215500110u64 }),
+                ("getSignatureStatuses", _) => {
+                    let lookups = self.lookups.fetch_add(1, Ordering::SeqCst) + 1;
+                    let status = match self.lands_after {
+                        Some(after) if lookups > after => json!({
+                            "slot": 7,
+                            "confirmations": null,
+                            "err": null,
+                            "status": { "Ok": null },
+                            "confirmationStatus": "confirmed",
+                        }),
+                        _ => Value::Null,
+                    };
+                    json!({ "context": { "slot": 1 }, "value": [status] })
+                }
+                ("isBlockhashValid", Blockhash::RpcError) => return ResponseTemplate::new(503),
+                ("isBlockhashValid", blockhash) => {
+                    json!({ "context": { "slot": 1 }, "value": matches!(blockhash, Blockhash::Valid) })
+                }
+                (other, _) => panic!("unexpected RPC call {}", other),
+            };
+            ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": body["id"], "result": result }))
+        }
+    }
+
+    async fn confirm(lands_after: Option<usize>, blockhash: Blockhash, bundle_status: &str) -> TxOutcome {
+        let rpc = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(Node { lands_after, blockhash, lookups: AtomicUsize::new(0) })
+            .mount(&rpc)
+            .await;
+        let jito = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "context": { "slot": 1 }, "value": [{ "status": bundle_status }] },
+            })))
+            .mount(&jito)
+            .await;
+
+        let confirmer = TxConfirmer {
+            rpc_client: Arc::new(RpcClient::new(rpc.uri())),
+            poll_interval: Duration::from_millis(5),
+            max_wait: Duration::from_millis(200),
+        };
+        confirmer
+            .confirm(&Signature::default(), &Hash::new_unique(), Some("bundle"), &JitoClient::new(&jito.uri()))
+            .await
+    }
+
+    #[tokio::test]
+    async fn expires_once_the_blockhash_is_reported_invalid() {
+        assert_eq!(confirm(None, Blockhash::Invalid, "Pending").await, TxOutcome::Expired);
+    }
+
+    #[tokio::test]
+    async fn valid_blockhash_at_the_hard_cap_is_unknown_not_expired() {
+        assert_eq!(confirm(None, Blockhash::Valid, "Pending").await, TxOutcome::Unknown);
+    }
+
+    #[tokio::test]
+    async fn blockhash_check_errors_never_expire_the_tx() {
+        assert_eq!(confirm(None, Blockhash::RpcError, "Pending").await, TxOutcome::Unknown);
+    }
+
+    #[tokio::test]
+    async fn failed_bundle_fails_the_tx() {
+        assert_eq!(
+            confirm(None, Blockhash::Valid, "Failed").await,
+            TxOutcome::Failed { error: "Jito bundle failed".to_string() }
+        );
+    }
+
+    #[tokio::test]
+    async fn tx_landing_outside_its_failed_bundle_is_confirmed() {
+        // Pending for the first round of polls, visible on the last look after the bundle failed
+        let outcome = confirm(Some(BLOCKHASH_CHECK_EVERY as usize), Blockhash::Valid, "Failed").await;
+        assert_eq!(outcome, TxOutcome::Confirmed { slot: 7 });
+    }
+
+    #[tokio::test]
+    async fn tx_landing_as_the_blockhash_expires_is_confirmed() {
+        let outcome = confirm(Some(BLOCKHASH_CHECK_EVERY as usize), Blockhash::Invalid, "Pending").await;
+        assert_eq!(outcome, TxOutcome::Confirmed { slot: 7 });
+    }
+
+    #[test]
+    fn only_an_expired_tx_is_retried() {
+        assert!(TxOutcome::Expired.error().unwrap().code().is_retryable());
+        assert!(!TxOutcome::Unknown.error().unwrap().code().is_retryable());
+    }
+}
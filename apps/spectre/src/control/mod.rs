@@ -0,0 +1,290 @@
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::monitor::execute_exit;
+use crate::position::ExitReason;
+use crate::redis::{Delivery, Outbox, ResultPublisher};
+use crate::trader::SpectreTrader;
+
+/// Operator command from the control queue (backend / dashboard)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlRequest {
+    /// Echoed on the reply so the sender can match it
+    pub id: String,
+    /// List the reply is pushed to (None = the configured default reply key)
+    #[serde(default)]
+    pub reply_to: Option<String>,
+    #[serde(flatten)]
+    pub command: ControlCommand,
+}
+
+/// `{"command": "sell", "tokenMint": "...", "percent": 50}`, `{"command": "close_all"}`, ...
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum ControlCommand {
+    /// Sell `percent` of a position (None = all of it)
+    Sell {
+        token_mint: String,
+        #[serde(default)]
+        percent: Option<f64>,
+    },
+    /// Sell every open position
+    CloseAll,
+    /// Stop taking new entries (exits keep running)
+    Pause,
+    Resume,
+    MarkUnsellable {
+        token_mint: String,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    UnmarkUnsellable {
+        token_mint: String,
+    },
+    /// Move SL/TP (percent from entry, positive like the signal's)
+    SetExits {
+        token_mint: String,
+        #[serde(default)]
+        stop_loss_percent: Option<f64>,
+        #[serde(default)]
+        take_profit_percent: Option<f64>,
+        /// Allow the SL to go below the current stop (breakeven/profit lock, tightened)
+        #[serde(default)]
+        allow_lower: bool,
+    },
+    /// Positions, pending exits and the pause flag
+    DumpState,
+}
+
+impl ControlCommand {
+    /// Command name as sent on the wire ("sell", "close_all", ...)
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControlCommand::Sell { .. } => "sell",
+            ControlCommand::CloseAll => "close_all",
+            ControlCommand::Pause => "pause",
+            ControlCommand::Resume => "resume",
+            ControlCommand::MarkUnsellable { .. } => "mark_unsellable",
+            ControlCommand::UnmarkUnsellable { .. } => "unmark_unsellable",
+            ControlCommand::SetExits { .. } => "set_exits",
+            ControlCommand::DumpState => "dump_state",
+        }
+    }
+
+    /// Sells: running one twice (redelivered after a crash) would sell again
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, ControlCommand::Sell { .. } | ControlCommand::CloseAll)
+    }
+}
+
+/// Acknowledgement pushed to the reply key once a command is done
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandReply {
+    pub id: String,
+    pub command: String,
+    pub ok: bool,
+    pub message: String,
+    /// Command-specific payload (trade result, position, state dump)
+    #[serde(default)]
+    pub data: Option<Value>,
+    pub timestamp: String,
+}
+
+impl CommandReply {
+    /// Reply to a payload that isn't a valid command
+    pub fn rejected(id: &str, message: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            command: "unknown".to_string(),
+            ok: false,
+            message: message.to_string(),
+            data: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Carries out operator commands against the live trader
+pub struct ControlHandler {
+    trader: Arc<SpectreTrader>,
+    publisher: Arc<dyn ResultPublisher>,
+}
+
+impl ControlHandler {
+    pub fn new(trader: Arc<SpectreTrader>, publisher: Arc<dyn ResultPublisher>) -> Self {
+        Self { trader, publisher }
+    }
+
+    /// Run a command to completion (sells wait for the outcome) and build its reply
+    pub async fn handle(&self, request: &ControlRequest) -> CommandReply {
+        let command = request.command.name();
+        info!("🎛️ Command {} ({})", command, request.id);
+
+        let (ok, message, data) = match self.run(&request.command).await {
+            Ok((message, data)) => (true, message, data),
+            Err(e) => {
+                warn!("🎛️ Command {} ({}) failed: {}", command, request.id, e);
+                (false, e.to_string(), None)
+            }
+        };
+
+        CommandReply {
+            id: request.id.clone(),
+            command: command.to_string(),
+            ok,
+            message,
+            data,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    async fn run(&self, command: &ControlCommand) -> Result<(String, Option<Value>)> {
+        let positions = self.trader.position_manager();
+
+        match command {
+            ControlCommand::Sell { token_mint, percent } => {
+                let sell_percent = percent.unwrap_or(100.0);
+                if !(sell_percent > 0.0 && sell_percent <= 100.0) {
+                    bail!("percent must be in (0, 100], got {}", sell_percent);
+                }
+                if !positions.has_position(token_mint).await {
+                    bail!("No position for {}", token_mint);
+                }
+
+                let reason = ExitReason::Manual { sell_percent };
+                match execute_exit(&self.trader, self.publisher.as_ref(), token_mint, reason).await {
+                    Some(result) if result.success => Ok((
+                        format!("Sold {}% of {} for {:.4} SOL", sell_percent, result.token_symbol, result.amount_sol),
+                        Some(serde_json::to_value(&result)?),
+                    )),
+                    Some(result) => Err(anyhow!("Sell failed: {}", result.error.as_deref().unwrap_or("Unknown"))),
+                    None => Ok((format!("An exit is already running for {} - handed to it", token_mint), None)),
+                }
+            }
+
+            ControlCommand::CloseAll => {
+                let open = positions.get_all_positions().await;
+                let exits = open.iter().map(|position| async move {
+                    let result = execute_exit(&self.trader, self.publisher.as_ref(), &position.token_mint, ExitReason::manual()).await;
+                    (position, result)
+                });
+
+                let mut failed = Vec::new();
+                for (position, result) in futures::future::join_all(exits).await {
+                    if result.is_some_and(|r| !r.success) {
+                        failed.push(position.token_symbol.clone());
+                    }
+                }
+
+                if !failed.is_empty() {
+                    bail!("{} of {} position(s) failed to close: {}", failed.len(), open.len(), failed.join(", "));
+                }
+                Ok((format!("Closed {} position(s)", open.len()), None))
+            }
+
+            ControlCommand::Pause => {
+                let was_paused = self.trader.set_paused(true);
+                info!("⏸️ New entries paused");
+                Ok((if was_paused { "Already paused" } else { "New entries paused" }.to_string(), None))
+            }
+
+            ControlCommand::Resume => {
+                let was_paused = self.trader.set_paused(false);
+                info!("▶️ New entries resumed");
+                Ok((if was_paused { "New entries resumed" } else { "Not paused" }.to_string(), None))
+            }
+
+            ControlCommand::MarkUnsellable { token_mint, reason } => {
+                if !positions.has_position(token_mint).await {
+                    bail!("No position for {}", token_mint);
+                }
+                positions.mark_unsellable(token_mint, reason.as_deref().unwrap_or("operator command")).await;
+                Ok((format!("{} marked unsellable", token_mint), None))
+            }
+
+            ControlCommand::UnmarkUnsellable { token_mint } => {
+                if !positions.unmark_unsellable(token_mint).await {
+                    bail!("No position for {}", token_mint);
+                }
+                Ok((format!("{} marked sellable", token_mint), None))
+            }
+
+            ControlCommand::SetExits { token_mint, stop_loss_percent, take_profit_percent, allow_lower } => {
+                if stop_loss_percent.is_none() && take_profit_percent.is_none() {
+                    bail!("set_exits needs stopLossPercent and/or takeProfitPercent");
+                }
+                let position = positions
+                    .set_exit_levels(token_mint, *stop_loss_percent, *take_profit_percent, *allow_lower)
+                    .await?
+                    .ok_or_else(|| anyhow!("No position for {}", token_mint))?;
+                Ok((
+                    format!("{} SL ${:.10} | TP ${:.10}", position.token_symbol, position.stop_loss_price, position.take_profit_price),
+                    Some(serde_json::to_value(&position)?),
+                ))
+            }
+
+            ControlCommand::DumpState => {
+                let open = positions.get_all_positions().await;
+                let mut pending_exits = serde_json::Map::new();
+                for position in &open {
+                    if let Some(reason) = positions.pending_exit(&position.token_mint).await {
+                        pending_exits.insert(position.token_mint.clone(), json!(reason.code()));
+                    }
+                }
+                Ok((
+                    format!("{} open position(s)", open.len()),
+                    Some(json!({
+                        "paused": self.trader.is_paused(),
+                        "positions": open,
+                        "pendingExits": pending_exits,
+                    })),
+                ))
+            }
+        }
+    }
+
+    /// Handle commands as they arrive (each in its own task, so a slow sell doesn't hold up a pause),
+    /// push every reply to its reply key and ack the command once its reply is out.
+    /// Destructive commands are acked before they run instead: a crash mid-sell loses the command
+    /// (the operator sees no reply and can resend) rather than selling again on redelivery
+    pub async fn serve(
+        self: Arc<Self>,
+        mut command_rx: mpsc::UnboundedReceiver<Delivery<ControlRequest>>,
+        replies: Arc<Outbox>,
+        default_reply_key: String,
+    ) {
+        info!("🎛️ Control channel ready");
+
+        while let Some(request) = command_rx.recv().await {
+            let handler = self.clone();
+            let replies = replies.clone();
+            let reply_key = request.reply_to.clone().unwrap_or_else(|| default_reply_key.clone());
+
+            tokio::spawn(async move {
+                let command = request.item.clone();
+                let unacked = if command.command.is_destructive() {
+                    request.ack().await;
+                    None
+                } else {
+                    Some(request)
+                };
+
+                let reply = handler.handle(&command).await;
+                if let Err(e) = replies.publish_command_reply(&reply_key, &reply) {
+                    warn!("⚠️ Failed to publish reply to command {}: {}", reply.id, e);
+                }
+                if let Some(request) = unacked {
+                    request.ack().await;
+                }
+            });
+        }
+
+        info!("🎛️ Control channel stopped");
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify, OwnedSemaphorePermit, Semaphore};
+use tracing::{info, warn, error};
+
+use crate::capture::{self, CaptureEvent};
+use crate::error::ErrorCode;
+use crate::pumpportal::PumpPortalClient;
+use crate::redis::{Delivery, ResultPublisher, SpectreSignal};
+use crate::trader::SpectreTrader;
+
+/// Signals waiting behind the one executing for the same mint
+struct MintQueue {
+    running_type: String,
+    queued: VecDeque<Delivery<SpectreSignal>>,
+}
+
+impl MintQueue {
+    /// Same signal type already running or queued for this mint
+    fn has_type(&self, signal_type: &str) -> bool {
+        self.running_type.eq_ignore_ascii_case(signal_type)
+            || self.queued.iter().any(|s| s.signal_type.eq_ignore_ascii_case(signal_type))
+    }
+}
+
+/// Executes buy signals as concurrent tasks so a retrying buy doesn't hold up the rest
+/// - at most `max_in_flight` mints are worked on at the same time, started in arrival order
+/// - signals for one mint run one after another, in arrival order
+/// - a signal whose type is already running/queued for its mint is dropped as a duplicate
+/// - a signal is acked once it has been executed (or dropped)
+pub struct SignalDispatcher {
+    trader: Arc<SpectreTrader>,
+    pumpportal: Arc<PumpPortalClient>,
+    publisher: Arc<dyn ResultPublisher>,
+    permits: Arc<Semaphore>,
+    mints: Mutex<HashMap<String, MintQueue>>,
+    idle: Notify,
+}
+
+impl SignalDispatcher {
+    pub fn new(
+        trader: Arc<SpectreTrader>,
+        pumpportal: Arc<PumpPortalClient>,
+        publisher: Arc<dyn ResultPublisher>,
+        max_in_flight: usize,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            trader,
+            pumpportal,
+            publisher,
+            permits: Arc::new(Semaphore::new(max_in_flight.max(1))),
+            mints: Mutex::new(HashMap::new()),
+            idle: Notify::new(),
+        })
+    }
+
+    /// Start (or queue) execution of a signal - false if it was dropped as a duplicate
+    /// Waits for a free slot when `max_in_flight` mints are already being worked on
+    pub async fn dispatch(self: &Arc<Self>, signal: impl Into<Delivery<SpectreSignal>>) -> bool {
+        let mut signal = signal.into();
+        signal = match Self::enqueue(&mut *self.mints.lock().await, signal) {
+            Ok(queued) => return queued,
+            Err(signal) => signal,
+        };
+
+        let permit = self.permits.clone().acquire_owned().await.expect("dispatcher semaphore is never closed");
+
+        let mut mints = self.mints.lock().await;
+        // Another dispatch may have started this mint while we waited for the slot
+        signal = match Self::enqueue(&mut mints, signal) {
+            Ok(queued) => return queued,
+            Err(signal) => signal,
+        };
+        mints.insert(
+            signal.token_mint.clone(),
+            MintQueue { running_type: signal.signal_type.clone(), queued: VecDeque::new() },
+        );
+
+        let dispatcher = self.clone();
+        tokio::spawn(async move { dispatcher.run_mint(signal, permit).await });
+        true
+    }
+
+    /// Queue behind a busy mint: Ok(false) = duplicate, Ok(true) = queued, Err(signal) = mint is idle
+    #[allow(clippy::result_large_err)] // Err hands the signal back, it isn't an error
+    fn enqueue(mints: &mut HashMap<String, MintQueue>, signal: Delivery<SpectreSignal>) -> Result<bool, Delivery<SpectreSignal>> {
+        let Some(queue) = mints.get_mut(&signal.token_mint) else {
+            return Err(signal);
+        };
+        let short_mint = &signal.token_mint[..16.min(signal.token_mint.len())];
+
+        if queue.has_type(&signal.signal_type) {
+            info!("🔁 Duplicate {} signal for {} dropped - already in flight", signal.signal_type, short_mint);
+            // Acked without waiting for the ack - the queue lock is held here
+            tokio::spawn(signal.ack());
+            return Ok(false);
+        }
+        info!("⏳ {} signal for {} queued behind the running {} signal", signal.signal_type, short_mint, queue.running_type);
+        queue.queued.push_back(signal);
+        Ok(true)
+    }
+
+    /// Number of mints with a signal running or queued
+    pub async fn busy_mints(&self) -> usize {
+        self.mints.lock().await.len()
+    }
+
+    /// Wait until every dispatched signal has been executed
+    pub async fn drain(&self) {
+        loop {
+            let idle = self.idle.notified();
+            if self.mints.lock().await.is_empty() {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    /// Work through one mint's signals in order, then release the mint and its slot
+    async fn run_mint(&self, mut signal: Delivery<SpectreSignal>, _permit: OwnedSemaphorePermit) {
+        loop {
+            self.execute(&signal).await;
+            let token_mint = signal.token_mint.clone();
+            signal.ack().await;
+
+            let mut mints = self.mints.lock().await;
+            let next = mints.get_mut(&token_mint).and_then(|queue| {
+                let next = queue.queued.pop_front()?;
+                queue.running_type = next.signal_type.clone();
+                Some(next)
+            });
+
+            match next {
+                Some(next) => signal = next,
+                None => {
+                    mints.remove(&token_mint);
+                    if mints.is_empty() {
+                        self.idle.notify_waiters();
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Execute the buy, subscribe to prices and trigger wallets and publish the result
+    async fn execute(&self, signal: &SpectreSignal) {
+        match self.trader.execute_buy(signal).await {
+            Ok(result) => {
+                capture::record(CaptureEvent::TradeResult { result: Box::new(result.clone()) });
+
+                if result.success {
+                    info!("✅ Trade successful! {}", signal.token_symbol);
+                    info!("   TX: {}", result.tx_signature.as_deref().unwrap_or("N/A"));
+                    info!("   Latency: {}ms", result.latency_ms);
+
+                    // Subscribe to real-time price updates for this token
+                    if let Err(e) = self.pumpportal.subscribe_token(&signal.token_mint).await {
+                        warn!("⚠️ Failed to subscribe to price updates: {}", e);
+                    }
+
+                    // Watch the wallets we copied in, to copy them out
+                    let trigger_wallets: Vec<String> = signal.wallets.iter().map(|w| w.address.clone()).collect();
+                    if let Err(e) = self.pumpportal.subscribe_accounts(&trigger_wallets).await {
+                        warn!("⚠️ Failed to subscribe to trigger wallet trades: {}", e);
+                    }
+
+                    // Publish result back to Node.js
+                    if let Err(e) = self.publisher.publish_trade_result(&result).await {
+                        warn!("⚠️ Failed to publish trade result: {}", e);
+                    }
+                } else {
+                    warn!("❌ Trade failed ({}): {}", signal.token_symbol, result.error.as_deref().unwrap_or("Unknown"));
+
+                    // A TX was sent but never confirmed - report it so the backend doesn't count a phantom buy
+                    // Skipped buys (risk limits, stale signal, paused) are reported too, so the backend knows why
+                    if result.tx_signature.is_some() || result.error_code.is_some_and(ErrorCode::is_skipped_buy) {
+                        if let Err(e) = self.publisher.publish_trade_result(&result).await {
+                            warn!("⚠️ Failed to publish trade result: {}", e);
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                error!("❌ Trade error ({}): {}", signal.token_symbol, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Failures of the trading pipeline, classified where they enter the process
+/// (venue HTTP responses, Jito/RPC submission, on-chain outcomes)
+#[derive(Debug, Clone, Error)]
+pub enum SpectreError {
+    /// No pool/route can fill the swap (Jupiter COULD_NOT_FIND_ANY_ROUTE, untradable tokens)
+    #[error("{context} failed: no route ({message})")]
+    NoRoute { context: String, message: String },
+
+    /// Price moved past the slippage tolerance (quote or on-chain check)
+    #[error("{context} failed: slippage exceeded ({message})")]
+    SlippageExceeded { context: String, message: String },
+
+    /// Transaction blockhash is no longer valid - it can never land
+    #[error("Blockhash expired: {0}")]
+    BlockhashExpired(String),
+
+    /// Wallet can't cover the swap or fees
+    #[error("Insufficient funds: {0}")]
+    InsufficientFunds(String),
+
+    /// Venue/RPC request failed (transport error, non-2xx, unparseable response)
+    #[error("{context} failed: {message}")]
+    VenueHttp { context: String, status: Option<u16>, message: String },
+
+    /// Venue built a transaction we can't sign as fee payer
+    #[error("Signing failed: {0}")]
+    SignFailed(String),
+
+    /// Neither Jito nor the RPC accepted the transaction
+    #[error("Submit rejected: {0}")]
+    SubmitRejected(String),
+
+    /// Landed on-chain but the transaction itself failed
+    #[error("Failed on-chain: {0}")]
+    TxFailed(String),
+
+    /// Gave up waiting while the blockhash was still valid - the transaction may still land
+    #[error("Transaction status unknown: {0}")]
+    TxUnknown(String),
+
+    /// Partial exit abandoned because a full exit of the same position is queued behind it
+    #[error("Superseded by {0}")]
+    Superseded(String),
+
+    /// Buy refused by the portfolio risk limits - nothing was sent
+    #[error("Risk limit: {0}")]
+    RiskRejected(String),
+
+    /// Signal older than the max signal age for its type - nothing was sent
+    #[error("Stale signal: {0}")]
+    StaleSignal(String),
+
+    /// New entries paused by an operator command - nothing was sent
+    #[error("Paused: {0}")]
+    Paused(String),
+}
+
+/// Machine-readable error category (`TradeResult.errorCode`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NoRoute,
+    SlippageExceeded,
+    BlockhashExpired,
+    InsufficientFunds,
+    VenueHttp,
+    SignFailed,
+    SubmitRejected,
+    TxFailed,
+    TxUnknown,
+    Superseded,
+    RiskRejected,
+    StaleSignal,
+    Paused,
+}
+
+impl ErrorCode {
+    /// Worth another attempt within the same order (the next one gets a new quote/blockhash)
+    pub fn is_retryable(self) -> bool {
+        !matches!(
+            self,
+            ErrorCode::NoRoute
+                | ErrorCode::InsufficientFunds
+                | ErrorCode::SignFailed
+                | ErrorCode::TxUnknown
+                | ErrorCode::Superseded
+                | ErrorCode::RiskRejected
+                | ErrorCode::StaleSignal
+                | ErrorCode::Paused
+        )
+    }
+
+    /// Buy skipped before anything was sent - reported so the backend knows why
+    pub fn is_skipped_buy(self) -> bool {
+        matches!(self, ErrorCode::RiskRejected | ErrorCode::StaleSignal | ErrorCode::Paused)
+    }
+
+    /// Counts towards marking a position unsellable
+    pub fn is_failed_sell(self) -> bool {
+        self == ErrorCode::NoRoute
+    }
+}
+
+impl SpectreError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SpectreError::NoRoute { .. } => ErrorCode::NoRoute,
+            SpectreError::SlippageExceeded { .. } => ErrorCode::SlippageExceeded,
+            SpectreError::BlockhashExpired(_) => ErrorCode::BlockhashExpired,
+            SpectreError::InsufficientFunds(_) => ErrorCode::InsufficientFunds,
+            SpectreError::VenueHttp { .. } => ErrorCode::VenueHttp,
+            SpectreError::SignFailed(_) => ErrorCode::SignFailed,
+            SpectreError::SubmitRejected(_) => ErrorCode::SubmitRejected,
+            SpectreError::TxFailed(_) => ErrorCode::TxFailed,
+            SpectreError::TxUnknown(_) => ErrorCode::TxUnknown,
+            SpectreError::Superseded(_) => ErrorCode::Superseded,
+            SpectreError::RiskRejected(_) => ErrorCode::RiskRejected,
+            SpectreError::StaleSignal(_) => ErrorCode::StaleSignal,
+            SpectreError::Paused(_) => ErrorCode::Paused,
+        }
+    }
+
+    /// Code of an error that went through `anyhow` (None = not a pipeline error)
+    pub fn code_of(error: &anyhow::Error) -> Option<ErrorCode> {
+        error.downcast_ref::<SpectreError>().map(SpectreError::code)
+    }
+
+    /// Unclassified errors are retried, like before
+    pub fn is_retryable(error: &anyhow::Error) -> bool {
+        Self::code_of(error).is_none_or(ErrorCode::is_retryable)
+    }
+
+    /// Transport-level failure talking to a venue (`context` = "Jupiter quote", ...)
+    pub fn http(context: &str, error: impl std::fmt::Display) -> Self {
+        SpectreError::VenueHttp { context: context.to_string(), status: None, message: error.to_string() }
+    }
+
+    /// Non-2xx venue response, classified from its body
+    pub fn from_venue_response(context: &str, status: u16, body: &str) -> Self {
+        let lower = body.to_lowercase();
+        let context = context.to_string();
+        let message = body.to_string();
+
+        if lower.contains("could_not_find")
+            || lower.contains("could not find any route")
+            || lower.contains("no route")
+            || lower.contains("not_tradable")
+            || lower.contains("not tradable")
+        {
+            SpectreError::NoRoute { context, message }
+        } else if lower.contains("slippage") {
+            SpectreError::SlippageExceeded { context, message }
+        } else if lower.contains("insufficient") {
+            SpectreError::InsufficientFunds(format!("{}: {}", context, message))
+        } else {
+            SpectreError::VenueHttp { context, status: Some(status), message }
+        }
+    }
+
+    /// Transaction/simulation error text (RPC preflight, landed-but-failed status)
+    /// None if it matches no known category
+    pub fn from_transaction_error(error: &str) -> Option<Self> {
+        let lower = error.to_lowercase();
+        let message = error.to_string();
+
+        // Jupiter SlippageToleranceExceeded (6001), pump.fun TooMuchSolRequired/TooLittleSolReceived (6002/6003)
+        if lower.contains("slippage") || ["0x1771", "0x1772", "0x1773"].iter().any(|c| lower.contains(c)) {
+            return Some(SpectreError::SlippageExceeded { context: "Transaction".to_string(), message });
+        }
+        // SPL token InsufficientFunds is custom error 0x1
+        if lower.contains("insufficient") || lower.ends_with("custom program error: 0x1") {
+            return Some(SpectreError::InsufficientFunds(message));
+        }
+        if lower.contains("blockhash not found") || lower.contains("blockhashnotfound") {
+            return Some(SpectreError::BlockhashExpired(message));
+        }
+        None
+    }
+}
@@ -3,12 +3,13 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use solana_sdk::{
     pubkey::Pubkey,
-    signature::{Keypair, Signer},
     system_instruction,
     transaction::VersionedTransaction,
 };
 use std::str::FromStr;
-use tracing::{info, warn};
+use tracing::info;
+
+use crate::error::SpectreError;
 
 // Jito tip accounts (rotate between them)
 const JITO_TIP_ACCOUNTS: &[&str] = &[
@@ -32,7 +33,6 @@ struct JitoBundleRequest {
 
 #[derive(Debug, Deserialize)]
 struct JitoBundleResponse {
-    jsonrpc: String,
     result: Option<String>,
     error: Option<JitoError>,
 }
@@ -43,12 +43,6 @@ struct JitoError {
     message: String,
 }
 
-#[derive(Debug, Deserialize)]
-struct JitoTipResponse {
-    jsonrpc: String,
-    result: Option<Vec<String>>,
-}
-
 pub struct JitoClient {
     client: Client,
     block_engine_url: String,
@@ -106,22 +100,30 @@ impl JitoClient {
             .post(&url)
             .json(&request)
             .send()
-            .await?;
+            .await
+            .map_err(|e| SpectreError::http("Jito bundle submission", e))?;
 
         let elapsed = start.elapsed();
 
         if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Jito bundle submission failed: {}", error_text));
+            let status = response.status().as_u16();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(SpectreError::SubmitRejected(format!("Jito bundle submission {}: {}", status, error_text)).into());
         }
 
-        let bundle_response: JitoBundleResponse = response.json().await?;
+        let bundle_response: JitoBundleResponse = response.json().await
+            .map_err(|e| SpectreError::http("Jito bundle submission", e))?;
 
         if let Some(error) = bundle_response.error {
-            return Err(anyhow!("Jito error: {} (code: {})", error.message, error.code));
+            // Simulation failures carry the program error (slippage, funds)
+            let message = format!("Jito error: {} (code: {})", error.message, error.code);
+            return Err(SpectreError::from_transaction_error(&error.message)
+                .unwrap_or(SpectreError::SubmitRejected(message))
+                .into());
         }
 
-        let bundle_id = bundle_response.result.ok_or_else(|| anyhow!("No bundle ID returned"))?;
+        let bundle_id = bundle_response.result
+            .ok_or_else(|| SpectreError::SubmitRejected("No bundle ID returned".to_string()))?;
 
         info!(
             "🚀 Jito bundle sent: {} (took: {:?})",
@@ -174,6 +176,47 @@ impl JitoClient {
 
         Ok(BundleStatus::Unknown)
     }
+
+    /// Check in-flight bundle status (last ~5 minutes)
+    /// Unlike getBundleStatuses this also reports bundles that were dropped
+    pub async fn get_inflight_bundle_status(&self, bundle_id: &str) -> Result<BundleStatus> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getInflightBundleStatuses",
+            "params": [[bundle_id]]
+        });
+
+        let url = format!("{}/api/v1/getInflightBundleStatuses", self.block_engine_url);
+
+        let response = self.client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Failed to get in-flight bundle status: {}", error_text));
+        }
+
+        let result: serde_json::Value = response.json().await?;
+
+        let status = result
+            .get("result")
+            .and_then(|r| r.get("value"))
+            .and_then(|v| v.as_array())
+            .and_then(|arr| arr.first())
+            .and_then(|first| first.get("status"))
+            .and_then(|s| s.as_str());
+
+        Ok(match status {
+            Some("Pending") => BundleStatus::Pending,
+            Some("Landed") => BundleStatus::Processed,
+            Some("Failed") => BundleStatus::Failed,
+            _ => BundleStatus::Unknown, // "Invalid" = not found (yet)
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -0,0 +1,693 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::mpsc;
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+use crate::position::{ExitReason, Position};
+use crate::redis::SpectreSignal;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS signals (
+    id               INTEGER PRIMARY KEY,
+    received_at      TEXT NOT NULL,
+    token_mint       TEXT NOT NULL,
+    token_symbol     TEXT NOT NULL,
+    signal_type      TEXT NOT NULL,
+    strength         TEXT NOT NULL,
+    market_cap_usd   REAL,
+    liquidity_usd    REAL,
+    entry_price_usd  REAL,
+    signal_timestamp TEXT NOT NULL,
+    payload          TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS orders (
+    id             INTEGER PRIMARY KEY,
+    created_at     TEXT NOT NULL,
+    side           TEXT NOT NULL,
+    venue          TEXT NOT NULL,
+    token_mint     TEXT NOT NULL,
+    token_symbol   TEXT NOT NULL,
+    attempt_number INTEGER NOT NULL,
+    success        INTEGER NOT NULL,
+    latency_ms     INTEGER NOT NULL,
+    tx_signature   TEXT,
+    error          TEXT
+);
+
+CREATE TABLE IF NOT EXISTS fills (
+    id            INTEGER PRIMARY KEY,
+    created_at    TEXT NOT NULL,
+    position_id   INTEGER NOT NULL,
+    side          TEXT NOT NULL,
+    token_mint    TEXT NOT NULL,
+    amount_tokens INTEGER NOT NULL,
+    amount_sol    REAL NOT NULL,
+    price_usd     REAL,
+    tx_signature  TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS positions (
+    id               INTEGER PRIMARY KEY,
+    token_mint       TEXT NOT NULL,
+    token_symbol     TEXT NOT NULL,
+    signal_type      TEXT NOT NULL,
+    strategy         TEXT,
+    opened_at        TEXT NOT NULL,
+    closed_at        TEXT,
+    entry_price_usd  REAL NOT NULL,
+    cost_sol         REAL NOT NULL,
+    tokens_bought    INTEGER NOT NULL,
+    tokens_sold      INTEGER NOT NULL DEFAULT 0,
+    cost_closed_sol  REAL NOT NULL DEFAULT 0,
+    proceeds_sol     REAL NOT NULL DEFAULT 0,
+    realized_pnl_sol REAL NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS exits (
+    id               INTEGER PRIMARY KEY,
+    created_at       TEXT NOT NULL,
+    position_id      INTEGER NOT NULL,
+    token_mint       TEXT NOT NULL,
+    reason           TEXT NOT NULL,
+    tokens_sold      INTEGER NOT NULL,
+    proceeds_sol     REAL NOT NULL,
+    cost_basis_sol   REAL NOT NULL,
+    realized_pnl_sol REAL NOT NULL,
+    closes_position  INTEGER NOT NULL,
+    tx_signature     TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_positions_open ON positions (token_mint, closed_at);
+CREATE INDEX IF NOT EXISTS idx_exits_created ON exits (created_at);
+CREATE INDEX IF NOT EXISTS idx_orders_created ON orders (created_at);
+";
+
+/// What an order is for - shared by all of its attempts
+pub struct OrderContext<'a> {
+    pub side: &'a str,  // "buy" or "sell"
+    pub venue: &'a str, // "pumpfun" or "jupiter"
+    pub token_mint: &'a str,
+    pub token_symbol: &'a str,
+}
+
+/// A confirmed sell, recorded against the open journal position
+pub struct ExitFill<'a> {
+    pub reason: &'a ExitReason,
+    pub tokens_sold: u64,
+    pub sol_received: f64,
+    pub price_usd: Option<f64>,
+    pub closes_position: bool,
+    pub tx_signature: &'a str,
+}
+
+/// Aggregated stats for one UTC day
+#[derive(Debug, Default, Clone)]
+pub struct DailyReport {
+    pub positions_closed: u32,
+    pub wins: u32,
+    pub exits: u32,
+    pub realized_pnl_sol: f64,
+    pub orders: u32,
+    pub failed_orders: u32,
+    pub avg_latency_ms: f64,
+}
+
+impl DailyReport {
+    pub fn win_rate(&self) -> f64 {
+        if self.positions_closed == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.positions_closed as f64 * 100.0
+    }
+}
+
+/// Work for the journal thread
+type Job = Box<dyn FnOnce(&mut Connection) + Send>;
+
+/// Local SQLite journal of signals, order attempts, fills and exits
+/// Source of truth for audit and realized PnL - independent of Redis delivery.
+/// Writes never fail a trade: errors are logged and the trade goes on.
+/// All SQLite I/O runs on a dedicated thread, in call order, so callers never block on disk.
+pub struct TradeJournal {
+    jobs: mpsc::Sender<Job>,
+}
+
+impl TradeJournal {
+    /// Open (or create) the journal database
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+        }
+
+        let mut conn = Connection::open(path)
+            .with_context(|| format!("Failed to open trade journal {}", path.display()))?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.execute_batch(SCHEMA)?;
+
+        let (jobs, queue) = mpsc::channel::<Job>();
+        std::thread::Builder::new()
+            .name("trade-journal".to_string())
+            .spawn(move || {
+                for job in queue {
+                    job(&mut conn);
+                }
+            })
+            .context("Failed to start trade journal thread")?;
+
+        Ok(Self { jobs })
+    }
+
+    fn now() -> String {
+        Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+    }
+
+    /// Queue a write - it runs in its own transaction, so a failed statement leaves nothing half-written
+    fn write(&self, what: &'static str, f: impl FnOnce(&Connection) -> rusqlite::Result<()> + Send + 'static) {
+        let job: Job = Box::new(move |conn| {
+            let result = conn.transaction().and_then(|tx| {
+                f(&tx)?;
+                tx.commit()
+            });
+            if let Err(e) = result {
+                warn!("⚠️ Failed to journal {}: {}", what, e);
+            }
+        });
+        if self.jobs.send(job).is_err() {
+            warn!("⚠️ Failed to journal {}: journal thread is gone", what);
+        }
+    }
+
+    /// Run a query after every write queued before it
+    async fn read<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&Connection) -> rusqlite::Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let (reply, result) = oneshot::channel();
+        self.jobs
+            .send(Box::new(move |conn| {
+                let _ = reply.send(f(conn));
+            }))
+            .map_err(|_| anyhow!("Trade journal thread is gone"))?;
+        Ok(result.await.context("Trade journal thread is gone")??)
+    }
+
+    /// Wait until every queued write has been committed
+    pub async fn flush(&self) {
+        if let Err(e) = self.read(|_| Ok(())).await {
+            warn!("⚠️ Failed to flush trade journal: {}", e);
+        }
+    }
+
+    /// Record a signal as received from Redis
+    pub fn record_signal(&self, signal: &SpectreSignal) {
+        let payload = serde_json::to_string(signal).unwrap_or_default();
+        let signal = signal.clone();
+        self.write("signal", move |conn| {
+            conn.execute(
+                "INSERT INTO signals (received_at, token_mint, token_symbol, signal_type, strength,
+                    market_cap_usd, liquidity_usd, entry_price_usd, signal_timestamp, payload)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    Self::now(),
+                    signal.token_mint,
+                    signal.token_symbol,
+                    signal.signal_type,
+                    signal.strength,
+                    signal.market_cap_usd,
+                    signal.liquidity_usd,
+                    signal.entry_price_usd,
+                    signal.timestamp,
+                    payload,
+                ],
+            )?;
+            Ok(())
+        });
+    }
+
+    /// Record a single order attempt (every retry is its own row, error = None means success)
+    pub fn record_attempt(
+        &self,
+        order: &OrderContext,
+        attempt_number: u32,
+        latency_ms: u64,
+        tx_signature: Option<&str>,
+        error: Option<&str>,
+    ) {
+        let (side, venue) = (order.side.to_string(), order.venue.to_string());
+        let (token_mint, token_symbol) = (order.token_mint.to_string(), order.token_symbol.to_string());
+        let tx_signature = tx_signature.map(String::from);
+        let error = error.map(String::from);
+        self.write("order", move |conn| {
+            conn.execute(
+                "INSERT INTO orders (created_at, side, venue, token_mint, token_symbol,
+                    attempt_number, success, latency_ms, tx_signature, error)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    Self::now(),
+                    side,
+                    venue,
+                    token_mint,
+                    token_symbol,
+                    attempt_number,
+                    error.is_none(),
+                    latency_ms as i64,
+                    tx_signature,
+                    error,
+                ],
+            )?;
+            Ok(())
+        });
+    }
+
+    /// Record a confirmed buy: opens a journal position and its buy fill
+    pub fn record_entry(&self, position: &Position, sol_spent: f64) {
+        let position = position.clone();
+        self.write("entry", move |conn| {
+            let position_id = Self::insert_position(conn, &position, sol_spent)?;
+            conn.execute(
+                "INSERT INTO fills (created_at, position_id, side, token_mint, amount_tokens, amount_sol, price_usd, tx_signature)
+                 VALUES (?1, ?2, 'buy', ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    Self::now(),
+                    position_id,
+                    position.token_mint,
+                    position.amount_tokens as i64,
+                    sol_spent,
+                    position.entry_price,
+                    position.tx_signature,
+                ],
+            )?;
+            Ok(())
+        });
+    }
+
+    /// Record a position we started tracking without seeing the buy (reconciliation)
+    pub fn record_adopted(&self, position: &Position) {
+        let position = position.clone();
+        self.write("adopted position", move |conn| {
+            Self::insert_position(conn, &position, position.amount_sol_invested).map(|_| ())
+        });
+    }
+
+    fn insert_position(conn: &Connection, position: &Position, cost_sol: f64) -> rusqlite::Result<i64> {
+        conn.execute(
+            "INSERT INTO positions (token_mint, token_symbol, signal_type, strategy, opened_at,
+                entry_price_usd, cost_sol, tokens_bought)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                position.token_mint,
+                position.token_symbol,
+                position.signal_type,
+                position.exit_strategy.as_ref().map(|s| s.name.clone()),
+                position.entry_time.to_rfc3339_opts(SecondsFormat::Millis, true),
+                position.entry_price,
+                cost_sol,
+                position.original_amount_tokens.max(position.amount_tokens) as i64,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Record a confirmed (partial or full) sell and realize PnL for it
+    /// Cost basis is proportional to tokens sold; the closing sell takes whatever cost is left.
+    pub fn record_exit(&self, position: &Position, exit: &ExitFill) {
+        let position = position.clone();
+        let reason = exit.reason.code();
+        let (tokens_sold, sol_received, price_usd) = (exit.tokens_sold, exit.sol_received, exit.price_usd);
+        let closes_position = exit.closes_position;
+        let tx_signature = exit.tx_signature.to_string();
+        self.write("exit", move |conn| {
+            let open: Option<(i64, f64, i64, f64)> = conn
+                .query_row(
+                    "SELECT id, cost_sol, tokens_bought, cost_closed_sol FROM positions
+                     WHERE token_mint = ?1 AND closed_at IS NULL ORDER BY id DESC LIMIT 1",
+                    params![position.token_mint],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                )
+                .optional()?;
+
+            // Position opened before the journal existed - start tracking it now
+            let (position_id, cost_sol, tokens_bought, cost_closed_sol) = match open {
+                Some(open) => open,
+                None => {
+                    let id = Self::insert_position(conn, &position, position.amount_sol_invested)?;
+                    let tokens = position.original_amount_tokens.max(position.amount_tokens) as i64;
+                    (id, position.amount_sol_invested, tokens, 0.0)
+                }
+            };
+
+            let cost_basis = if closes_position || tokens_bought <= 0 {
+                cost_sol - cost_closed_sol
+            } else {
+                (cost_sol * tokens_sold as f64 / tokens_bought as f64).min(cost_sol - cost_closed_sol)
+            };
+            let realized_pnl = sol_received - cost_basis;
+            let now = Self::now();
+
+            conn.execute(
+                "INSERT INTO fills (created_at, position_id, side, token_mint, amount_tokens, amount_sol, price_usd, tx_signature)
+                 VALUES (?1, ?2, 'sell', ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    now,
+                    position_id,
+                    position.token_mint,
+                    tokens_sold as i64,
+                    sol_received,
+                    price_usd,
+                    tx_signature,
+                ],
+            )?;
+            conn.execute(
+                "INSERT INTO exits (created_at, position_id, token_mint, reason, tokens_sold, proceeds_sol,
+                    cost_basis_sol, realized_pnl_sol, closes_position, tx_signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    now,
+                    position_id,
+                    position.token_mint,
+                    reason,
+                    tokens_sold as i64,
+                    sol_received,
+                    cost_basis,
+                    realized_pnl,
+                    closes_position,
+                    tx_signature,
+                ],
+            )?;
+            conn.execute(
+                "UPDATE positions SET
+                    tokens_sold = tokens_sold + ?2,
+                    cost_closed_sol = cost_closed_sol + ?3,
+                    proceeds_sol = proceeds_sol + ?4,
+                    realized_pnl_sol = realized_pnl_sol + ?5,
+                    closed_at = CASE WHEN ?6 THEN ?7 ELSE closed_at END
+                 WHERE id = ?1",
+                params![
+                    position_id,
+                    tokens_sold as i64,
+                    cost_basis,
+                    sol_received,
+                    realized_pnl,
+                    closes_position,
+                    now,
+                ],
+            )?;
+
+            info!(
+                "📒 Journaled {} exit for {}: {:.4} SOL received | realized PnL {:+.4} SOL",
+                reason,
+                position.token_symbol,
+                sol_received,
+                realized_pnl
+            );
+            Ok(())
+        });
+    }
+
+    /// Realized PnL of today's exits (UTC), partial exits included
+    pub async fn realized_pnl_today(&self) -> Result<f64> {
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+        self.read(move |conn| {
+            conn.query_row(
+                "SELECT COALESCE(SUM(realized_pnl_sol), 0) FROM exits WHERE created_at >= ?1",
+                params![today],
+                |row| row.get::<_, f64>(0),
+            )
+        })
+        .await
+    }
+
+    /// Per-day stats for the last `days` days (UTC), oldest first
+    pub async fn daily_report(&self, days: u32) -> Result<BTreeMap<String, DailyReport>> {
+        let since = (Utc::now() - chrono::Duration::days(days.saturating_sub(1) as i64))
+            .format("%Y-%m-%d")
+            .to_string();
+
+        self.read(move |conn| {
+            let mut report: BTreeMap<String, DailyReport> = BTreeMap::new();
+
+            let mut stmt = conn.prepare(
+                "SELECT date(closed_at), COUNT(*), SUM(realized_pnl_sol > 0)
+                 FROM positions WHERE closed_at >= ?1 GROUP BY 1",
+            )?;
+            let rows = stmt.query_map(params![since], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?, row.get::<_, u32>(2)?))
+            })?;
+            for row in rows {
+                let (day, closed, wins) = row?;
+                let entry = report.entry(day).or_default();
+                entry.positions_closed = closed;
+                entry.wins = wins;
+            }
+
+            // Realized PnL by exit date, so partial exits count on the day they happened
+            let mut stmt = conn.prepare(
+                "SELECT date(created_at), COUNT(*), SUM(realized_pnl_sol)
+                 FROM exits WHERE created_at >= ?1 GROUP BY 1",
+            )?;
+            let rows = stmt.query_map(params![since], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?, row.get::<_, f64>(2)?))
+            })?;
+            for row in rows {
+                let (day, exits, pnl) = row?;
+                let entry = report.entry(day).or_default();
+                entry.exits = exits;
+                entry.realized_pnl_sol = pnl;
+            }
+
+            let mut stmt = conn.prepare(
+                "SELECT date(created_at), COUNT(*), SUM(success = 0), AVG(latency_ms)
+                 FROM orders WHERE created_at >= ?1 GROUP BY 1",
+            )?;
+            let rows = stmt.query_map(params![since], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, u32>(1)?,
+                    row.get::<_, u32>(2)?,
+                    row.get::<_, f64>(3)?,
+                ))
+            })?;
+            for row in rows {
+                let (day, orders, failed, latency) = row?;
+                let entry = report.entry(day).or_default();
+                entry.orders = orders;
+                entry.failed_orders = failed;
+                entry.avg_latency_ms = latency;
+            }
+
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Print the daily PnL / win-rate report to stdout
+    pub async fn print_report(&self, days: u32) -> Result<()> {
+        let report = self.daily_report(days).await?;
+
+        println!(
+            "{:<10}  {:>6}  {:>5}  {:>7}  {:>6}  {:>12}  {:>7}  {:>7}  {:>9}",
+            "day", "closed", "wins", "win%", "exits", "pnl (SOL)", "orders", "failed", "avg ms"
+        );
+
+        let mut total = DailyReport::default();
+        for (day, stats) in &report {
+            println!(
+                "{:<10}  {:>6}  {:>5}  {:>6.1}%  {:>6}  {:>+12.4}  {:>7}  {:>7}  {:>9.0}",
+                day,
+                stats.positions_closed,
+                stats.wins,
+                stats.win_rate(),
+                stats.exits,
+                stats.realized_pnl_sol,
+                stats.orders,
+                stats.failed_orders,
+                stats.avg_latency_ms
+            );
+            total.positions_closed += stats.positions_closed;
+            total.wins += stats.wins;
+            total.exits += stats.exits;
+            total.realized_pnl_sol += stats.realized_pnl_sol;
+            total.orders += stats.orders;
+            total.failed_orders += stats.failed_orders;
+        }
+
+        println!(
+            "{:<10}  {:>6}  {:>5}  {:>6.1}%  {:>6}  {:>+12.4}  {:>7}  {:>7}",
+            "total",
+            total.positions_closed,
+            total.wins,
+            total.win_rate(),
+            total.exits,
+            total.realized_pnl_sol,
+            total.orders,
+            total.failed_orders
+        );
+
+        let open: u32 = self
+            .read(|conn| conn.query_row("SELECT COUNT(*) FROM positions WHERE closed_at IS NULL", [], |row| row.get(0)))
+            .await?;
+        println!("open positions: {}", open);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COST: f64 = 0.1;
+    const TOKENS: u64 = 1_000_000;
+
+    fn journal() -> (tempfile::TempDir, TradeJournal) {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = TradeJournal::open(dir.path().join("journal.db")).unwrap();
+        (dir, journal)
+    }
+
+    fn position() -> Position {
+        Position::new("MINT".into(), "TEST".into(), 0.001, TOKENS, COST, 25.0, 100.0, "sig".into(), false)
+    }
+
+    fn exit(reason: &ExitReason, tokens_sold: u64, sol_received: f64, closes_position: bool) -> ExitFill<'_> {
+        ExitFill { reason, tokens_sold, sol_received, price_usd: None, closes_position, tx_signature: "sig" }
+    }
+
+    /// (cost_basis_sol, realized_pnl_sol) of every exit, oldest first
+    async fn exits(journal: &TradeJournal) -> Vec<(f64, f64)> {
+        journal
+            .read(|conn| {
+                let mut stmt = conn.prepare("SELECT cost_basis_sol, realized_pnl_sol FROM exits ORDER BY id")?;
+                let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                rows.collect()
+            })
+            .await
+            .unwrap()
+    }
+
+    /// (cost_closed_sol, proceeds_sol, realized_pnl_sol, closed) of the journal position
+    async fn journal_position(journal: &TradeJournal) -> (f64, f64, f64, bool) {
+        journal
+            .read(|conn| {
+                conn.query_row(
+                    "SELECT cost_closed_sol, proceeds_sol, realized_pnl_sol, closed_at IS NOT NULL FROM positions",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                )
+            })
+            .await
+            .unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-12, "expected {}, got {}", expected, actual);
+    }
+
+    #[tokio::test]
+    async fn full_exit_realizes_proceeds_minus_cost() {
+        let (_dir, journal) = journal();
+        let position = position();
+        journal.record_entry(&position, COST);
+
+        journal.record_exit(&position, &exit(&ExitReason::TakeProfit, TOKENS, 0.25, true));
+
+        let exits = exits(&journal).await;
+        assert_eq!(exits.len(), 1);
+        assert_close(exits[0].0, COST);
+        assert_close(exits[0].1, 0.15);
+        let (cost_closed, proceeds, pnl, closed) = journal_position(&journal).await;
+        assert_close(cost_closed, COST);
+        assert_close(proceeds, 0.25);
+        assert_close(pnl, 0.15);
+        assert!(closed);
+        assert_close(journal.realized_pnl_today().await.unwrap(), 0.15);
+    }
+
+    #[tokio::test]
+    async fn partial_exits_take_proportional_cost_and_the_close_takes_the_rest() {
+        let (_dir, journal) = journal();
+        let position = position();
+        journal.record_entry(&position, COST);
+        let partial = ExitReason::Manual { sell_percent: 25.0 };
+
+        // 25% of the tokens carry 25% of the cost
+        journal.record_exit(&position, &exit(&partial, TOKENS / 4, 0.05, false));
+        assert!(!journal_position(&journal).await.3, "still open after a partial exit");
+
+        // The closing sell reports fewer tokens than are left (fees, rounding) - it still takes all remaining cost
+        journal.record_exit(&position, &exit(&ExitReason::StopLoss, TOKENS / 2, 0.03, true));
+
+        let exits = exits(&journal).await;
+        assert_close(exits[0].0, 0.025);
+        assert_close(exits[0].1, 0.025);
+        assert_close(exits[1].0, 0.075);
+        assert_close(exits[1].1, -0.045);
+        let (cost_closed, proceeds, pnl, closed) = journal_position(&journal).await;
+        assert_close(cost_closed, COST);
+        assert_close(proceeds, 0.08);
+        assert_close(pnl, -0.02);
+        assert!(closed);
+        assert_close(journal.realized_pnl_today().await.unwrap(), -0.02);
+    }
+
+    #[tokio::test]
+    async fn partial_cost_never_exceeds_what_is_left() {
+        let (_dir, journal) = journal();
+        let position = position();
+        journal.record_entry(&position, COST);
+        let partial = ExitReason::Manual { sell_percent: 80.0 };
+
+        journal.record_exit(&position, &exit(&partial, TOKENS * 8 / 10, 0.2, false));
+        // Reports more tokens than remain
+        journal.record_exit(&position, &exit(&partial, TOKENS * 8 / 10, 0.2, false));
+
+        let exits = exits(&journal).await;
+        assert_close(exits[0].0, 0.08);
+        assert_close(exits[1].0, 0.02);
+        assert_close(journal_position(&journal).await.0, COST);
+    }
+
+    #[tokio::test]
+    async fn exit_without_an_entry_uses_the_invested_amount() {
+        let (_dir, journal) = journal();
+        let position = position();
+
+        journal.record_exit(&position, &exit(&ExitReason::StopLoss, TOKENS, 0.04, true));
+
+        let exits = exits(&journal).await;
+        assert_close(exits[0].0, COST);
+        assert_close(exits[0].1, -0.06);
+        assert!(journal_position(&journal).await.3);
+    }
+
+    #[tokio::test]
+    async fn daily_report_counts_wins_and_exits() {
+        let (_dir, journal) = journal();
+        let winner = position();
+        let loser = Position::new("LOSER".into(), "LOSE".into(), 0.001, TOKENS, COST, 25.0, 100.0, "sig".into(), false);
+        journal.record_entry(&winner, COST);
+        journal.record_entry(&loser, COST);
+
+        journal.record_exit(&winner, &exit(&ExitReason::Manual { sell_percent: 50.0 }, TOKENS / 2, 0.1, false));
+        journal.record_exit(&winner, &exit(&ExitReason::TakeProfit, TOKENS / 2, 0.1, true));
+        journal.record_exit(&loser, &exit(&ExitReason::StopLoss, TOKENS, 0.05, true));
+
+        let report = journal.daily_report(1).await.unwrap();
+        let today = report.get(&Utc::now().format("%Y-%m-%d").to_string()).expect("today");
+        assert_eq!(today.positions_closed, 2);
+        assert_eq!(today.wins, 1);
+        assert_eq!(today.exits, 3);
+        assert_close(today.realized_pnl_sol, 0.05);
+        assert_close(today.win_rate(), 50.0);
+    }
+}
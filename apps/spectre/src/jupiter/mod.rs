@@ -1,4 +1,5 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
+use async_trait::async_trait;
 use base64::Engine;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -8,6 +9,9 @@ use solana_sdk::{
 };
 use tracing::info;
 
+use crate::error::SpectreError;
+use crate::venue::{BuyOrder, RetryPolicy, SellOrder, Side, SwapTx, SwapVenue};
+
 // Jupiter API v1 (2025+) - requires API key from portal.jup.ag
 const JUPITER_QUOTE_API: &str = "https://api.jup.ag/swap/v1/quote";
 const JUPITER_SWAP_API: &str = "https://api.jup.ag/swap/v1/swap";
@@ -109,6 +113,18 @@ impl JupiterClient {
         }
     }
 
+    /// Client on a custom API base (`{base}/quote`, `{base}/swap`) - self-hosted or mock
+    pub fn with_base_url(api_key: Option<String>, base_url: &str) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        info!("🔧 Using Jupiter API at {}", base_url);
+
+        Self {
+            quote_url: format!("{}/quote", base_url),
+            swap_url: format!("{}/swap", base_url),
+            ..Self::with_api_key(api_key)
+        }
+    }
+
     /// Get quote for swapping SOL to token
     pub async fn get_quote(
         &self,
@@ -132,16 +148,19 @@ impl JupiterClient {
             request = request.header("x-api-key", api_key);
         }
 
-        let response = request.send().await?;
+        let response = request.send().await
+            .map_err(|e| SpectreError::http("Jupiter quote", e))?;
 
         let elapsed = start.elapsed();
 
         if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Jupiter quote failed: {}", error_text));
+            let status = response.status().as_u16();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(SpectreError::from_venue_response("Jupiter quote", status, &error_text).into());
         }
 
-        let quote: QuoteResponse = response.json().await?;
+        let quote: QuoteResponse = response.json().await
+            .map_err(|e| SpectreError::http("Jupiter quote", e))?;
 
         info!(
             "📊 Jupiter quote: {} SOL -> {} tokens (impact: {}%, took: {:?})",
@@ -182,22 +201,27 @@ impl JupiterClient {
             request = request.header("x-api-key", api_key);
         }
 
-        let response = request.send().await?;
+        let response = request.send().await
+            .map_err(|e| SpectreError::http("Jupiter swap request", e))?;
 
         let elapsed = start.elapsed();
 
         if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Jupiter swap request failed: {}", error_text));
+            let status = response.status().as_u16();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(SpectreError::from_venue_response("Jupiter swap request", status, &error_text).into());
         }
 
-        let swap_response: SwapResponse = response.json().await?;
+        let swap_response: SwapResponse = response.json().await
+            .map_err(|e| SpectreError::http("Jupiter swap request", e))?;
 
         // Decode the transaction
         let tx_bytes = base64::engine::general_purpose::STANDARD
-            .decode(&swap_response.swap_transaction)?;
+            .decode(&swap_response.swap_transaction)
+            .map_err(|e| SpectreError::http("Jupiter swap decode", e))?;
 
-        let transaction: VersionedTransaction = bincode::deserialize(&tx_bytes)?;
+        let transaction: VersionedTransaction = bincode::deserialize(&tx_bytes)
+            .map_err(|e| SpectreError::http("Jupiter swap decode", e))?;
 
         info!(
             "🔄 Jupiter swap tx prepared (took: {:?}, valid until block: {})",
@@ -231,16 +255,19 @@ impl JupiterClient {
             request = request.header("x-api-key", api_key);
         }
 
-        let response = request.send().await?;
+        let response = request.send().await
+            .map_err(|e| SpectreError::http("Jupiter sell quote", e))?;
 
         let elapsed = start.elapsed();
 
         if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Jupiter sell quote failed: {}", error_text));
+            let status = response.status().as_u16();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(SpectreError::from_venue_response("Jupiter sell quote", status, &error_text).into());
         }
 
-        let quote: QuoteResponse = response.json().await?;
+        let quote: QuoteResponse = response.json().await
+            .map_err(|e| SpectreError::http("Jupiter sell quote", e))?;
 
         info!(
             "📊 Jupiter sell quote: {} tokens -> {} SOL (impact: {}%, took: {:?})",
@@ -254,6 +281,73 @@ impl JupiterClient {
     }
 }
 
+/// Raw output amount of a quote
+fn quoted_out(quote: &QuoteResponse) -> Option<u64> {
+    quote.out_amount.parse().ok()
+}
+
+#[async_trait]
+impl SwapVenue for JupiterClient {
+    fn name(&self) -> &'static str {
+        "jupiter"
+    }
+
+    fn label(&self) -> &'static str {
+        "JUPITER"
+    }
+
+    fn retry_policy(&self, side: Side) -> RetryPolicy {
+        match side {
+            Side::Buy => RetryPolicy { max_attempts: 2, retry_delay_ms: 500 },
+            Side::Sell => RetryPolicy { max_attempts: 5, retry_delay_ms: 1000 },
+        }
+    }
+
+    /// Swap transactions are re-signed with a blockhash fetched in parallel with the swap request
+    fn needs_fresh_blockhash(&self) -> bool {
+        true
+    }
+
+    async fn quote_buy(&self, token_mint: &str, amount_lamports: u64, slippage_bps: u16) -> Result<Option<u64>> {
+        let quote = self.get_quote(token_mint, amount_lamports, slippage_bps).await?;
+        Ok(quoted_out(&quote))
+    }
+
+    async fn quote_sell(&self, token_mint: &str, amount_tokens: u64, slippage_bps: u16) -> Result<Option<u64>> {
+        let quote = self.get_sell_quote(token_mint, amount_tokens, slippage_bps).await?;
+        Ok(quoted_out(&quote))
+    }
+
+    async fn build_buy(&self, order: &BuyOrder) -> Result<SwapTx> {
+        let amount_lamports = (order.amount_sol * 1e9) as u64;
+        let quote = self.get_quote(&order.token_mint, amount_lamports, order.slippage_bps).await?;
+        let quoted_out = quoted_out(&quote);
+
+        let (transaction, _last_valid_block) = self
+            .get_swap_transaction(quote, &order.wallet, order.priority_fee_lamports)
+            .await?;
+
+        Ok(SwapTx { transaction, quoted_out })
+    }
+
+    async fn build_sell(&self, order: &SellOrder) -> Result<SwapTx> {
+        // Start at +5% slippage, add 2% per retry
+        let extra_slippage = 500 + (order.attempt - 1) * 200;
+        let quote = self.get_sell_quote(
+            &order.token_mint,
+            order.amount_tokens,
+            order.slippage_bps + extra_slippage as u16,
+        ).await?;
+        let quoted_out = quoted_out(&quote);
+
+        let (transaction, _last_valid_block) = self
+            .get_swap_transaction(quote, &order.wallet, order.priority_fee_lamports)
+            .await?;
+
+        Ok(SwapTx { transaction, quoted_out })
+    }
+}
+
 impl Default for JupiterClient {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,34 @@
+//! SPECTRE - Solana trading bot for consensus signals
+//!
+//! External services sit behind traits so the trading logic can run against fakes:
+//! swap venues (`venue::SwapVenue`), price feeds (`price::PriceSource`),
+//! transaction submission (`submit::TxSubmitter`) and wallet reads (`wallet::WalletReader`).
+
+pub mod advisory;
+pub mod backtest;
+pub mod birdeye;
+pub mod capture;
+pub mod config;
+pub mod confirm;
+pub mod control;
+pub mod dispatch;
+pub mod error;
+pub mod jito;
+pub mod journal;
+pub mod jupiter;
+pub mod monitor;
+pub mod paper;
+pub mod position;
+pub mod price;
+pub mod pumpfun_trade;
+pub mod pumpportal;
+pub mod redis;
+pub mod replay;
+pub mod risk;
+pub mod sizing;
+pub mod store;
+pub mod strategy;
+pub mod submit;
+pub mod trader;
+pub mod venue;
+pub mod wallet;
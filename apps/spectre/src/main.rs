@@ -1,27 +1,33 @@
-mod config;
-mod jupiter;
-mod jito;
-mod redis;
-mod position;
-mod trader;
-mod birdeye;
-mod pumpportal;
-mod pumpfun_trade;
-
 use anyhow::Result;
 use std::sync::Arc;
-use tracing::{info, warn, error, Level};
+use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
-use crate::config::Config;
-use crate::redis::RedisListener;
-use crate::trader::SpectreTrader;
-use crate::birdeye::BirdeyeClient;
-use crate::pumpportal::PumpPortalClient;
-use crate::position::ExitReason;
-
+use spectre::advisory::{advisory_listener, copy_exit_listener};
+use spectre::backtest;
+use spectre::birdeye::BirdeyeClient;
+use spectre::capture;
+use spectre::config::Config;
+use spectre::control::ControlHandler;
+use spectre::dispatch::SignalDispatcher;
+use spectre::journal;
+use spectre::monitor::position_monitor;
+use spectre::position::Position;
+use spectre::pumpportal::PumpPortalClient;
+use spectre::redis::RedisListener;
+use spectre::replay::{self, Replay, ReplayOptions};
+use spectre::trader::SpectreTrader;
 #[tokio::main]
 async fn main() -> Result<()> {
+    // `spectre report [--days N] [--db PATH] [--paper]` - print journal PnL and exit
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("report") => return run_report(&args[1..]).await,
+        // `spectre backtest --ticks ... --entries ...` - replay recorded prices through the exit logic
+        Some("backtest") => return backtest::run(&args[1..]),
+        _ => {}
+    }
+
     // Initialize logging
     FmtSubscriber::builder()
         .with_max_level(Level::INFO)
@@ -32,23 +38,65 @@ async fn main() -> Result<()> {
 
     info!("👻 SPECTRE starting...");
 
-    // Load configuration
-    let config = Config::from_env()?;
+    // `spectre replay <capture.jsonl> [--speed X] [--step]` - re-run a recorded session with simulated execution
+    let mut replay = match args.first().map(String::as_str) {
+        Some("replay") => Some(Replay::load(ReplayOptions::parse(&args[1..])?)?),
+        _ => None,
+    };
+
+    // Load configuration (`--paper` forces paper trading)
+    let mut config = Config::from_env(replay.is_some() || args.iter().any(|a| a == "--paper"))?;
+
+    if let Some(ref replay) = replay {
+        Replay::prepare_config(&mut config)?;
+        capture::start_memory();
+        info!("🎬 REPLAY MODE - {}", replay.describe());
+    } else if let Some(ref dir) = config.capture_dir {
+        capture::start_file(dir)?;
+    }
+
+    if config.paper_mode {
+        info!("📝 PAPER MODE - quotes are real, fills are simulated, nothing is signed or sent");
+    }
 
     info!("📝 Configuration:");
     info!("   RPC: {}", config.rpc_url);
     info!("   Jito: {}", config.jito_block_engine_url);
     info!("   Wallet: {}", config.wallet_pubkey());
     info!("   Trade amount: {} SOL", config.trade_amount_sol);
+    info!("   Sizing: {}", config.sizing.describe());
     info!("   Slippage: {}%", config.slippage_bps as f64 / 100.0);
     info!("   Stop Loss: {}%", config.stop_loss_percent);
     info!("   Take Profit: +{}%", config.take_profit_percent);
     info!("   Priority fee (buy): {} lamports ({:.4} SOL)", config.jito_tip_lamports, config.jito_tip_lamports as f64 / 1e9);
     info!("   Priority fee (sell): {} lamports ({:.4} SOL)", config.jito_tip_sell_lamports, config.jito_tip_sell_lamports as f64 / 1e9);
+    info!("   Max concurrent signals: {}", config.max_concurrent_signals);
+    info!("   Max signal age: {}s (0 = no limit) {:?}", config.max_signal_age_secs, config.max_signal_age_by_type);
+    info!(
+        "   Risk: max {} positions | max deployed {} SOL | daily loss limit {} SOL | reserve {} SOL (0 = no limit)",
+        config.max_open_positions, config.max_deployed_sol, config.daily_loss_limit_sol, config.min_sol_reserve
+    );
+    info!("   Redis max downtime: {}s (0 = retry forever)", config.redis_max_downtime_secs);
+    info!("   Control: {} (replies: {})", config.control_queue, config.control_reply_key);
+    info!("   Exit advisories: {} ({})", config.advisory_queue, config.advisory_policy.describe());
+    info!("   Copy-exit: {}", config.copy_exit.describe());
     info!("   Position check interval: {}s", config.position_check_interval_secs);
+    info!("   Position journal: {}", config.positions_file);
+    info!("   Trade journal: {}", config.journal_db);
+    if let Some(ref dir) = config.capture_dir {
+        info!("   Capture: {}", dir);
+    }
+    if config.paper_mode {
+        info!("   Paper fills: slippage {}% | fee {}%", config.paper_slippage_bps as f64 / 100.0, config.paper_fee_bps as f64 / 100.0);
+    }
+    info!("   Exit strategies: {}", config.exit_strategies_file.as_deref().unwrap_or("built-in"));
+    info!("   Reconcile: adopt orphans={} | min value ${} | interval {}s", config.reconcile_adopt_orphans, config.reconcile_min_value_usd, config.reconcile_interval_secs);
 
     // Initialize trader
-    let trader = Arc::new(SpectreTrader::new(config.clone()));
+    let trader = Arc::new(SpectreTrader::new(config.clone())?);
+
+    // Restore open positions from the journal (crash/redeploy recovery)
+    let restored_positions = trader.position_manager().restore().await?;
 
     // Check balance
     match trader.get_balance().await {
@@ -56,53 +104,116 @@ async fn main() -> Result<()> {
         Err(e) => warn!("⚠️ Failed to get balance: {}", e),
     }
 
-    // Initialize Redis listener
-    let redis_listener = Arc::new(tokio::sync::Mutex::new(
-        RedisListener::new(&config.redis_url, &config.redis_channel).await?
-    ));
-    let mut signal_rx = redis_listener.lock().await.subscribe().await?;
-
-    // Subscribe to pre-signals for Fast Confirm optimization
-    let mut pre_signal_rx = redis_listener.lock().await.subscribe_pre_signals().await?;
+    // Initialize Redis listener (replay never publishes results)
+    // Nothing connects here - the supervised listeners make the first connection, so Redis may still be starting
+    let redis_listener = match replay {
+        Some(_) => RedisListener::offline(),
+        None => RedisListener::new(&config.redis_url, &config.redis_channel)?
+            .with_max_downtime(match config.redis_max_downtime_secs {
+                0 => None,
+                secs => Some(std::time::Duration::from_secs(secs)),
+            }),
+    };
+    let redis_health = redis_listener.health();
+    // Results and command replies are published by their own task, so the trading path never waits on Redis
+    let outbox = redis_listener.outbox();
 
     // Initialize Birdeye/DexScreener client for price monitoring (fallback)
-    let birdeye = Arc::new(BirdeyeClient::new(config.birdeye_api_key.clone()));
+    // Replay answers from the recorded price responses instead
+    let birdeye = Arc::new(match replay {
+        Some(_) => BirdeyeClient::replaying(),
+        None => BirdeyeClient::with_urls(config.birdeye_api_key.clone(), &config.dexscreener_api_url, &config.birdeye_api_url),
+    });
+    if let Some(ref mut replay) = replay {
+        replay.prime(&birdeye).await;
+    }
 
     // Initialize PumpPortal WebSocket client for real-time pump.fun prices
-    let mut pumpportal = PumpPortalClient::new();
+    let mut pumpportal = PumpPortalClient::with_ws_url(&config.pumpportal_ws_url);
 
     // Get SOL price for PumpPortal (from DexScreener)
     let sol_price = birdeye.get_price("So11111111111111111111111111111111111111112").await.unwrap_or(200.0);
     info!("💰 SOL price: ${:.2}", sol_price);
+    trader.set_sol_price(sol_price).await;
 
-    // Start PumpPortal WebSocket
-    let price_rx = pumpportal.start(sol_price).await?;
+    let expected_results = replay.as_ref().map(Replay::expected_results);
+
+    // Inputs: Redis queues (signals + pre-signals for Fast Confirm + operator commands + exit advisories)
+    // and PumpPortal WebSocket, or the same channels fed from a capture (no commands/advisories during replay)
+    let (mut signal_rx, mut pre_signal_rx, command_rx, advisory_rx, price_rx) = match replay {
+        Some(replay) => {
+            let (trade_tx, price_rx) = pumpportal.start_replay(sol_price).await;
+            let (signal_rx, pre_signal_rx) = replay.start(birdeye.clone(), trade_tx);
+            (signal_rx, pre_signal_rx, None, None, price_rx)
+        }
+        None => {
+            let signal_rx = redis_listener.subscribe().await?;
+            let pre_signal_rx = redis_listener.subscribe_pre_signals().await?;
+            let command_rx = redis_listener.subscribe_commands(&config.control_queue, &config.control_reply_key).await?;
+            let advisory_rx = redis_listener.subscribe_exit_advisories(&config.advisory_queue).await?;
+            let price_rx = pumpportal.start(sol_price).await?;
+            info!("🔌 PumpPortal WebSocket started for real-time pump.fun prices");
+            (signal_rx, pre_signal_rx, Some(command_rx), Some(advisory_rx), price_rx)
+        }
+    };
+    let wallet_sell_rx = pumpportal.take_wallet_sells();
     let pumpportal = Arc::new(pumpportal);
 
-    info!("🔌 PumpPortal WebSocket started for real-time pump.fun prices");
+    // Resume price monitoring (and trigger wallet watching) for restored positions
+    for position in &restored_positions {
+        if let Err(e) = pumpportal.subscribe_token(&position.token_mint).await {
+            warn!("⚠️ Failed to resubscribe {}: {}", position.token_symbol, e);
+        }
+        if let Err(e) = pumpportal.subscribe_accounts(&position.trigger_wallets).await {
+            warn!("⚠️ Failed to resubscribe trigger wallets of {}: {}", position.token_symbol, e);
+        }
+    }
+    if !restored_positions.is_empty() {
+        info!("♻️ Resumed monitoring of {} restored position(s)", restored_positions.len());
+    }
+
+    // Reconcile tracked positions with on-chain wallet holdings
+    // (paper positions never exist on-chain - reconciling would drop them all)
+    if !config.paper_mode {
+        match trader.reconcile_positions(birdeye.as_ref()).await {
+            Ok(report) => subscribe_adopted(&pumpportal, &report.adopted).await,
+            Err(e) => warn!("⚠️ Wallet reconciliation failed: {}", e),
+        }
+    }
+
+    // Optional periodic reconciliation (catches sells we never saw confirmed)
+    let reconcile_handle = if config.reconcile_interval_secs > 0 && !config.paper_mode {
+        let reconcile_trader = trader.clone();
+        let reconcile_birdeye = birdeye.clone();
+        let reconcile_pumpportal = pumpportal.clone();
+        let interval = tokio::time::Duration::from_secs(config.reconcile_interval_secs);
+        Some(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                match reconcile_trader.reconcile_positions(reconcile_birdeye.as_ref()).await {
+                    Ok(report) => subscribe_adopted(&reconcile_pumpportal, &report.adopted).await,
+                    Err(e) => warn!("⚠️ Wallet reconciliation failed: {}", e),
+                }
+            }
+        }))
+    } else {
+        None
+    };
 
     // Shutdown channel
     let (shutdown_tx, _) = tokio::sync::broadcast::channel::<()>(1);
     let shutdown_rx = shutdown_tx.subscribe();
 
     // Start position monitor in background
-    let monitor_trader = trader.clone();
-    let monitor_birdeye = birdeye.clone();
-    let monitor_pumpportal = pumpportal.clone();
-    let monitor_redis = redis_listener.clone();
-    let check_interval = config.position_check_interval_secs;
-
-    let monitor_handle = tokio::spawn(async move {
-        position_monitor(
-            monitor_trader,
-            monitor_pumpportal,
-            monitor_birdeye,
-            monitor_redis,
-            check_interval,
-            shutdown_rx,
-            price_rx
-        ).await;
-    });
+    let monitor_handle = tokio::spawn(position_monitor(
+        trader.clone(),
+        pumpportal.clone(),
+        birdeye.clone(),
+        outbox.clone(),
+        config.position_check_interval_secs,
+        shutdown_rx,
+        price_rx,
+    ));
 
     // Start pre-signal handler in background (Fast Confirm optimization)
     let presignal_trader = trader.clone();
@@ -110,15 +221,55 @@ async fn main() -> Result<()> {
         info!("⚡ Fast Confirm: Pre-signal handler started");
         while let Some(pre_signal) = pre_signal_rx.recv().await {
             presignal_trader.prepare_tx_for_presignal(&pre_signal).await;
+            pre_signal.ack().await;
         }
         info!("⚡ Pre-signal handler stopped");
     });
 
+    // Operator commands (sell, close-all, pause, ...), each answered on its reply key
+    let control_handle = command_rx.map(|command_rx| {
+        let handler = Arc::new(ControlHandler::new(trader.clone(), outbox.clone()));
+        tokio::spawn(handler.serve(command_rx, outbox.clone(), config.control_reply_key.clone()))
+    });
+
+    // Exits advised by the backend (trigger wallets selling, consensus reversed)
+    let advisory_handle = advisory_rx.map(|advisory_rx| {
+        tokio::spawn(advisory_listener(trader.clone(), outbox.clone(), config.advisory_policy.clone(), advisory_rx))
+    });
+
+    // Results/replies go out as they come (kept and retried while Redis is unavailable)
+    let outbox_handle = tokio::spawn(outbox.clone().run());
+
+    // Copy-exits from the trigger wallets' own sells
+    let copy_exit_handle = wallet_sell_rx.map(|wallet_sell_rx| {
+        tokio::spawn(copy_exit_listener(trader.clone(), outbox.clone(), config.copy_exit.clone(), wallet_sell_rx))
+    });
+
+    // Buys run as concurrent tasks (one at a time per mint)
+    let dispatcher = SignalDispatcher::new(
+        trader.clone(),
+        pumpportal.clone(),
+        outbox.clone(),
+        config.max_concurrent_signals,
+    );
+
     info!("🚀 SPECTRE ready! Waiting for signals...");
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    // Main loop - process signals
-    while let Some(signal) = signal_rx.recv().await {
+    // Main loop - process signals (until the signal feed ends, or Redis is gone for good)
+    let mut redis_fatal = None;
+    loop {
+        let signal = tokio::select! {
+            signal = signal_rx.recv() => match signal {
+                Some(signal) => signal,
+                None => break,
+            },
+            reason = redis_health.fatal() => {
+                redis_fatal = Some(reason);
+                break;
+            }
+        };
+
         info!("");
         info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
         info!("👻 SIGNAL RECEIVED");
@@ -126,214 +277,107 @@ async fn main() -> Result<()> {
         info!("   MCap: ${:.0}", signal.market_cap_usd.unwrap_or(0.0));
         info!("   Liquidity: ${:.0}", signal.liquidity_usd.unwrap_or(0.0));
         info!("   Strength: {}", signal.strength);
+        if let Some(age_ms) = signal.age_ms() {
+            info!("   Age: {:.1}s", age_ms as f64 / 1000.0);
+        }
         info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-        // Execute buy
-        match trader.execute_buy(&signal).await {
-            Ok(result) => {
-                if result.success {
-                    info!("✅ Trade successful!");
-                    info!("   TX: {}", result.tx_signature.as_deref().unwrap_or("N/A"));
-                    info!("   Latency: {}ms", result.latency_ms);
-
-                    // Subscribe to real-time price updates for this token
-                    if let Err(e) = pumpportal.subscribe_token(&signal.token_mint).await {
-                        warn!("⚠️ Failed to subscribe to price updates: {}", e);
-                    }
-
-                    // Publish result back to Node.js
-                    if let Err(e) = redis_listener.lock().await.publish_trade_result(&result).await {
-                        warn!("⚠️ Failed to publish trade result: {}", e);
-                    }
-                } else {
-                    warn!("❌ Trade failed: {}", result.error.as_deref().unwrap_or("Unknown"));
-                }
-            }
-            Err(e) => {
-                error!("❌ Trade error: {}", e);
-            }
-        }
+        dispatcher.dispatch(signal).await;
+    }
+
+    // Let buys still in flight finish before shutting down
+    dispatcher.drain().await;
+
+    if expected_results.is_some() {
+        tokio::time::sleep(tokio::time::Duration::from_secs(replay::SETTLE_SECS)).await;
     }
 
     // Cleanup
     let _ = shutdown_tx.send(());
     let _ = monitor_handle.await;
     presignal_handle.abort(); // Stop pre-signal handler
-
-    info!("👋 SPECTRE shutting down...");
-    Ok(())
-}
-
-/// Background task for monitoring positions and executing SL/TP
-/// Uses PumpPortal WebSocket for real-time pump.fun prices,
-/// falls back to DexScreener for non-pump.fun tokens
-async fn position_monitor(
-    trader: Arc<SpectreTrader>,
-    pumpportal: Arc<PumpPortalClient>,
-    birdeye: Arc<BirdeyeClient>,
-    redis_listener: Arc<tokio::sync::Mutex<RedisListener>>,
-    check_interval_secs: u64,
-    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
-    mut price_rx: tokio::sync::mpsc::UnboundedReceiver<crate::pumpportal::PriceUpdate>,
-) {
-    let check_interval = tokio::time::Duration::from_secs(check_interval_secs);
-
-    info!("📊 Position monitor started");
-    info!("   - Real-time pump.fun prices via PumpPortal WebSocket");
-    info!("   - Fallback to DexScreener every {}s for non-pump.fun tokens", check_interval_secs);
-
-    loop {
-        tokio::select! {
-            // Handle real-time price updates from PumpPortal
-            Some(price_update) = price_rx.recv() => {
-                // Check if we have a position for this token
-                if let Some(position) = trader.position_manager().get_position(&price_update.token_mint).await {
-                    let current_price = price_update.price_usd;
-
-                    // Sync entry price on first update (fixes price discrepancy)
-                    // This updates entry_price, SL, and TP based on real PumpPortal price
-                    if position.needs_price_sync() {
-                        trader.position_manager().sync_entry_price(&price_update.token_mint, current_price).await;
-                        // Get updated position after sync
-                        continue; // Skip this update, next one will have synced prices
-                    }
-
-                    // Track high price for logging
-                    trader.position_manager().update_high_price(&price_update.token_mint, current_price).await;
-
-                    // Calculate PnL
-                    let pnl = position.calculate_pnl(current_price);
-
-                    // Check if we should exit
-                    if let Some(exit_reason) = position.check_exit(current_price) {
-                        let reason_str = match &exit_reason {
-                            ExitReason::StopLoss => "🛑 STOP LOSS".to_string(),
-                            ExitReason::TakeProfit => "🎯 TAKE PROFIT".to_string(),
-                            ExitReason::Manual => "👤 MANUAL".to_string(),
-                            ExitReason::ScaledTakeProfit { stage, trigger_percent, .. } => {
-                                format!("🎯 NINJA TP#{} (+{:.0}%)", stage, trigger_percent)
-                            }
-                        };
-
-                        info!("🚨 {} triggered for {} at ${:.10} ({:.1}%)",
-                            reason_str,
-                            position.token_symbol,
-                            current_price,
-                            pnl.pnl_percent
-                        );
-
-                        // Execute sell (partial for scaled, full for others)
-                        execute_exit(&trader, &redis_listener, &position.token_mint, exit_reason).await;
-                    }
+    if let Some(handle) = control_handle {
+        handle.abort();
+    }
+    if let Some(handle) = advisory_handle {
+        handle.abort();
+    }
+    if let Some(handle) = copy_exit_handle {
+        handle.abort();
+    }
+    if let Some(handle) = reconcile_handle {
+        handle.abort();
+    }
+    outbox_handle.abort();
+    if outbox.pending() > 0 {
+        match redis_fatal {
+            Some(_) => warn!("⚠️ {} unpublished result(s) lost on shutdown - Redis is down", outbox.pending()),
+            None => {
+                if let Err(e) = outbox.flush().await {
+                    warn!("⚠️ Unpublished results lost on shutdown: {}", e);
                 }
             }
+        }
+    }
 
-            // Periodic check for positions (fallback for tokens not on pump.fun)
-            _ = tokio::time::sleep(check_interval) => {
-                let positions = trader.position_manager().get_all_positions().await;
+    // Journal writes are queued - let them land before exiting
+    trader.journal().flush().await;
+    trader.position_manager().flush().await;
 
-                if positions.is_empty() {
-                    continue;
-                }
+    if let Some(expected) = expected_results {
+        replay::compare(&expected, &capture::recorded_results())?;
+    }
 
-                info!("📊 Checking {} position(s)...", positions.len());
-
-                for position in positions {
-                    // First try PumpPortal cache (real-time)
-                    let current_price = if let Some(price) = pumpportal.get_price(&position.token_mint).await {
-                        price
-                    } else {
-                        // Fallback to DexScreener for non-pump.fun tokens
-                        match birdeye.get_price(&position.token_mint).await {
-                            Ok(price) => price,
-                            Err(e) => {
-                                warn!("⚠️ Failed to get price for {}: {}", position.token_symbol, e);
-                                continue;
-                            }
-                        }
-                    };
-
-                    // Track high price for logging
-                    trader.position_manager().update_high_price(&position.token_mint, current_price).await;
-
-                    // Calculate PnL
-                    let pnl = position.calculate_pnl(current_price);
-                    info!(
-                        "   {} @ ${:.10} | PnL: {:.1}% | SL: ${:.10} | Stage: {}",
-                        position.token_symbol,
-                        current_price,
-                        pnl.pnl_percent,
-                        position.stop_loss_price,
-                        position.scaled_exit_stage
-                    );
-
-                    // Check if we should exit
-                    if let Some(exit_reason) = position.check_exit(current_price) {
-                        let reason_str = match &exit_reason {
-                            ExitReason::StopLoss => "🛑 STOP LOSS".to_string(),
-                            ExitReason::TakeProfit => "🎯 TAKE PROFIT".to_string(),
-                            ExitReason::Manual => "👤 MANUAL".to_string(),
-                            ExitReason::ScaledTakeProfit { stage, trigger_percent, .. } => {
-                                format!("🎯 NINJA TP#{} (+{:.0}%)", stage, trigger_percent)
-                            }
-                        };
-
-                        info!("🚨 {} triggered for {} at ${:.10} ({:.1}%)",
-                            reason_str,
-                            position.token_symbol,
-                            current_price,
-                            pnl.pnl_percent
-                        );
-
-                        // Execute sell (partial for scaled, full for others)
-                        execute_exit(&trader, &redis_listener, &position.token_mint, exit_reason).await;
-                    }
-                }
-            }
+    if let Some(reason) = redis_fatal {
+        anyhow::bail!("Redis unavailable, shutting down: {}", reason);
+    }
 
-            _ = shutdown_rx.recv() => {
-                info!("📊 Position monitor shutting down...");
-                break;
+    info!("👋 SPECTRE shutting down...");
+    Ok(())
+}
+
+/// Print the daily PnL / win-rate report from the trade journal
+async fn run_report(args: &[String]) -> Result<()> {
+    dotenvy::dotenv().ok();
+
+    let mut days: u32 = 7;
+    let mut db = None;
+    let paper = args.iter().any(|a| a == "--paper");
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--days" => {
+                days = args
+                    .next()
+                    .and_then(|v| v.parse().ok())
+                    .ok_or_else(|| anyhow::anyhow!("--days expects a number"))?;
+            }
+            "--db" => {
+                db = Some(args
+                    .next()
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("--db expects a path"))?);
             }
+            "--paper" => {}
+            other => anyhow::bail!("Unknown report option: {} (usage: spectre report [--days N] [--db PATH] [--paper])", other),
         }
     }
-}
 
-/// Helper to execute exit and publish result
-async fn execute_exit(
-    trader: &Arc<SpectreTrader>,
-    redis_listener: &Arc<tokio::sync::Mutex<RedisListener>>,
-    token_mint: &str,
-    exit_reason: ExitReason,
-) {
-    match trader.execute_sell(token_mint, exit_reason).await {
-        Ok(result) => {
-            if result.success {
-                info!("✅ Exit executed successfully!");
-                info!("   TX: {}", result.tx_signature.as_deref().unwrap_or("N/A"));
-
-                // Publish result back to Node.js
-                if let Err(e) = redis_listener.lock().await.publish_trade_result(&result).await {
-                    warn!("⚠️ Failed to publish trade result: {}", e);
-                }
-            } else {
-                let error_msg = result.error.as_deref().unwrap_or("Unknown");
-                error!("❌ Exit failed: {}", error_msg);
+    let db = db.unwrap_or_else(|| match paper {
+        true => std::env::var("PAPER_JOURNAL_DB").unwrap_or_else(|_| "data/paper/journal.db".to_string()),
+        false => std::env::var("JOURNAL_DB").unwrap_or_else(|_| "data/journal.db".to_string()),
+    });
 
-                // If quote failed (no route), increment failed sell counter
-                if error_msg.contains("no route") || error_msg.contains("COULD_NOT_FIND") || error_msg.contains("quote failed") {
-                    trader.position_manager().increment_failed_sell(token_mint).await;
-                }
-            }
-        }
-        Err(e) => {
-            error!("❌ Exit error: {}", e);
+    println!("👻 SPECTRE report - last {} day(s) from {}", days, db);
+    journal::TradeJournal::open(&db)?.print_report(days).await
+}
 
-            // Also increment on error
-            let error_str = e.to_string();
-            if error_str.contains("no route") || error_str.contains("COULD_NOT_FIND") {
-                trader.position_manager().increment_failed_sell(token_mint).await;
-            }
+/// Subscribe to real-time prices for positions adopted by reconciliation
+async fn subscribe_adopted(pumpportal: &Arc<PumpPortalClient>, adopted: &[Position]) {
+    for position in adopted {
+        if let Err(e) = pumpportal.subscribe_token(&position.token_mint).await {
+            warn!("⚠️ Failed to subscribe to price updates for {}: {}", position.token_symbol, e);
         }
     }
 }
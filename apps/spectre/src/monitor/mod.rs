@@ -0,0 +1,300 @@
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, info, warn, error};
+
+use crate::capture::{self, CaptureEvent};
+use crate::error::{ErrorCode, SpectreError};
+use crate::position::{ExitClaim, ExitReason};
+use crate::price::PriceSource;
+use crate::pumpportal::PriceUpdate;
+use crate::redis::{ResultPublisher, TradeResult};
+use crate::trader::SpectreTrader;
+
+/// Background task for monitoring positions and executing SL/TP
+/// `price_rx` and `realtime` carry real-time pump.fun prices (PumpPortal WebSocket),
+/// `fallback` is polled for everything else (DexScreener)
+pub async fn position_monitor(
+    trader: Arc<SpectreTrader>,
+    realtime: Arc<dyn PriceSource>,
+    fallback: Arc<dyn PriceSource>,
+    publisher: Arc<dyn ResultPublisher>,
+    check_interval_secs: u64,
+    mut shutdown_rx: broadcast::Receiver<()>,
+    mut price_rx: mpsc::UnboundedReceiver<PriceUpdate>,
+) {
+    let check_interval = tokio::time::Duration::from_secs(check_interval_secs);
+
+    info!("📊 Position monitor started");
+    info!("   - Real-time pump.fun prices via PumpPortal WebSocket");
+    info!("   - Fallback to DexScreener every {}s for non-pump.fun tokens", check_interval_secs);
+
+    loop {
+        tokio::select! {
+            // Handle real-time price updates from PumpPortal
+            Some(price_update) = price_rx.recv() => {
+                // Check if we have a position for this token
+                if let Some(position) = trader.position_manager().get_position(&price_update.token_mint).await {
+                    let current_price = price_update.price_usd;
+
+                    // Sync entry price on first update (fixes price discrepancy)
+                    // This updates entry_price, SL, and TP based on real PumpPortal price
+                    if position.needs_price_sync() {
+                        trader.position_manager().sync_entry_price(&price_update.token_mint, current_price).await;
+                        // Get updated position after sync
+                        continue; // Skip this update, next one will have synced prices
+                    }
+
+                    // Track high-water mark (trailing stop) and stagnation window
+                    trader.position_manager().record_price(&price_update.token_mint, current_price).await;
+
+                    // Calculate PnL
+                    let pnl = position.calculate_pnl(current_price);
+
+                    // Check if we should exit
+                    if let Some(exit_reason) = position.check_exit(current_price) {
+                        if !exit_wanted(&trader, &position.token_mint, &exit_reason).await {
+                            continue;
+                        }
+
+                        let reason_str = exit_label(&exit_reason);
+
+                        info!("🚨 {} triggered for {} at ${:.10} ({:.1}%)",
+                            reason_str,
+                            position.token_symbol,
+                            current_price,
+                            pnl.pnl_percent
+                        );
+
+                        // Execute sell (partial for scaled, full for others)
+                        spawn_exit(&trader, &publisher, &position.token_mint, exit_reason);
+                    }
+                }
+            }
+
+            // Periodic check for positions (fallback for tokens not on pump.fun)
+            _ = tokio::time::sleep(check_interval) => {
+                let positions = trader.position_manager().get_all_positions().await;
+
+                if positions.is_empty() {
+                    continue;
+                }
+
+                info!("📊 Checking {} position(s)...", positions.len());
+
+                for position in positions {
+                    // First try PumpPortal cache (real-time)
+                    let current_price = if let Ok(price) = realtime.get_price(&position.token_mint).await {
+                        price
+                    } else {
+                        // Fallback to DexScreener for non-pump.fun tokens
+                        match fallback.get_price(&position.token_mint).await {
+                            Ok(price) => price,
+                            Err(e) => {
+                                warn!("⚠️ Failed to get price for {}: {}", position.token_symbol, e);
+
+                                // Time-based exits don't need a price
+                                if let Some(exit_reason) = position.check_time_exit(chrono::Utc::now()) {
+                                    if exit_wanted(&trader, &position.token_mint, &exit_reason).await {
+                                        info!("🚨 {} triggered for {} (no price)", exit_label(&exit_reason), position.token_symbol);
+                                        spawn_exit(&trader, &publisher, &position.token_mint, exit_reason);
+                                    }
+                                }
+                                continue;
+                            }
+                        }
+                    };
+
+                    // Non-pump.fun positions opened without an entry price take the first polled one
+                    // (pump.fun positions sync from the real-time feed above)
+                    if position.needs_price_sync() && !position.is_pumpfun {
+                        trader.position_manager().sync_entry_price(&position.token_mint, current_price).await;
+                        continue;
+                    }
+
+                    // Track high-water mark (trailing stop) and stagnation window
+                    trader.position_manager().record_price(&position.token_mint, current_price).await;
+
+                    // Calculate PnL
+                    let pnl = position.calculate_pnl(current_price);
+                    info!(
+                        "   {} @ ${:.10} | PnL: {:.1}% | SL: ${:.10} | Stage: {}",
+                        position.token_symbol,
+                        current_price,
+                        pnl.pnl_percent,
+                        position.stop_loss_price,
+                        position.scaled_exit_stage
+                    );
+
+                    // Check if we should exit
+                    if let Some(exit_reason) = position.check_exit(current_price) {
+                        if !exit_wanted(&trader, &position.token_mint, &exit_reason).await {
+                            continue;
+                        }
+
+                        let reason_str = exit_label(&exit_reason);
+
+                        info!("🚨 {} triggered for {} at ${:.10} ({:.1}%)",
+                            reason_str,
+                            position.token_symbol,
+                            current_price,
+                            pnl.pnl_percent
+                        );
+
+                        // Execute sell (partial for scaled, full for others)
+                        spawn_exit(&trader, &publisher, &position.token_mint, exit_reason);
+                    }
+                }
+            }
+
+            _ = shutdown_rx.recv() => {
+                info!("📊 Position monitor shutting down...");
+                break;
+            }
+        }
+    }
+}
+
+/// Log label for an exit trigger
+pub fn exit_label(exit_reason: &ExitReason) -> String {
+    match exit_reason {
+        ExitReason::StopLoss => "🛑 STOP LOSS".to_string(),
+        ExitReason::TakeProfit => "🎯 TAKE PROFIT".to_string(),
+        ExitReason::Manual { sell_percent } if *sell_percent < 100.0 => {
+            format!("👤 MANUAL ({:.0}%)", sell_percent)
+        }
+        ExitReason::Manual { .. } => "👤 MANUAL".to_string(),
+        ExitReason::TrailingStop { high_price, trail_percent } => {
+            format!("📉 TRAILING STOP (-{:.0}% from ${:.10})", trail_percent, high_price)
+        }
+        ExitReason::MaxHoldTime { held_minutes } => {
+            format!("⏰ MAX HOLD ({}m)", held_minutes)
+        }
+        ExitReason::Stagnation { minutes, band_percent } => {
+            format!("💤 STAGNATION (±{:.0}% for {}m)", band_percent, minutes)
+        }
+        ExitReason::SmartWalletExit { sell_percent } => {
+            format!("🐋 SMART WALLET EXIT ({:.0}%)", sell_percent)
+        }
+        ExitReason::ConsensusReversed { sell_percent } => {
+            format!("🔃 CONSENSUS REVERSED ({:.0}%)", sell_percent)
+        }
+        ExitReason::ScaledTakeProfit { stage, trigger_percent, .. } => {
+            format!("🎯 TP#{} (+{:.0}%)", stage, trigger_percent)
+        }
+        ExitReason::ReconciledZeroBalance => "🧹 RECONCILED (zero balance)".to_string(),
+    }
+}
+
+/// Trigger worth acting on: no exit running yet, or a full exit superseding a running partial one
+async fn exit_wanted(trader: &SpectreTrader, token_mint: &str, exit_reason: &ExitReason) -> bool {
+    match trader.position_manager().pending_exit(token_mint).await {
+        None => true,
+        Some(pending) => pending.is_partial() && !exit_reason.is_partial(),
+    }
+}
+
+/// Run an exit in the background so a retrying sell doesn't hold up price updates
+fn spawn_exit(trader: &Arc<SpectreTrader>, publisher: &Arc<dyn ResultPublisher>, token_mint: &str, exit_reason: ExitReason) {
+    let trader = trader.clone();
+    let publisher = publisher.clone();
+    let token_mint = token_mint.to_string();
+    tokio::spawn(async move {
+        execute_exit(&trader, publisher.as_ref(), &token_mint, exit_reason).await;
+    });
+}
+
+/// Execute an exit and publish the result
+/// Only one exit per position runs at a time: repeated triggers are coalesced, a full exit
+/// arriving during a partial one is queued and runs as soon as the partial one stops
+/// Returns the result of the last sell run here (None if handed to the running exit, or the sell errored)
+pub async fn execute_exit(
+    trader: &SpectreTrader,
+    publisher: &dyn ResultPublisher,
+    token_mint: &str,
+    exit_reason: ExitReason,
+) -> Option<TradeResult> {
+    let short_mint = &token_mint[..16.min(token_mint.len())];
+
+    match trader.position_manager().begin_exit(token_mint, &exit_reason).await {
+        ExitClaim::Started => {}
+        ExitClaim::Escalated => {
+            info!("⏫ {} queued for {} - supersedes the running partial exit", exit_label(&exit_reason), short_mint);
+            return None;
+        }
+        ExitClaim::Coalesced => {
+            debug!("Exit already running for {}, {} coalesced", short_mint, exit_reason);
+            return None;
+        }
+    }
+
+    let mut exit_reason = exit_reason;
+    loop {
+        let result = run_exit(trader, publisher, token_mint, exit_reason).await;
+
+        match trader.position_manager().finish_exit(token_mint).await {
+            Some(next) if trader.position_manager().has_position(token_mint).await => {
+                info!("⏫ Running queued {} for {}", exit_label(&next), short_mint);
+                exit_reason = next;
+            }
+            Some(_) => {
+                // Partial exit closed the position - nothing left for the queued one
+                trader.position_manager().finish_exit(token_mint).await;
+                return result;
+            }
+            None => return result,
+        }
+    }
+}
+
+/// Sell for one exit reason, publish the result and count unroutable sells
+async fn run_exit(
+    trader: &SpectreTrader,
+    publisher: &dyn ResultPublisher,
+    token_mint: &str,
+    exit_reason: ExitReason,
+) -> Option<TradeResult> {
+    match trader.execute_sell(token_mint, exit_reason).await {
+        Ok(result) => {
+            capture::record(CaptureEvent::TradeResult { result: Box::new(result.clone()) });
+
+            if result.success {
+                info!("✅ Exit executed successfully!");
+                info!("   TX: {}", result.tx_signature.as_deref().unwrap_or("N/A"));
+
+                // Publish result back to Node.js
+                if let Err(e) = publisher.publish_trade_result(&result).await {
+                    warn!("⚠️ Failed to publish trade result: {}", e);
+                }
+            } else {
+                let error_msg = result.error.as_deref().unwrap_or("Unknown");
+                if result.error_code == Some(ErrorCode::Superseded) {
+                    info!("⏫ Exit handed over: {}", error_msg);
+                    return Some(result);
+                }
+                error!("❌ Exit failed: {}", error_msg);
+
+                // A TX was sent but never confirmed - report the failed sell
+                if result.tx_signature.is_some() {
+                    if let Err(e) = publisher.publish_trade_result(&result).await {
+                        warn!("⚠️ Failed to publish trade result: {}", e);
+                    }
+                }
+
+                // No route to sell through - counts towards marking the position unsellable
+                if result.error_code.is_some_and(|code| code.is_failed_sell()) {
+                    trader.position_manager().increment_failed_sell(token_mint).await;
+                }
+            }
+            Some(result)
+        }
+        Err(e) => {
+            error!("❌ Exit error: {}", e);
+
+            // Also increment on error
+            if SpectreError::code_of(&e).is_some_and(|code| code.is_failed_sell()) {
+                trader.position_manager().increment_failed_sell(token_mint).await;
+            }
+            None
+        }
+    }
+}
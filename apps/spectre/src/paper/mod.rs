@@ -0,0 +1,163 @@
+/// Base network fee per signature (lamports)
+pub const BASE_FEE_LAMPORTS: u64 = 5_000;
+
+/// Simulated fill of a paper (or backtest) trade
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulatedFill {
+    /// Tokens bought or sold (raw units)
+    pub amount_tokens: u64,
+    /// Effective USD price per whole token, after slippage and fees
+    pub price_usd: f64,
+    /// SOL spent (buys) or received (sells), network fees included
+    pub amount_sol: f64,
+}
+
+/// Slippage + fee model used to simulate fills without sending transactions
+#[derive(Debug, Clone, Copy)]
+pub struct FillModel {
+    /// Adverse move vs the quoted price in bps (buys fill higher, sells lower)
+    pub slippage_bps: u16,
+    /// Venue fee in bps on top of a raw price (pump.fun bonding curve charges 1%)
+    /// Not applied to Jupiter quotes - they already include pool fees
+    pub fee_bps: u16,
+}
+
+impl FillModel {
+    fn slippage(&self) -> f64 {
+        self.slippage_bps as f64 / 10_000.0
+    }
+
+    fn fee(&self) -> f64 {
+        self.fee_bps as f64 / 10_000.0
+    }
+
+    /// Buy `sol_in` worth of tokens at a spot price (USD per whole token)
+    pub fn buy_at_price(&self, sol_in: f64, network_fee_sol: f64, price_usd: f64, sol_usd: f64, decimals: u8) -> SimulatedFill {
+        let fill_price = price_usd * (1.0 + self.slippage());
+        let ui_tokens = sol_in * (1.0 - self.fee()) * sol_usd / fill_price;
+        Self::buy_fill(sol_in, network_fee_sol, ui_tokens, sol_usd, decimals)
+    }
+
+    /// Buy from a quote (`quoted_out` raw tokens for `sol_in`)
+    pub fn buy_from_quote(&self, sol_in: f64, network_fee_sol: f64, quoted_out: u64, sol_usd: f64, decimals: u8) -> SimulatedFill {
+        let ui_tokens = quoted_out as f64 * (1.0 - self.slippage()) / 10f64.powi(decimals as i32);
+        Self::buy_fill(sol_in, network_fee_sol, ui_tokens, sol_usd, decimals)
+    }
+
+    fn buy_fill(sol_in: f64, network_fee_sol: f64, ui_tokens: f64, sol_usd: f64, decimals: u8) -> SimulatedFill {
+        let amount_tokens = (ui_tokens * 10f64.powi(decimals as i32)) as u64;
+        // Same definition as live fills: SOL put into the swap / tokens received
+        let price_usd = if ui_tokens > 0.0 { sol_in * sol_usd / ui_tokens } else { 0.0 };
+        SimulatedFill {
+            amount_tokens,
+            price_usd,
+            amount_sol: sol_in + network_fee_sol,
+        }
+    }
+
+    /// Sell raw tokens at a spot price (USD per whole token)
+    pub fn sell_at_price(&self, amount_tokens: u64, decimals: u8, price_usd: f64, sol_usd: f64, network_fee_sol: f64) -> SimulatedFill {
+        let ui_tokens = amount_tokens as f64 / 10f64.powi(decimals as i32);
+        let fill_price = price_usd * (1.0 - self.slippage()) * (1.0 - self.fee());
+        let sol_out = if sol_usd > 0.0 { ui_tokens * fill_price / sol_usd } else { 0.0 };
+        SimulatedFill {
+            amount_tokens,
+            price_usd: fill_price,
+            amount_sol: (sol_out - network_fee_sol).max(0.0),
+        }
+    }
+
+    /// Sell from a quote (`quoted_out_lamports` SOL for `amount_tokens`)
+    pub fn sell_from_quote(&self, amount_tokens: u64, decimals: u8, quoted_out_lamports: u64, sol_usd: f64, network_fee_sol: f64) -> SimulatedFill {
+        let sol_out = quoted_out_lamports as f64 / 1e9 * (1.0 - self.slippage());
+        let ui_tokens = amount_tokens as f64 / 10f64.powi(decimals as i32);
+        SimulatedFill {
+            amount_tokens,
+            price_usd: if ui_tokens > 0.0 { sol_out * sol_usd / ui_tokens } else { 0.0 },
+            amount_sol: (sol_out - network_fee_sol).max(0.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL_USD: f64 = 100.0;
+    const DECIMALS: u8 = 6;
+    const NETWORK_FEE: f64 = 0.000005;
+    /// 1% slippage and a 1% venue fee
+    const MODEL: FillModel = FillModel { slippage_bps: 100, fee_bps: 100 };
+    const FREE: FillModel = FillModel { slippage_bps: 0, fee_bps: 0 };
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn frictionless_buy_fills_at_the_spot_price() {
+        let fill = FREE.buy_at_price(1.0, NETWORK_FEE, 0.5, SOL_USD, DECIMALS);
+        assert_eq!(fill.amount_tokens, 200_000_000);
+        assert_close(fill.price_usd, 0.5);
+        assert_close(fill.amount_sol, 1.0 + NETWORK_FEE);
+    }
+
+    #[test]
+    fn buy_at_price_pays_slippage_and_fee() {
+        let fill = MODEL.buy_at_price(1.0, NETWORK_FEE, 0.5, SOL_USD, DECIMALS);
+
+        // 0.99 SOL after the fee buys at 0.505
+        let ui_tokens = 0.99 * SOL_USD / 0.505;
+        assert_eq!(fill.amount_tokens, (ui_tokens * 1e6) as u64);
+        // Effective price is the whole SOL in per token received
+        assert_close(fill.price_usd, 0.505 / 0.99);
+        assert_close(fill.amount_sol, 1.0 + NETWORK_FEE);
+    }
+
+    #[test]
+    fn buy_from_quote_applies_slippage_but_no_fee() {
+        let fill = MODEL.buy_from_quote(1.0, NETWORK_FEE, 200_000_000, SOL_USD, DECIMALS);
+        assert!(fill.amount_tokens.abs_diff(198_000_000) <= 1, "{}", fill.amount_tokens);
+        assert_close(fill.price_usd, SOL_USD / 198.0);
+        assert_close(fill.amount_sol, 1.0 + NETWORK_FEE);
+
+        let no_fee = FillModel { fee_bps: 0, ..MODEL }.buy_from_quote(1.0, NETWORK_FEE, 200_000_000, SOL_USD, DECIMALS);
+        assert_eq!(fill, no_fee);
+    }
+
+    #[test]
+    fn sell_at_price_pays_slippage_fee_and_network_fee() {
+        let fill = MODEL.sell_at_price(200_000_000, DECIMALS, 0.5, SOL_USD, NETWORK_FEE);
+        assert_eq!(fill.amount_tokens, 200_000_000);
+        assert_close(fill.price_usd, 0.5 * 0.99 * 0.99);
+        assert_close(fill.amount_sol, 200.0 * 0.49005 / SOL_USD - NETWORK_FEE);
+    }
+
+    #[test]
+    fn sell_from_quote_applies_slippage_but_no_fee() {
+        let fill = MODEL.sell_from_quote(200_000_000, DECIMALS, 1_000_000_000, SOL_USD, NETWORK_FEE);
+        assert_close(fill.amount_sol, 0.99 - NETWORK_FEE);
+        assert_close(fill.price_usd, 0.99 * SOL_USD / 200.0);
+
+        let no_fee = FillModel { fee_bps: 0, ..MODEL }.sell_from_quote(200_000_000, DECIMALS, 1_000_000_000, SOL_USD, NETWORK_FEE);
+        assert_eq!(fill, no_fee);
+    }
+
+    #[test]
+    fn sells_never_return_negative_sol() {
+        let dust = MODEL.sell_at_price(1, DECIMALS, 0.5, SOL_USD, NETWORK_FEE);
+        assert_eq!(dust.amount_sol, 0.0);
+        let dust = MODEL.sell_from_quote(1, DECIMALS, 1_000, SOL_USD, NETWORK_FEE);
+        assert_eq!(dust.amount_sol, 0.0);
+        let no_sol_price = MODEL.sell_at_price(200_000_000, DECIMALS, 0.5, 0.0, 0.0);
+        assert_eq!(no_sol_price.amount_sol, 0.0);
+    }
+
+    #[test]
+    fn round_trip_at_a_flat_price_loses_slippage_and_fee_both_ways() {
+        let buy = MODEL.buy_at_price(1.0, 0.0, 0.5, SOL_USD, DECIMALS);
+        let sell = MODEL.sell_at_price(buy.amount_tokens, DECIMALS, 0.5, SOL_USD, 0.0);
+        // Fee and a 1% higher price on the way in, a 1% lower price and the fee on the way out
+        assert!((sell.amount_sol - 0.99f64.powi(3) / 1.01).abs() < 1e-6, "{}", sell.amount_sol);
+    }
+}
@@ -1,10 +1,13 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
+use crate::store::PositionStore;
+use crate::strategy::{percent_reached, ExitStrategy, PERCENT_EPSILON};
+
 /// Active position being monitored for SL/TP
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
@@ -31,21 +34,47 @@ pub struct Position {
     /// True if entry price was synced with real PumpPortal price
     #[serde(default)]
     pub price_synced: bool,
-    /// Highest price seen since entry (for logging only)
+    /// Highest price seen since entry (high-water mark for the trailing stop)
     #[serde(default)]
     pub high_price: f64,
     /// Signal type (ninja, consensus, etc.) - determines exit strategy
     #[serde(default)]
     pub signal_type: String,
-    /// Scaled exit stage for NINJA signals (0=none, 1=sold 60% at +30%, 2=sold 20% at +40%, 3=sold 20% at +50%)
+    /// Scaled exit stage (0=none, N=ladder rung N has been sold)
     #[serde(default)]
     pub scaled_exit_stage: u8,
+    /// Exit strategy (ladder + SL overrides) resolved at entry
+    /// None = legacy behavior (built-in NINJA ladder for ninja signals, plain SL/TP otherwise)
+    #[serde(default)]
+    pub exit_strategy: Option<ExitStrategy>,
     /// Original token amount (before any partial sells)
     #[serde(default)]
     pub original_amount_tokens: u64,
+    /// Token decimals (known once the fill was read from the wallet)
+    #[serde(default)]
+    pub token_decimals: Option<u8>,
+    /// True if position was adopted from on-chain wallet holdings (not opened by a signal we saw)
+    #[serde(default)]
+    pub reconstructed: bool,
+    /// Last observed price (0 = none yet) - paper sells fill at this price
+    #[serde(default)]
+    pub last_price: f64,
+    /// Price the stagnation window is measured from (0 = entry price)
+    #[serde(default)]
+    pub stagnation_anchor_price: f64,
+    /// When price last left the stagnation band (None = entry time)
+    #[serde(default)]
+    pub stagnation_anchor_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// Smart wallets whose buys triggered the entry (watched for copy-exits)
+    #[serde(default)]
+    pub trigger_wallets: Vec<String>,
+    /// Trigger wallets seen selling this token since entry
+    #[serde(default)]
+    pub trigger_wallets_sold: Vec<String>,
 }
 
 impl Position {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         token_mint: String,
         token_symbol: String,
@@ -71,6 +100,7 @@ impl Position {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_signal_type(
         token_mint: String,
         token_symbol: String,
@@ -90,19 +120,15 @@ impl Position {
         // TP comes as positive number (e.g., 50 means +50% from entry)
         let take_profit_price = entry_price * (1.0 + take_profit_percent.abs() / 100.0);
 
-        let is_ninja = signal_type == "ninja";
-
         info!(
-            "📊 Position created ({}, {}): {} @ ${:.10} | SL: ${:.10} (-{:.0}%) | TP: ${:.10} (+{:.0}%){}",
+            "📊 Position created ({}): {} @ ${:.10} | SL: ${:.10} (-{:.0}%) | TP: ${:.10} (+{:.0}%)",
             if is_pumpfun { "pump.fun" } else { "Jupiter" },
-            if is_ninja { "NINJA scaled exits" } else { "standard" },
             token_symbol,
             entry_price,
             stop_loss_price,
             stop_loss_percent.abs(),
             take_profit_price,
-            take_profit_percent.abs(),
-            if is_ninja { " | Scaled: 80%@+30%, 15%@+50%, 5%@+80%" } else { "" }
+            take_profit_percent.abs()
         );
 
         Self {
@@ -121,13 +147,27 @@ impl Position {
             is_unsellable: false,
             is_pumpfun,
             price_synced: false,
-            high_price: entry_price, // For logging only
+            high_price: entry_price, // High-water mark for the trailing stop starts at entry
             signal_type,
             scaled_exit_stage: 0,
+            exit_strategy: None,
             original_amount_tokens: amount_tokens,
+            token_decimals: None,
+            reconstructed: false,
+            last_price: 0.0,
+            stagnation_anchor_price: 0.0,
+            stagnation_anchor_time: None,
+            trigger_wallets: Vec::new(),
+            trigger_wallets_sold: Vec::new(),
         }
     }
 
+    /// Remember the wallets behind the entry signal
+    pub fn with_trigger_wallets(mut self, wallets: Vec<String>) -> Self {
+        self.trigger_wallets = wallets;
+        self
+    }
+
     /// Short initial period to wait for first price sync (in seconds)
     /// We need at least one PumpPortal price update to sync entry_price
     const PRICE_SYNC_WAIT_SECS: i64 = 3;
@@ -167,93 +207,253 @@ impl Position {
         );
     }
 
-    /// Update high price for logging (no trailing SL - we use scaled exits)
-    pub fn update_high_price(&mut self, current_price: f64) {
+    /// Apply the real on-chain fill (amount received + effective entry price)
+    /// The effective price already includes fees and slippage, so no PumpPortal sync is needed
+    pub fn apply_fill(&mut self, amount_tokens: u64, decimals: u8, entry_price: f64) {
+        self.amount_tokens = amount_tokens;
+        self.original_amount_tokens = amount_tokens;
+        self.token_decimals = Some(decimals);
+
+        if entry_price > 0.0 {
+            self.entry_price = entry_price;
+            self.high_price = entry_price;
+            self.stop_loss_price = entry_price * (1.0 - self.stop_loss_percent.abs() / 100.0);
+            self.take_profit_price = entry_price * (1.0 + self.take_profit_percent.abs() / 100.0);
+            self.price_synced = true;
+        }
+    }
+
+    /// Update high-water mark, returns true if a new high was set
+    pub fn update_high_price(&mut self, current_price: f64) -> bool {
         if current_price > self.high_price {
             self.high_price = current_price;
+            return true;
         }
+        false
     }
 
-    /// Check if entry price has been synced with real PumpPortal price
-    pub fn needs_price_sync(&self) -> bool {
-        // Only sync pump.fun positions that haven't been synced yet
-        self.is_pumpfun && !self.price_synced
+    /// Record an observed price: last price, high-water mark + stagnation anchor
+    /// Returns true if state that an exit rule depends on changed (worth persisting)
+    pub fn record_price(&mut self, current_price: f64, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.last_price = current_price;
+        let new_high = self.update_high_price(current_price) && self.has_trailing_stop();
+
+        let band_percent = match self.exit_strategy {
+            Some(ref s) if s.stagnation_minutes.is_some() => s.stagnation_band_percent,
+            _ => return new_high,
+        };
+
+        // Price left the band - restart the stagnation window from here
+        if !self.is_within_stagnation_band(current_price, band_percent) {
+            self.stagnation_anchor_price = current_price;
+            self.stagnation_anchor_time = Some(now);
+            return true;
+        }
+
+        new_high
+    }
+
+    fn is_within_stagnation_band(&self, current_price: f64, band_percent: f64) -> bool {
+        let anchor = if self.stagnation_anchor_price > 0.0 {
+            self.stagnation_anchor_price
+        } else {
+            self.entry_price
+        };
+        ((current_price / anchor - 1.0) * 100.0).abs() <= band_percent.abs() + PERCENT_EPSILON
     }
 
-    // NINJA scaled exit thresholds (conservative: +30%→80%, +50%→15%, +80%→5%)
-    const NINJA_TP1_PERCENT: f64 = 30.0;  // +30% -> sell 80%
-    const NINJA_TP2_PERCENT: f64 = 50.0;  // +50% -> sell 15%
-    const NINJA_TP3_PERCENT: f64 = 80.0;  // +80% -> sell remaining 5%
+    /// True if the exit strategy uses a trailing stop (high-water mark matters)
+    pub fn has_trailing_stop(&self) -> bool {
+        self.exit_strategy
+            .as_ref()
+            .is_some_and(|s| s.trailing_stop_percent.is_some())
+    }
 
-    const NINJA_TP1_SELL_PERCENT: f64 = 80.0;  // Sell 80% of position at TP1
-    const NINJA_TP2_SELL_PERCENT: f64 = 75.0;  // Sell 75% of remaining (= 15% of original) at TP2
-    const NINJA_TP3_SELL_PERCENT: f64 = 100.0; // Sell 100% of remaining (= 5% of original) at TP3
+    /// Check if entry price has been synced with real PumpPortal price
+    /// Positions opened without any entry price (no fill, quote or signal price) also wait for one
+    pub fn needs_price_sync(&self) -> bool {
+        !self.price_synced && (self.is_pumpfun || self.entry_price <= 0.0)
+    }
 
     /// Check if this is a NINJA signal (uses scaled exits)
     pub fn is_ninja(&self) -> bool {
         self.signal_type == "ninja"
     }
 
-    /// Check if current price triggers SL or TP
-    /// Returns None if position is marked as unsellable or waiting for price sync
-    /// For NINJA signals, returns ScaledTakeProfit with percentage to sell
+    /// Attach an exit strategy; its SL/TP overrides replace the signal's values
+    pub fn with_exit_strategy(mut self, strategy: ExitStrategy) -> Self {
+        self.reprice_exit_levels(strategy.stop_loss_percent, strategy.take_profit_percent);
+
+        info!(
+            "📐 Exit strategy '{}' for {}: SL -{:.0}% | Ladder: {}",
+            strategy.name,
+            self.token_symbol,
+            self.stop_loss_percent.abs(),
+            strategy.describe_ladder()
+        );
+
+        self.exit_strategy = Some(strategy);
+        self
+    }
+
+    /// Replace SL and/or TP (percent from entry, positive like the signal's) and reprice them
+    /// SL must be in (0, 100) and TP above 0. Nothing changes if either is out of range, or if
+    /// the new SL is below the current stop (breakeven/profit lock, tightened) without `allow_lower_stop`.
+    pub fn set_exit_levels(&mut self, stop_loss_percent: Option<f64>, take_profit_percent: Option<f64>, allow_lower_stop: bool) -> Result<()> {
+        if let Some(sl) = stop_loss_percent {
+            if !(sl > 0.0 && sl < 100.0) {
+                bail!("stop loss must be between 0 and 100%, got {}", sl);
+            }
+            let stop_loss_price = self.entry_price * (1.0 - sl / 100.0);
+            if stop_loss_price < self.stop_loss_price && !allow_lower_stop {
+                bail!(
+                    "SL -{}% (${:.10}) would lower the current stop ${:.10} (set allowLower to force)",
+                    sl,
+                    stop_loss_price,
+                    self.stop_loss_price
+                );
+            }
+        }
+        if let Some(tp) = take_profit_percent {
+            if !(tp > 0.0 && tp.is_finite()) {
+                bail!("take profit must be above 0%, got {}", tp);
+            }
+        }
+
+        self.reprice_exit_levels(stop_loss_percent, take_profit_percent);
+        Ok(())
+    }
+
+    fn reprice_exit_levels(&mut self, stop_loss_percent: Option<f64>, take_profit_percent: Option<f64>) {
+        if let Some(sl) = stop_loss_percent {
+            self.stop_loss_percent = sl;
+            self.stop_loss_price = self.entry_price * (1.0 - sl.abs() / 100.0);
+        }
+        if let Some(tp) = take_profit_percent {
+            self.take_profit_percent = tp;
+            self.take_profit_price = self.entry_price * (1.0 + tp.abs() / 100.0);
+        }
+    }
+
+    /// Effective exit strategy (falls back to the built-in NINJA ladder for legacy positions)
+    pub fn effective_strategy(&self) -> Option<ExitStrategy> {
+        match self.exit_strategy {
+            Some(ref strategy) => Some(strategy.clone()),
+            None if self.is_ninja() => Some(ExitStrategy::ninja()),
+            None => None,
+        }
+    }
+
+    /// Check if current price (or holding time) triggers an exit
+    /// Returns None if position is marked as unsellable (only time exits while waiting for price sync)
+    /// With a ladder strategy, returns ScaledTakeProfit with percentage to sell
     pub fn check_exit(&self, current_price: f64) -> Option<ExitReason> {
+        self.check_exit_at(current_price, chrono::Utc::now())
+    }
+
+    /// `check_exit` at an explicit point in time
+    pub fn check_exit_at(&self, current_price: f64, now: chrono::DateTime<chrono::Utc>) -> Option<ExitReason> {
         if self.is_unsellable {
             return None;
         }
 
-        // Don't check exits until we've synced price from PumpPortal
+        // Don't check price exits until we've synced price from PumpPortal
         if self.needs_price_sync() {
+            return self.time_exit(None, now);
+        }
+
+        self.check_price_exit(current_price)
+            .or_else(|| self.time_exit(Some(current_price), now))
+    }
+
+    /// Time-based exits only (max hold, stagnation) - used when no fresh price is available
+    pub fn check_time_exit(&self, now: chrono::DateTime<chrono::Utc>) -> Option<ExitReason> {
+        if self.is_unsellable {
             return None;
         }
+        self.time_exit(None, now)
+    }
+
+    fn time_exit(&self, current_price: Option<f64>, now: chrono::DateTime<chrono::Utc>) -> Option<ExitReason> {
+        let strategy = self.exit_strategy.as_ref()?;
+
+        if let Some(max_hold_minutes) = strategy.max_hold_minutes {
+            let held_minutes = (now - self.entry_time).num_minutes();
+            if held_minutes >= max_hold_minutes as i64 {
+                return Some(ExitReason::MaxHoldTime { held_minutes });
+            }
+        }
 
+        if let Some(stagnation_minutes) = strategy.stagnation_minutes {
+            // A price outside the band resets the window (see record_price)
+            let moved = current_price
+                .is_some_and(|p| !self.is_within_stagnation_band(p, strategy.stagnation_band_percent));
+            let since = self.stagnation_anchor_time.unwrap_or(self.entry_time);
+            let stagnant_minutes = (now - since).num_minutes();
+            if !moved && stagnant_minutes >= stagnation_minutes as i64 {
+                return Some(ExitReason::Stagnation {
+                    minutes: stagnant_minutes,
+                    band_percent: strategy.stagnation_band_percent,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Price-based exits: SL, trailing stop, ladder, TP
+    fn check_price_exit(&self, current_price: f64) -> Option<ExitReason> {
         // Stop loss always triggers full exit
         if current_price <= self.stop_loss_price {
             return Some(ExitReason::StopLoss);
         }
 
-        // For NINJA signals, use scaled exits
-        if self.is_ninja() {
-            let profit_percent = (current_price / self.entry_price - 1.0) * 100.0;
+        let profit_percent = (current_price / self.entry_price - 1.0) * 100.0;
+        let strategy = self.effective_strategy();
 
-            // Check each TP level based on current stage
-            match self.scaled_exit_stage {
-                0 if profit_percent >= Self::NINJA_TP1_PERCENT => {
-                    // Stage 0 -> 1: Sell 60% at +30%
-                    Some(ExitReason::ScaledTakeProfit {
-                        stage: 1,
-                        sell_percent: Self::NINJA_TP1_SELL_PERCENT,
-                        trigger_percent: Self::NINJA_TP1_PERCENT,
-                    })
+        // Trailing stop below the high-water mark (full exit)
+        if let Some(ref strategy) = strategy {
+            let high_price = self.high_price.max(current_price);
+            if let Some(trail_price) = strategy.trailing_stop_price(self.entry_price, high_price) {
+                if current_price <= trail_price {
+                    return Some(ExitReason::TrailingStop {
+                        high_price,
+                        trail_percent: strategy.trailing_stop_percent.unwrap_or_default(),
+                    });
                 }
-                1 if profit_percent >= Self::NINJA_TP2_PERCENT => {
-                    // Stage 1 -> 2: Sell 50% of remaining (20% of original) at +40%
-                    Some(ExitReason::ScaledTakeProfit {
-                        stage: 2,
-                        sell_percent: Self::NINJA_TP2_SELL_PERCENT,
-                        trigger_percent: Self::NINJA_TP2_PERCENT,
-                    })
-                }
-                2 if profit_percent >= Self::NINJA_TP3_PERCENT => {
-                    // Stage 2 -> 3: Sell 100% of remaining (20% of original) at +50%
-                    Some(ExitReason::ScaledTakeProfit {
-                        stage: 3,
-                        sell_percent: Self::NINJA_TP3_SELL_PERCENT,
-                        trigger_percent: Self::NINJA_TP3_PERCENT,
-                    })
+            }
+        }
+
+        // Next rung of the scaled exit ladder (if any)
+        if let Some(strategy) = strategy {
+            if let Some(rung) = strategy.rung(self.scaled_exit_stage) {
+                if percent_reached(profit_percent, rung.trigger_percent) {
+                    return Some(ExitReason::ScaledTakeProfit {
+                        stage: self.scaled_exit_stage + 1,
+                        sell_percent: rung.sell_percent,
+                        trigger_percent: rung.trigger_percent,
+                    });
                 }
-                _ => None,
+                // Still climbing the ladder - full TP only after the last rung
+                return None;
             }
+        }
+
+        // Standard exit: full position at TP
+        if current_price >= self.take_profit_price {
+            Some(ExitReason::TakeProfit)
         } else {
-            // Standard exit: full position at TP
-            if current_price >= self.take_profit_price {
-                Some(ExitReason::TakeProfit)
-            } else {
-                None
-            }
+            None
         }
     }
 
+    /// Tokens a partial exit (scaled or manual) would sell, and whether that closes the position
+    /// Pure calculation - the position is only advanced once the sell confirms
+    pub fn scaled_exit_amount(&self, sell_percent: f64) -> (u64, bool) {
+        let tokens_to_sell = (self.amount_tokens as f64 * sell_percent / 100.0) as u64;
+        (tokens_to_sell, tokens_to_sell >= self.amount_tokens)
+    }
+
     /// Advance to next scaled exit stage and reduce position
     /// Returns the amount of tokens to sell
     pub fn advance_scaled_exit(&mut self, stage: u8, sell_percent: f64) -> u64 {
@@ -261,8 +461,21 @@ impl Position {
         self.amount_tokens = self.amount_tokens.saturating_sub(tokens_to_sell);
         self.scaled_exit_stage = stage;
 
+        // Breakeven / profit lock after the configured stage
+        let locked_stop = self.effective_strategy()
+            .and_then(|s| s.locked_stop_price(self.entry_price, stage));
+        if let Some(locked_stop) = locked_stop {
+            if locked_stop > self.stop_loss_price {
+                info!(
+                    "🔒 {} stop moved after TP#{}: ${:.10} -> ${:.10}",
+                    self.token_symbol, stage, self.stop_loss_price, locked_stop
+                );
+                self.stop_loss_price = locked_stop;
+            }
+        }
+
         info!(
-            "🎯 NINJA scaled exit stage {} for {}: selling {} tokens ({:.0}%), {} remaining",
+            "🎯 Scaled exit stage {} for {}: selling {} tokens ({:.0}%), {} remaining",
             stage,
             self.token_symbol,
             tokens_to_sell,
@@ -273,6 +486,14 @@ impl Position {
         tokens_to_sell
     }
 
+    /// SOL cost of the tokens still held (cost basis shrinks with partial exits)
+    pub fn open_cost_sol(&self) -> f64 {
+        if self.original_amount_tokens == 0 {
+            return self.amount_sol_invested;
+        }
+        self.amount_sol_invested * self.amount_tokens as f64 / self.original_amount_tokens as f64
+    }
+
     /// Check if position is fully closed (no more tokens)
     pub fn is_fully_closed(&self) -> bool {
         self.amount_tokens == 0
@@ -298,26 +519,83 @@ impl Position {
 pub enum ExitReason {
     StopLoss,
     TakeProfit,
-    Manual,
-    /// Scaled take profit for NINJA signals (partial sells)
+    /// Operator command (control channel) - sells `sell_percent` of the position
+    Manual {
+        sell_percent: f64,
+    },
+    /// Trailing stop hit (price fell trail_percent below the high-water mark)
+    TrailingStop {
+        high_price: f64,
+        trail_percent: f64,
+    },
+    /// Position held longer than the strategy's max hold time
+    MaxHoldTime {
+        held_minutes: i64,
+    },
+    /// Price stayed within ±band_percent for `minutes`
+    Stagnation {
+        minutes: i64,
+        band_percent: f64,
+    },
+    /// Exit advisory: the smart wallets behind the entry are selling
+    SmartWalletExit {
+        sell_percent: f64,
+    },
+    /// Exit advisory: the buy consensus reversed
+    ConsensusReversed {
+        sell_percent: f64,
+    },
+    /// Scaled take profit from the exit strategy ladder (partial sells)
     ScaledTakeProfit {
-        stage: u8,           // 1, 2, or 3
+        stage: u8,           // 1-based ladder rung
         sell_percent: f64,   // Percentage of current position to sell
         trigger_percent: f64, // Profit % that triggered this exit
     },
+    /// Reconciliation found no on-chain balance (tokens left the wallet outside the bot)
+    ReconciledZeroBalance,
 }
 
 impl ExitReason {
-    /// Check if this is a partial exit (scaled TP)
+    /// Full manual exit
+    pub fn manual() -> Self {
+        ExitReason::Manual { sell_percent: 100.0 }
+    }
+
+    /// Check if this is a partial exit (scaled TP, manual or advisory sell of part of the position)
     pub fn is_partial(&self) -> bool {
-        matches!(self, ExitReason::ScaledTakeProfit { .. })
+        match self {
+            ExitReason::ScaledTakeProfit { .. } => true,
+            ExitReason::Manual { sell_percent }
+            | ExitReason::SmartWalletExit { sell_percent }
+            | ExitReason::ConsensusReversed { sell_percent } => *sell_percent < 100.0,
+            _ => false,
+        }
+    }
+
+    /// Machine-readable reason for TradeResult
+    pub fn code(&self) -> &'static str {
+        match self {
+            ExitReason::StopLoss => "stop_loss",
+            ExitReason::TakeProfit => "take_profit",
+            ExitReason::Manual { .. } => "manual",
+            ExitReason::TrailingStop { .. } => "trailing_stop",
+            ExitReason::MaxHoldTime { .. } => "max_hold_time",
+            ExitReason::Stagnation { .. } => "stagnation",
+            ExitReason::SmartWalletExit { .. } => "smart_wallet_exit",
+            ExitReason::ConsensusReversed { .. } => "consensus_reversed",
+            ExitReason::ScaledTakeProfit { .. } => "scaled_take_profit",
+            ExitReason::ReconciledZeroBalance => "reconciled_zero_balance",
+        }
     }
 
     /// Get the sell percentage for scaled exits (100 for full exits)
     pub fn sell_percent(&self) -> f64 {
         match self {
             ExitReason::ScaledTakeProfit { sell_percent, .. } => *sell_percent,
-            _ => 100.0, // Full exit for SL, TP, Manual
+            ExitReason::Manual { sell_percent }
+            | ExitReason::SmartWalletExit { sell_percent }
+            | ExitReason::ConsensusReversed { sell_percent } => sell_percent.min(100.0),
+            _ => 100.0, // Full exit for SL, TP, trailing stop, time exits
         }
     }
 }
@@ -327,10 +605,29 @@ impl std::fmt::Display for ExitReason {
         match self {
             ExitReason::StopLoss => write!(f, "Stop Loss"),
             ExitReason::TakeProfit => write!(f, "Take Profit"),
-            ExitReason::Manual => write!(f, "Manual"),
+            ExitReason::Manual { sell_percent } if *sell_percent < 100.0 => {
+                write!(f, "Manual ({:.0}%)", sell_percent)
+            }
+            ExitReason::Manual { .. } => write!(f, "Manual"),
+            ExitReason::TrailingStop { trail_percent, .. } => {
+                write!(f, "Trailing Stop (-{:.0}% from high)", trail_percent)
+            }
+            ExitReason::MaxHoldTime { held_minutes } => {
+                write!(f, "Max Hold Time ({}m)", held_minutes)
+            }
+            ExitReason::Stagnation { minutes, band_percent } => {
+                write!(f, "Stagnation (±{:.0}% for {}m)", band_percent, minutes)
+            }
+            ExitReason::SmartWalletExit { sell_percent } => {
+                write!(f, "Smart Wallet Exit ({:.0}%)", sell_percent)
+            }
+            ExitReason::ConsensusReversed { sell_percent } => {
+                write!(f, "Consensus Reversed ({:.0}%)", sell_percent)
+            }
             ExitReason::ScaledTakeProfit { stage, trigger_percent, .. } => {
                 write!(f, "Take Profit #{} (+{:.0}%)", stage, trigger_percent)
             }
+            ExitReason::ReconciledZeroBalance => write!(f, "Reconciled (zero balance)"),
         }
     }
 }
@@ -343,26 +640,109 @@ pub struct PnL {
     pub entry_price: f64,
 }
 
+/// Exit currently running for a position (in memory only - nothing is in flight after a restart)
+#[derive(Debug, Clone)]
+struct PendingExit {
+    reason: ExitReason,
+    /// Full exit that arrived while a partial one was running - runs next
+    escalation: Option<ExitReason>,
+}
+
+/// Outcome of asking to start an exit
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExitClaim {
+    /// No exit was running - the caller runs this one
+    Started,
+    /// An exit is already running and covers this trigger
+    Coalesced,
+    /// A partial exit is running - this full exit supersedes it and runs right after
+    Escalated,
+}
+
 /// Position manager - tracks all active positions
+/// Optionally backed by a `PositionStore` journal so positions survive restarts
 pub struct PositionManager {
     positions: Arc<RwLock<HashMap<String, Position>>>,
+    store: Option<Arc<PositionStore>>,
+    /// One exit per position at a time
+    pending_exits: RwLock<HashMap<String, PendingExit>>,
 }
 
 impl PositionManager {
     pub fn new() -> Self {
         Self {
             positions: Arc::new(RwLock::new(HashMap::new())),
+            store: None,
+            pending_exits: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Create a position manager that journals every change to disk
+    pub fn with_store(store: PositionStore) -> Self {
+        Self {
+            positions: Arc::new(RwLock::new(HashMap::new())),
+            store: Some(Arc::new(store)),
+            pending_exits: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Load open positions from the journal (call once at startup)
+    /// Returns the restored positions so the caller can resubscribe price feeds
+    pub async fn restore(&self) -> Result<Vec<Position>> {
+        let Some(ref store) = self.store else {
+            return Ok(Vec::new());
+        };
+
+        let loader = store.clone();
+        let restored = tokio::task::spawn_blocking(move || loader.load()).await??;
+        let mut positions = self.positions.write().await;
+        for position in &restored {
+            info!(
+                "♻️ Restored position {} ({}) | {} tokens @ ${:.10} | Stage: {}",
+                position.token_symbol,
+                &position.token_mint[..16.min(position.token_mint.len())],
+                position.amount_tokens,
+                position.entry_price,
+                position.scaled_exit_stage
+            );
+            positions.insert(position.token_mint.clone(), position.clone());
+        }
+
+        Ok(restored)
+    }
+
+    /// Queue a snapshot of the position for the journal - the write itself runs on the store thread
+    /// Called under the positions lock only so snapshots are queued in the order the changes were made
+    fn persist(&self, position: &Position) {
+        if let Some(ref store) = self.store {
+            store.record_upsert(position);
+        }
+    }
+
+    /// Wait until every queued journal write is on disk
+    pub async fn flush(&self) {
+        if let Some(store) = self.store.clone() {
+            if let Err(e) = tokio::task::spawn_blocking(move || store.flush()).await {
+                warn!("⚠️ Failed to flush position journal: {}", e);
+            }
         }
     }
 
     pub async fn add_position(&self, position: Position) {
         let mut positions = self.positions.write().await;
+        self.persist(&position);
         positions.insert(position.token_mint.clone(), position);
     }
 
     pub async fn remove_position(&self, token_mint: &str) -> Option<Position> {
         let mut positions = self.positions.write().await;
-        positions.remove(token_mint)
+        let removed = positions.remove(token_mint);
+        if removed.is_some() {
+            if let Some(ref store) = self.store {
+                store.record_remove(token_mint);
+            }
+        }
+        removed
     }
 
     pub async fn get_position(&self, token_mint: &str) -> Option<Position> {
@@ -385,6 +765,58 @@ impl PositionManager {
         positions.len()
     }
 
+    /// Claim the exit slot of a position before selling
+    /// A full exit arriving while a partial one runs is queued as an escalation; everything else is coalesced
+    pub async fn begin_exit(&self, token_mint: &str, reason: &ExitReason) -> ExitClaim {
+        let mut pending_exits = self.pending_exits.write().await;
+
+        let Some(pending) = pending_exits.get_mut(token_mint) else {
+            pending_exits.insert(token_mint.to_string(), PendingExit { reason: *reason, escalation: None });
+            return ExitClaim::Started;
+        };
+
+        if pending.reason.is_partial() && !reason.is_partial() && pending.escalation.is_none() {
+            pending.escalation = Some(*reason);
+            return ExitClaim::Escalated;
+        }
+
+        ExitClaim::Coalesced
+    }
+
+    /// Release the exit slot once a sell finished (confirmed or not)
+    /// Returns a queued escalation, which keeps the slot and must be run by the caller
+    pub async fn finish_exit(&self, token_mint: &str) -> Option<ExitReason> {
+        let mut pending_exits = self.pending_exits.write().await;
+
+        let escalation = pending_exits.get_mut(token_mint).and_then(|p| p.escalation.take());
+        match escalation {
+            Some(reason) => {
+                if let Some(pending) = pending_exits.get_mut(token_mint) {
+                    pending.reason = reason;
+                }
+                Some(reason)
+            }
+            None => {
+                pending_exits.remove(token_mint);
+                None
+            }
+        }
+    }
+
+    /// Exit currently running for a position
+    pub async fn pending_exit(&self, token_mint: &str) -> Option<ExitReason> {
+        self.pending_exits.read().await.get(token_mint).map(|p| p.reason)
+    }
+
+    /// A full exit is waiting behind the running partial one (stop retrying the partial)
+    pub async fn is_exit_superseded(&self, token_mint: &str) -> bool {
+        self.pending_exits
+            .read()
+            .await
+            .get(token_mint)
+            .is_some_and(|p| p.escalation.is_some())
+    }
+
     /// Increment failed sell attempts and mark as unsellable if too many failures
     /// Returns true if position was marked as unsellable
     pub async fn increment_failed_sell(&self, token_mint: &str) -> bool {
@@ -400,8 +832,10 @@ impl PositionManager {
                     "⚠️ {} marked as UNSELLABLE after {} failed sell attempts (no route found)",
                     position.token_symbol, position.failed_sell_attempts
                 );
+                self.persist(position);
                 return true;
             }
+            self.persist(position);
         }
         false
     }
@@ -415,9 +849,85 @@ impl PositionManager {
                 "⚠️ {} marked as UNSELLABLE: {}",
                 position.token_symbol, reason
             );
+            self.persist(position);
         }
     }
 
+    /// Clear the unsellable flag and the failed sell count (sells are tried again)
+    pub async fn unmark_unsellable(&self, token_mint: &str) -> bool {
+        let mut positions = self.positions.write().await;
+        let Some(position) = positions.get_mut(token_mint) else {
+            return false;
+        };
+        position.is_unsellable = false;
+        position.failed_sell_attempts = 0;
+        info!("✅ {} marked as sellable again", position.token_symbol);
+        self.persist(position);
+        true
+    }
+
+    /// Move a position's SL/TP (see `Position::set_exit_levels`), returns the updated position
+    /// Err if the levels are rejected, Ok(None) if there is no position
+    pub async fn set_exit_levels(
+        &self,
+        token_mint: &str,
+        stop_loss_percent: Option<f64>,
+        take_profit_percent: Option<f64>,
+        allow_lower_stop: bool,
+    ) -> Result<Option<Position>> {
+        let mut positions = self.positions.write().await;
+        let Some(position) = positions.get_mut(token_mint) else {
+            return Ok(None);
+        };
+        position.set_exit_levels(stop_loss_percent, take_profit_percent, allow_lower_stop)?;
+        info!(
+            "🎚️ {} exits adjusted: SL ${:.10} (-{:.0}%) | TP ${:.10} (+{:.0}%)",
+            position.token_symbol,
+            position.stop_loss_price,
+            position.stop_loss_percent.abs(),
+            position.take_profit_price,
+            position.take_profit_percent.abs()
+        );
+        self.persist(position);
+        Ok(Some(position.clone()))
+    }
+
+    /// Raise the stop to `percent` below the last observed price (entry price if none yet)
+    /// Returns (old, new) stop price, None if the stop is already that tight or there is no position
+    pub async fn tighten_stop(&self, token_mint: &str, percent: f64) -> Option<(f64, f64)> {
+        let mut positions = self.positions.write().await;
+        let position = positions.get_mut(token_mint)?;
+
+        let reference = if position.last_price > 0.0 { position.last_price } else { position.entry_price };
+        let tightened = reference * (1.0 - percent.abs() / 100.0);
+        if tightened <= position.stop_loss_price {
+            return None;
+        }
+
+        let old_stop = position.stop_loss_price;
+        position.stop_loss_price = tightened;
+        info!(
+            "🔒 {} stop tightened to {:.0}% below ${:.10}: ${:.10} -> ${:.10}",
+            position.token_symbol, percent.abs(), reference, old_stop, tightened
+        );
+        self.persist(position);
+        Some((old_stop, tightened))
+    }
+
+    /// Record a sell of this token by one of the position's trigger wallets
+    /// Returns (wallets sold, trigger wallets) the first time each trigger wallet sells, None otherwise
+    pub async fn record_trigger_sell(&self, token_mint: &str, wallet: &str) -> Option<(usize, usize)> {
+        let mut positions = self.positions.write().await;
+        let position = positions.get_mut(token_mint)?;
+        if !position.trigger_wallets.iter().any(|w| w == wallet) || position.trigger_wallets_sold.iter().any(|w| w == wallet) {
+            return None;
+        }
+
+        position.trigger_wallets_sold.push(wallet.to_string());
+        self.persist(position);
+        Some((position.trigger_wallets_sold.len(), position.trigger_wallets.len()))
+    }
+
     /// Sync position's entry price with real PumpPortal price
     /// This fixes the price discrepancy between backend and PumpPortal
     /// Returns true if sync was performed
@@ -426,32 +936,55 @@ impl PositionManager {
         if let Some(position) = positions.get_mut(token_mint) {
             if position.needs_price_sync() {
                 position.sync_with_real_price(real_price);
+                self.persist(position);
                 return true;
             }
         }
         false
     }
 
-    /// Update high price for logging (no trailing SL - we use scaled exits)
-    pub async fn update_high_price(&self, token_mint: &str, current_price: f64) {
+    /// Record an observed price (high-water mark + stagnation window)
+    /// Persisted only when an exit rule depends on the change
+    pub async fn record_price(&self, token_mint: &str, current_price: f64) {
         let mut positions = self.positions.write().await;
         if let Some(position) = positions.get_mut(token_mint) {
-            position.update_high_price(current_price);
+            if position.record_price(current_price, chrono::Utc::now()) {
+                self.persist(position);
+            }
         }
     }
 
-    /// Advance scaled exit stage for NINJA signals
+    /// Advance scaled exit stage (ladder rung sold)
     /// Returns (tokens_to_sell, position_fully_closed)
     pub async fn advance_scaled_exit(&self, token_mint: &str, stage: u8, sell_percent: f64) -> Option<(u64, bool)> {
         let mut positions = self.positions.write().await;
         if let Some(position) = positions.get_mut(token_mint) {
             let tokens_to_sell = position.advance_scaled_exit(stage, sell_percent);
             let fully_closed = position.is_fully_closed();
+            self.persist(position);
             return Some((tokens_to_sell, fully_closed));
         }
         None
     }
 
+    /// Overwrite token amount with the on-chain wallet balance (reconciliation)
+    pub async fn set_amount_tokens(&self, token_mint: &str, amount_tokens: u64) {
+        let mut positions = self.positions.write().await;
+        if let Some(position) = positions.get_mut(token_mint) {
+            info!(
+                "🔁 Reconciled {} amount: {} -> {} tokens (on-chain)",
+                position.token_symbol,
+                position.amount_tokens,
+                amount_tokens
+            );
+            position.amount_tokens = amount_tokens;
+            if position.original_amount_tokens < amount_tokens {
+                position.original_amount_tokens = amount_tokens;
+            }
+            self.persist(position);
+        }
+    }
+
     /// Update position after a partial sell (reduce tokens)
     pub async fn update_tokens_after_sell(&self, token_mint: &str, tokens_sold: u64) {
         let mut positions = self.positions.write().await;
@@ -463,6 +996,7 @@ impl PositionManager {
                 tokens_sold,
                 position.amount_tokens
             );
+            self.persist(position);
         }
     }
 }
@@ -472,3 +1006,248 @@ impl Default for PositionManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::ExitRung;
+
+    const ENTRY: f64 = 0.001;
+
+    /// Synced position at ENTRY with a -25% stop, far TP and the given strategy
+    fn position(strategy: ExitStrategy) -> Position {
+        let mut position = Position::new("MINT".into(), "TEST".into(), ENTRY, 1_000_000, 0.1, 25.0, 1000.0, "sig".into(), false)
+            .with_exit_strategy(strategy);
+        position.price_synced = true;
+        position
+    }
+
+    fn strategy() -> ExitStrategy {
+        ExitStrategy { name: "test".into(), ladder: Vec::new(), ..ExitStrategy::ninja() }
+    }
+
+    fn trailing(trail_percent: f64, activation_percent: f64) -> ExitStrategy {
+        ExitStrategy { trailing_stop_percent: Some(trail_percent), trailing_activation_percent: activation_percent, ..strategy() }
+    }
+
+    fn ladder(rungs: &[(f64, f64)], breakeven_after_stage: Option<u8>, profit_lock_percent: f64) -> ExitStrategy {
+        ExitStrategy {
+            ladder: rungs.iter().map(|&(trigger_percent, sell_percent)| ExitRung { trigger_percent, sell_percent }).collect(),
+            breakeven_after_stage,
+            profit_lock_percent,
+            ..strategy()
+        }
+    }
+
+    #[test]
+    fn stop_loss_triggers_at_the_stop_price() {
+        let position = position(strategy());
+        assert_eq!(position.check_exit(position.stop_loss_price), Some(ExitReason::StopLoss));
+        assert_eq!(position.check_exit(position.stop_loss_price * 1.0001), None);
+    }
+
+    #[test]
+    fn trailing_stop_follows_the_high_water_mark() {
+        let mut position = position(trailing(10.0, 0.0));
+        let now = chrono::Utc::now();
+
+        assert!(position.record_price(ENTRY * 2.0, now), "new high is worth persisting");
+        assert_eq!(position.high_price, ENTRY * 2.0);
+        assert!(!position.record_price(ENTRY * 1.9, now));
+        assert_eq!(position.high_price, ENTRY * 2.0, "high never drops");
+
+        // Exactly 10% below the high triggers, just above doesn't
+        let trail_price = ENTRY * 2.0 * 0.9;
+        assert_eq!(position.check_exit(trail_price * 1.0001), None);
+        assert_eq!(
+            position.check_exit(trail_price),
+            Some(ExitReason::TrailingStop { high_price: ENTRY * 2.0, trail_percent: 10.0 })
+        );
+    }
+
+    #[test]
+    fn trailing_stop_arms_exactly_at_the_activation_level() {
+        let mut position = position(trailing(10.0, 30.0));
+
+        // High +29% - not armed, a 10% pullback is no exit
+        position.high_price = ENTRY * 1.29;
+        assert_eq!(position.check_exit(ENTRY * 1.29 * 0.9), None);
+
+        // High exactly +30% arms it
+        position.high_price = ENTRY * 1.3;
+        assert!(matches!(position.check_exit(ENTRY * 1.3 * 0.9), Some(ExitReason::TrailingStop { .. })));
+    }
+
+    #[test]
+    fn ladder_rung_triggers_exactly_at_its_target() {
+        let position = position(ladder(&[(30.0, 50.0), (60.0, 100.0)], None, 0.0));
+
+        assert_eq!(position.check_exit(ENTRY * 1.2999), None);
+        assert_eq!(
+            position.check_exit(ENTRY * 1.3),
+            Some(ExitReason::ScaledTakeProfit { stage: 1, sell_percent: 50.0, trigger_percent: 30.0 })
+        );
+    }
+
+    #[test]
+    fn partial_exit_then_breakeven_stop() {
+        let mut position = position(ladder(&[(30.0, 50.0), (60.0, 100.0)], Some(1), 0.0));
+
+        let sold = position.advance_scaled_exit(1, 50.0);
+        assert_eq!(sold, 500_000);
+        assert_eq!(position.amount_tokens, 500_000);
+        assert_eq!(position.stop_loss_price, ENTRY, "stop at breakeven after TP1");
+
+        // Back to entry - the rest goes at breakeven; next rung still ahead above
+        assert_eq!(position.check_exit(ENTRY), Some(ExitReason::StopLoss));
+        assert_eq!(position.check_exit(ENTRY * 1.3), None);
+        assert!(matches!(position.check_exit(ENTRY * 1.6), Some(ExitReason::ScaledTakeProfit { stage: 2, .. })));
+    }
+
+    #[test]
+    fn profit_lock_waits_for_its_stage_and_never_lowers_the_stop() {
+        let mut position = position(ladder(&[(30.0, 50.0), (60.0, 50.0), (90.0, 100.0)], Some(2), 10.0));
+        let initial_stop = position.stop_loss_price;
+
+        position.advance_scaled_exit(1, 50.0);
+        assert_eq!(position.stop_loss_price, initial_stop, "lock only after stage 2");
+
+        position.advance_scaled_exit(2, 50.0);
+        assert!((position.stop_loss_price - ENTRY * 1.1).abs() < 1e-15);
+
+        // A stop already above the lock stays where it is
+        position.stop_loss_price = ENTRY * 1.5;
+        position.advance_scaled_exit(3, 100.0);
+        assert_eq!(position.stop_loss_price, ENTRY * 1.5);
+        assert!(position.is_fully_closed());
+    }
+
+    fn timed(max_hold_minutes: Option<u64>, stagnation_minutes: Option<u64>) -> ExitStrategy {
+        ExitStrategy { max_hold_minutes, stagnation_minutes, stagnation_band_percent: 5.0, ..strategy() }
+    }
+
+    #[test]
+    fn max_hold_exits_once_the_time_is_up() {
+        let position = position(timed(Some(60), None));
+        let entry = position.entry_time;
+
+        assert_eq!(position.check_exit_at(ENTRY, entry + chrono::Duration::seconds(59 * 60 + 59)), None);
+        assert_eq!(
+            position.check_exit_at(ENTRY, entry + chrono::Duration::minutes(60)),
+            Some(ExitReason::MaxHoldTime { held_minutes: 60 })
+        );
+        // Also without a price (no feed), and before the price sync
+        let mut unsynced = position.clone();
+        unsynced.is_pumpfun = true;
+        unsynced.price_synced = false;
+        assert!(matches!(unsynced.check_exit_at(ENTRY, entry + chrono::Duration::minutes(61)), Some(ExitReason::MaxHoldTime { .. })));
+        assert!(matches!(position.check_time_exit(entry + chrono::Duration::minutes(61)), Some(ExitReason::MaxHoldTime { .. })));
+    }
+
+    #[test]
+    fn price_exits_win_over_time_exits() {
+        let position = position(timed(Some(60), None));
+        let late = position.entry_time + chrono::Duration::minutes(90);
+        assert_eq!(position.check_exit_at(position.stop_loss_price, late), Some(ExitReason::StopLoss));
+    }
+
+    #[test]
+    fn stagnation_exits_when_price_stays_in_the_band() {
+        let mut position = position(timed(None, Some(30)));
+        let entry = position.entry_time;
+
+        // +5% is still inside the ±5% band (edge included)
+        assert!(!position.record_price(ENTRY * 1.05, entry + chrono::Duration::minutes(10)));
+        assert_eq!(position.check_exit_at(ENTRY * 1.05, entry + chrono::Duration::minutes(29)), None);
+        assert_eq!(
+            position.check_exit_at(ENTRY * 1.05, entry + chrono::Duration::minutes(30)),
+            Some(ExitReason::Stagnation { minutes: 30, band_percent: 5.0 })
+        );
+
+        // A price outside the band is no stagnation, even with the window expired
+        assert_eq!(position.check_exit_at(ENTRY * 1.2, entry + chrono::Duration::minutes(31)), None);
+    }
+
+    #[test]
+    fn leaving_the_band_restarts_the_stagnation_window() {
+        let mut position = position(timed(None, Some(30)));
+        let entry = position.entry_time;
+        let moved_at = entry + chrono::Duration::minutes(20);
+
+        assert!(position.record_price(ENTRY * 1.2, moved_at), "new anchor is worth persisting");
+        assert_eq!(position.stagnation_anchor_price, ENTRY * 1.2);
+
+        // Measured from the move now: +1% around the new anchor for 29 then 30 minutes
+        assert_eq!(position.check_exit_at(ENTRY * 1.212, moved_at + chrono::Duration::minutes(29)), None);
+        assert!(matches!(
+            position.check_exit_at(ENTRY * 1.212, moved_at + chrono::Duration::minutes(30)),
+            Some(ExitReason::Stagnation { minutes: 30, .. })
+        ));
+    }
+
+    #[test]
+    fn unsellable_positions_never_exit() {
+        let mut position = position(timed(Some(1), Some(1)));
+        position.is_unsellable = true;
+        let late = position.entry_time + chrono::Duration::hours(5);
+        assert_eq!(position.check_exit_at(0.0, late), None);
+        assert_eq!(position.check_time_exit(late), None);
+    }
+
+    #[test]
+    fn set_exit_levels_rejects_out_of_range_values() {
+        let mut position = position(strategy());
+        let (stop, target) = (position.stop_loss_price, position.take_profit_price);
+
+        for sl in [0.0, -10.0, 100.0, 150.0, f64::NAN] {
+            assert!(position.set_exit_levels(Some(sl), None, true).is_err(), "SL {}", sl);
+        }
+        for tp in [0.0, -50.0, f64::NAN, f64::INFINITY] {
+            assert!(position.set_exit_levels(None, Some(tp), true).is_err(), "TP {}", tp);
+        }
+        // A bad TP rejects a good SL sent with it
+        assert!(position.set_exit_levels(Some(10.0), Some(-1.0), false).is_err());
+        assert_eq!((position.stop_loss_price, position.take_profit_price), (stop, target));
+
+        position.set_exit_levels(Some(10.0), Some(200.0), false).unwrap();
+        assert!((position.stop_loss_price - ENTRY * 0.9).abs() < 1e-15);
+        assert!((position.take_profit_price - ENTRY * 3.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn set_exit_levels_never_lowers_the_stop_unless_asked() {
+        let mut position = position(ladder(&[(30.0, 50.0), (60.0, 100.0)], Some(1), 0.0));
+        position.advance_scaled_exit(1, 50.0);
+        assert_eq!(position.stop_loss_price, ENTRY);
+
+        // Breakeven lock stays; TP alone is still accepted
+        assert!(position.set_exit_levels(Some(25.0), None, false).is_err());
+        assert_eq!(position.stop_loss_price, ENTRY);
+        position.set_exit_levels(None, Some(500.0), false).unwrap();
+        assert_eq!(position.stop_loss_price, ENTRY);
+
+        position.set_exit_levels(Some(25.0), None, true).unwrap();
+        assert!((position.stop_loss_price - ENTRY * 0.75).abs() < 1e-15);
+    }
+
+    #[test]
+    fn position_without_an_entry_price_waits_for_the_first_price() {
+        let mut position = Position::new("MINT".into(), "TEST".into(), 0.0, 1_000_000, 0.1, 25.0, 100.0, "sig".into(), false)
+            .with_exit_strategy(ladder(&[(30.0, 50.0)], None, 0.0));
+        assert!(position.needs_price_sync());
+        assert_eq!(position.check_exit(ENTRY), None, "no TP or rung off a zero entry");
+
+        position.sync_with_real_price(ENTRY);
+        assert!(!position.needs_price_sync());
+        assert!((position.stop_loss_price - ENTRY * 0.75).abs() < 1e-15);
+        assert_eq!(position.check_exit(ENTRY), None);
+        assert!(matches!(position.check_exit(ENTRY * 1.3), Some(ExitReason::ScaledTakeProfit { stage: 1, .. })));
+    }
+
+    #[test]
+    fn priced_jupiter_positions_need_no_sync() {
+        let position = Position::new("MINT".into(), "TEST".into(), ENTRY, 1_000_000, 0.1, 25.0, 100.0, "sig".into(), false);
+        assert!(!position.needs_price_sync());
+        assert_eq!(position.check_exit(ENTRY * 0.75), Some(ExitReason::StopLoss));
+    }
+}
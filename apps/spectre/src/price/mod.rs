@@ -0,0 +1,30 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+use crate::birdeye::BirdeyeClient;
+use crate::pumpportal::PumpPortalClient;
+
+/// Where the position monitor and reconciliation read token prices from
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    /// Current price in USD per whole token
+    async fn get_price(&self, token_mint: &str) -> Result<f64>;
+}
+
+/// DexScreener with Birdeye fallback (or recorded responses in replay)
+#[async_trait]
+impl PriceSource for BirdeyeClient {
+    async fn get_price(&self, token_mint: &str) -> Result<f64> {
+        BirdeyeClient::get_price(self, token_mint).await
+    }
+}
+
+/// Last price seen on the PumpPortal WebSocket (only subscribed pump.fun tokens)
+#[async_trait]
+impl PriceSource for PumpPortalClient {
+    async fn get_price(&self, token_mint: &str) -> Result<f64> {
+        PumpPortalClient::get_price(self, token_mint)
+            .await
+            .ok_or_else(|| anyhow!("No PumpPortal price for {}", token_mint))
+    }
+}
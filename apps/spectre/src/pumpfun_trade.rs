@@ -1,13 +1,17 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
+use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use solana_sdk::{
-    signature::{Keypair, Signer},
-    transaction::VersionedTransaction,
-};
-use tracing::{debug, info, warn};
+use solana_sdk::transaction::VersionedTransaction;
+use tracing::{debug, info};
 
-const PUMPPORTAL_API_URL: &str = "https://pumpportal.fun/api/trade-local";
+use crate::error::SpectreError;
+use crate::venue::{BuyOrder, RetryPolicy, SellOrder, Side, SwapTx, SwapVenue};
+
+pub const PUMPPORTAL_API_URL: &str = "https://pumpportal.fun/api/trade-local";
+
+/// pump.fun mints always use 6 decimals
+pub const PUMPFUN_DECIMALS: u8 = 6;
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +27,39 @@ pub struct PumpTradeRequest {
     pub pool: Option<String>,  // "pump" for bonding curve
 }
 
+/// How much to sell on the pump.fun sell path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PumpSellAmount {
+    /// Exact amount in raw token units (converted to whole tokens with the mint's decimals)
+    Tokens { raw_amount: u64, decimals: u8 },
+    /// Percentage of the wallet's current balance (100.0 = everything)
+    Percent(f64),
+}
+
+impl PumpSellAmount {
+    /// Format as PumpPortal `amount` field ("123.456" tokens or "80%")
+    fn to_request_amount(self) -> String {
+        match self {
+            PumpSellAmount::Tokens { raw_amount, decimals } => {
+                let whole = raw_amount as f64 / 10f64.powi(decimals as i32);
+                format!("{:.*}", decimals as usize, whole)
+            }
+            PumpSellAmount::Percent(percent) => {
+                format!("{}%", percent.clamp(0.0, 100.0))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for PumpSellAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PumpSellAmount::Tokens { .. } => write!(f, "{} tokens", self.to_request_amount()),
+            PumpSellAmount::Percent(_) => write!(f, "{} of balance", self.to_request_amount()),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PumpTradeResponse {
     // Response is raw bytes (base64 encoded transaction)
@@ -30,15 +67,22 @@ pub struct PumpTradeResponse {
 
 pub struct PumpfunTrader {
     client: Client,
+    api_url: String,
 }
 
 impl PumpfunTrader {
     pub fn new() -> Self {
+        Self::with_api_url(PUMPPORTAL_API_URL)
+    }
+
+    /// Trader on a custom trade-local endpoint
+    pub fn with_api_url(api_url: &str) -> Self {
         Self {
             client: Client::builder()
                 .timeout(std::time::Duration::from_secs(10))
                 .build()
                 .expect("Failed to create HTTP client"),
+            api_url: api_url.to_string(),
         }
     }
 
@@ -66,19 +110,21 @@ impl PumpfunTrader {
         debug!("PumpPortal buy request: {:?}", request);
 
         let response = self.client
-            .post(PUMPPORTAL_API_URL)
+            .post(&self.api_url)
             .json(&request)
             .send()
-            .await?;
+            .await
+            .map_err(|e| SpectreError::http("PumpPortal trade", e))?;
 
         if !response.status().is_success() {
-            let status = response.status();
+            let status = response.status().as_u16();
             let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("PumpPortal API error {}: {}", status, error_text));
+            return Err(SpectreError::from_venue_response("PumpPortal trade", status, &error_text).into());
         }
 
         // Response is raw bytes (serialized transaction)
-        let tx_bytes = response.bytes().await?;
+        let tx_bytes = response.bytes().await
+            .map_err(|e| SpectreError::http("PumpPortal trade", e))?;
 
         info!("📦 Got pump.fun transaction ({} bytes)", tx_bytes.len());
 
@@ -86,12 +132,13 @@ impl PumpfunTrader {
     }
 
     /// Get a sell transaction for pump.fun bonding curve
-    /// Uses "100%" to sell all tokens (avoids token amount mismatch issues)
+    /// Full exits should use `PumpSellAmount::Percent(100.0)` (avoids token amount mismatch issues),
+    /// scaled exits an exact token amount or percentage
     pub async fn get_sell_transaction(
         &self,
         wallet_pubkey: &str,
         token_mint: &str,
-        _amount_tokens: u64, // Ignored - we always sell 100%
+        amount: PumpSellAmount,
         slippage_percent: u16,
         priority_fee_sol: f64,
     ) -> Result<Vec<u8>> {
@@ -99,7 +146,7 @@ impl PumpfunTrader {
             public_key: wallet_pubkey.to_string(),
             action: "sell".to_string(),
             mint: token_mint.to_string(),
-            amount: "100%".to_string(), // Always sell all tokens to avoid balance mismatch
+            amount: amount.to_request_amount(),
             denominated_in_sol: "false".to_string(),
             slippage: slippage_percent,
             priority_fee: priority_fee_sol,
@@ -109,46 +156,116 @@ impl PumpfunTrader {
         debug!("PumpPortal sell request: {:?}", request);
 
         let response = self.client
-            .post(PUMPPORTAL_API_URL)
+            .post(&self.api_url)
             .json(&request)
             .send()
-            .await?;
+            .await
+            .map_err(|e| SpectreError::http("PumpPortal trade", e))?;
 
         if !response.status().is_success() {
-            let status = response.status();
+            let status = response.status().as_u16();
             let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("PumpPortal API error {}: {}", status, error_text));
+            return Err(SpectreError::from_venue_response("PumpPortal trade", status, &error_text).into());
         }
 
-        let tx_bytes = response.bytes().await?;
+        let tx_bytes = response.bytes().await
+            .map_err(|e| SpectreError::http("PumpPortal trade", e))?;
 
         info!("📦 Got pump.fun sell transaction ({} bytes)", tx_bytes.len());
 
         Ok(tx_bytes.to_vec())
     }
 
-    /// Deserialize and sign a transaction from PumpPortal
-    pub fn sign_transaction(
-        &self,
-        tx_bytes: &[u8],
-        wallet: &Keypair,
-    ) -> Result<VersionedTransaction> {
-        // Deserialize the transaction
-        let mut transaction: VersionedTransaction = bincode::deserialize(tx_bytes)
-            .map_err(|e| anyhow!("Failed to deserialize transaction: {}", e))?;
-
-        // Sign with wallet
-        let message_data = transaction.message.serialize();
-        let signature = wallet.sign_message(&message_data);
-
-        // Update signature (first signer is our wallet)
-        if transaction.signatures.is_empty() {
-            transaction.signatures.push(signature);
-        } else {
-            transaction.signatures[0] = signature;
+    /// Decide what to send to PumpPortal for a sell
+    /// - full exit: 100% of the wallet balance (no dust left behind)
+    /// - partial exit: exact token amount when the fill (and decimals) were read on-chain,
+    ///   otherwise the same percentage of the wallet balance (our amount is only an estimate)
+    fn sell_amount(order: &SellOrder) -> PumpSellAmount {
+        if order.full_exit {
+            return PumpSellAmount::Percent(100.0);
         }
 
-        Ok(transaction)
+        match order.decimals {
+            Some(decimals) => PumpSellAmount::Tokens {
+                raw_amount: order.amount_tokens,
+                decimals,
+            },
+            None => PumpSellAmount::Percent(order.sell_percent),
+        }
+    }
+}
+
+/// Deserialize a transaction returned by PumpPortal
+fn deserialize_transaction(tx_bytes: &[u8]) -> Result<VersionedTransaction> {
+    bincode::deserialize(tx_bytes)
+        .map_err(|e| SpectreError::http("PumpPortal transaction decode", e).into())
+}
+
+#[async_trait]
+impl SwapVenue for PumpfunTrader {
+    fn name(&self) -> &'static str {
+        "pumpfun"
+    }
+
+    fn label(&self) -> &'static str {
+        "PUMP.FUN"
+    }
+
+    fn retry_policy(&self, side: Side) -> RetryPolicy {
+        match side {
+            Side::Buy => RetryPolicy { max_attempts: 2, retry_delay_ms: 500 },
+            Side::Sell => RetryPolicy { max_attempts: 3, retry_delay_ms: 500 },
+        }
+    }
+
+    fn token_decimals(&self) -> Option<u8> {
+        Some(PUMPFUN_DECIMALS)
+    }
+
+    /// PumpPortal has no quote API - paper fills use the signal price instead
+    async fn quote_buy(&self, _token_mint: &str, _amount_lamports: u64, _slippage_bps: u16) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    /// PumpPortal has no quote API - paper fills use the last observed price instead
+    async fn quote_sell(&self, _token_mint: &str, _amount_tokens: u64, _slippage_bps: u16) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    async fn build_buy(&self, order: &BuyOrder) -> Result<SwapTx> {
+        let tx_bytes = self.get_buy_transaction(
+            &order.wallet.to_string(),
+            &order.token_mint,
+            order.amount_sol,
+            order.slippage_bps / 100,
+            order.priority_fee_lamports as f64 / 1e9,
+        ).await?;
+
+        Ok(SwapTx {
+            transaction: deserialize_transaction(&tx_bytes)?,
+            quoted_out: None,
+        })
+    }
+
+    async fn build_sell(&self, order: &SellOrder) -> Result<SwapTx> {
+        let amount = Self::sell_amount(order);
+        info!("📦 pump.fun sell amount: {}", amount);
+
+        // Increase slippage on retries
+        let slippage_percent = order.slippage_bps / 100 + ((order.attempt - 1) * 5) as u16;
+
+        let tx_bytes = self.get_sell_transaction(
+            &order.wallet.to_string(),
+            &order.token_mint,
+            amount,
+            slippage_percent,
+            order.priority_fee_lamports as f64 / 1e9,
+        ).await?;
+
+        Ok(SwapTx {
+            transaction: deserialize_transaction(&tx_bytes)?,
+            quoted_out: None,
+        })
     }
 }
 
@@ -157,3 +274,54 @@ impl Default for PumpfunTrader {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn order(amount_tokens: u64, decimals: Option<u8>, full_exit: bool, sell_percent: f64) -> SellOrder {
+        SellOrder {
+            wallet: Pubkey::new_unique(),
+            token_mint: "mint".into(),
+            amount_tokens,
+            decimals,
+            full_exit,
+            sell_percent,
+            slippage_bps: 1_000,
+            priority_fee_lamports: 0,
+            attempt: 1,
+        }
+    }
+
+    #[test]
+    fn full_exit_sells_the_whole_balance() {
+        let amount = PumpfunTrader::sell_amount(&order(1_000_000, Some(6), true, 100.0));
+        assert_eq!(amount, PumpSellAmount::Percent(100.0));
+        assert_eq!(amount.to_request_amount(), "100%");
+    }
+
+    #[test]
+    fn partial_exit_with_known_decimals_sells_exact_tokens() {
+        let amount = PumpfunTrader::sell_amount(&order(800_000_123_456, Some(6), false, 80.0));
+        assert_eq!(amount, PumpSellAmount::Tokens { raw_amount: 800_000_123_456, decimals: 6 });
+        assert_eq!(amount.to_request_amount(), "800000.123456");
+    }
+
+    #[test]
+    fn partial_exit_without_decimals_sells_a_percent_of_the_balance() {
+        let amount = PumpfunTrader::sell_amount(&order(800_000, None, false, 80.0));
+        assert_eq!(amount, PumpSellAmount::Percent(80.0));
+        assert_eq!(amount.to_request_amount(), "80%");
+    }
+
+    #[test]
+    fn token_amounts_keep_every_decimal_of_the_mint() {
+        let tokens = |raw_amount, decimals| PumpSellAmount::Tokens { raw_amount, decimals }.to_request_amount();
+        assert_eq!(tokens(1, 6), "0.000001");
+        assert_eq!(tokens(5_000_000, 6), "5.000000");
+        assert_eq!(tokens(123_456_789, 9), "0.123456789");
+        assert_eq!(tokens(42, 0), "42");
+        assert_eq!(PumpSellAmount::Percent(150.0).to_request_amount(), "100%");
+    }
+}
@@ -1,13 +1,15 @@
 use anyhow::{anyhow, Result};
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{info, warn, error, debug};
 
-const PUMPPORTAL_WS_URL: &str = "wss://pumpportal.fun/api/data";
+use crate::capture::{self, CaptureEvent};
+
+pub const PUMPPORTAL_WS_URL: &str = "wss://pumpportal.fun/api/data";
 
 #[derive(Debug, Clone, Serialize)]
 struct SubscribeMessage {
@@ -15,7 +17,7 @@ struct SubscribeMessage {
     keys: Vec<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TradeEvent {
     pub signature: Option<String>,
@@ -28,6 +30,40 @@ pub struct TradeEvent {
     pub virtual_sol_reserves: Option<f64>,
     pub virtual_token_reserves: Option<f64>,
     pub market_cap_sol: Option<f64>,
+    /// Wallet that traded (PumpPortal's name for `user`)
+    pub trader_public_key: Option<String>,
+    /// "buy", "sell" or "create"
+    pub tx_type: Option<String>,
+}
+
+impl TradeEvent {
+    /// Wallet behind the trade
+    pub fn trader(&self) -> Option<&str> {
+        self.trader_public_key.as_deref().or(self.user.as_deref())
+    }
+
+    pub fn is_sell(&self) -> bool {
+        match self.tx_type.as_deref() {
+            Some(tx_type) => tx_type == "sell",
+            None => self.is_buy == Some(false),
+        }
+    }
+
+    /// The trade as a sell by one of the watched wallets
+    fn wallet_sell<'a>(&self, watched: impl IntoIterator<Item = &'a String>) -> Option<WalletSell> {
+        let trader = self.trader()?;
+        if !self.is_sell() || !watched.into_iter().any(|w| w == trader) {
+            return None;
+        }
+        Some(WalletSell {
+            wallet: trader.to_string(),
+            token_mint: self.mint.clone(),
+            signature: self.signature.clone(),
+            sol_amount: self.sol_amount,
+            token_amount: self.token_amount,
+            timestamp: self.timestamp.unwrap_or_else(|| chrono::Utc::now().timestamp()),
+        })
+    }
 }
 
 /// Price update from trade events
@@ -39,58 +75,172 @@ pub struct PriceUpdate {
     pub timestamp: i64,
 }
 
+/// Sell by a watched wallet (account trade subscription)
+#[derive(Debug, Clone)]
+pub struct WalletSell {
+    pub wallet: String,
+    pub token_mint: String,
+    pub signature: Option<String>,
+    pub sol_amount: Option<f64>,
+    pub token_amount: Option<f64>,
+    pub timestamp: i64,
+}
+
+/// Subscribe request for the WebSocket handler
+#[derive(Debug, Clone)]
+enum Subscription {
+    /// subscribeTokenTrade - prices of a token
+    Token(String),
+    /// subscribeAccountTrade - trades of a wallet
+    Account(String),
+}
+
 /// PumpPortal WebSocket client for real-time price monitoring
 pub struct PumpPortalClient {
+    ws_url: String,
     /// Current prices for subscribed tokens (token_mint -> price_usd)
     prices: Arc<RwLock<HashMap<String, f64>>>,
     /// Channel to send subscribe requests
-    subscribe_tx: Option<mpsc::UnboundedSender<String>>,
-    /// Channel to receive price updates
-    price_rx: Option<mpsc::UnboundedReceiver<PriceUpdate>>,
+    subscribe_tx: Option<mpsc::UnboundedSender<Subscription>>,
     /// SOL price in USD (updated periodically)
     sol_price_usd: Arc<RwLock<f64>>,
+    /// Sells by subscribed wallets
+    wallet_sell_tx: mpsc::UnboundedSender<WalletSell>,
+    wallet_sell_rx: Option<mpsc::UnboundedReceiver<WalletSell>>,
 }
 
 impl PumpPortalClient {
     pub fn new() -> Self {
+        Self::with_ws_url(PUMPPORTAL_WS_URL)
+    }
+
+    /// Client on a custom WebSocket endpoint
+    pub fn with_ws_url(ws_url: &str) -> Self {
+        let (wallet_sell_tx, wallet_sell_rx) = mpsc::unbounded_channel();
         Self {
+            ws_url: ws_url.to_string(),
             prices: Arc::new(RwLock::new(HashMap::new())),
             subscribe_tx: None,
-            price_rx: None,
             sol_price_usd: Arc::new(RwLock::new(200.0)), // Default SOL price
+            wallet_sell_tx,
+            wallet_sell_rx: Some(wallet_sell_rx),
         }
     }
 
+    /// Receiver of sells by wallets subscribed with `subscribe_accounts` (can be taken once)
+    pub fn take_wallet_sells(&mut self) -> Option<mpsc::UnboundedReceiver<WalletSell>> {
+        self.wallet_sell_rx.take()
+    }
+
     /// Start the WebSocket connection and return price receiver
     pub async fn start(&mut self, initial_sol_price: f64) -> Result<mpsc::UnboundedReceiver<PriceUpdate>> {
         *self.sol_price_usd.write().await = initial_sol_price;
 
-        let (subscribe_tx, subscribe_rx) = mpsc::unbounded_channel::<String>();
+        let (subscribe_tx, subscribe_rx) = mpsc::unbounded_channel::<Subscription>();
         let (price_tx, price_rx) = mpsc::unbounded_channel::<PriceUpdate>();
 
         self.subscribe_tx = Some(subscribe_tx);
 
+        let ws_url = self.ws_url.clone();
         let prices = self.prices.clone();
         let sol_price = self.sol_price_usd.clone();
+        let wallet_sell_tx = self.wallet_sell_tx.clone();
 
         // Spawn WebSocket handler
         tokio::spawn(async move {
-            Self::ws_handler(subscribe_rx, price_tx, prices, sol_price).await;
+            Self::ws_handler(ws_url, subscribe_rx, price_tx, wallet_sell_tx, prices, sol_price).await;
         });
 
         Ok(price_rx)
     }
 
+    /// Replay mode: trade events come from a capture instead of the WebSocket
+    /// Like the live feed, a mint's events are only delivered once it is subscribed
+    /// (held back until then), and wallet sells once the wallet is. Returns the sender to feed them and the same price receiver as `start`
+    pub async fn start_replay(
+        &mut self,
+        initial_sol_price: f64,
+    ) -> (mpsc::UnboundedSender<TradeEvent>, mpsc::UnboundedReceiver<PriceUpdate>) {
+        *self.sol_price_usd.write().await = initial_sol_price;
+
+        let (subscribe_tx, mut subscribe_rx) = mpsc::unbounded_channel::<Subscription>();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel::<TradeEvent>();
+        let (price_tx, price_rx) = mpsc::unbounded_channel::<PriceUpdate>();
+
+        self.subscribe_tx = Some(subscribe_tx);
+
+        let prices = self.prices.clone();
+        let sol_price = self.sol_price_usd.clone();
+        let wallet_sell_tx = self.wallet_sell_tx.clone();
+
+        tokio::spawn(async move {
+            let mut subscribed: HashSet<String> = HashSet::new();
+            let mut accounts: HashSet<String> = HashSet::new();
+            let mut held: HashMap<String, Vec<TradeEvent>> = HashMap::new();
+            let mut capture_done = false;
+
+            loop {
+                tokio::select! {
+                    Some(subscription) = subscribe_rx.recv() => {
+                        match subscription {
+                            Subscription::Token(token_mint) => {
+                                if subscribed.insert(token_mint.clone()) {
+                                    for trade in held.remove(&token_mint).unwrap_or_default() {
+                                        Self::handle_trade(&trade, &prices, &sol_price, &price_tx).await;
+                                    }
+                                }
+                            }
+                            Subscription::Account(wallet) => {
+                                accounts.insert(wallet);
+                            }
+                        }
+                    }
+
+                    trade = event_rx.recv(), if !capture_done => {
+                        if let Some(sell) = trade.as_ref().and_then(|t| t.wallet_sell(&accounts)) {
+                            let _ = wallet_sell_tx.send(sell);
+                        }
+                        match trade {
+                            Some(trade) if subscribed.contains(&trade.mint) => {
+                                Self::handle_trade(&trade, &prices, &sol_price, &price_tx).await;
+                            }
+                            Some(trade) => held.entry(trade.mint.clone()).or_default().push(trade),
+                            None => capture_done = true,
+                        }
+                    }
+
+                    else => break,
+                }
+            }
+        });
+
+        (event_tx, price_rx)
+    }
+
     /// Subscribe to price updates for a token
     pub async fn subscribe_token(&self, token_mint: &str) -> Result<()> {
         if let Some(ref tx) = self.subscribe_tx {
-            tx.send(token_mint.to_string())
+            tx.send(Subscription::Token(token_mint.to_string()))
                 .map_err(|e| anyhow!("Failed to send subscribe request: {}", e))?;
             info!("📡 Subscribing to price updates for {}", &token_mint[..8.min(token_mint.len())]);
         }
         Ok(())
     }
 
+    /// Subscribe to the trades of wallets (their sells come out of `take_wallet_sells`)
+    pub async fn subscribe_accounts(&self, wallets: &[String]) -> Result<()> {
+        if let Some(ref tx) = self.subscribe_tx {
+            for wallet in wallets {
+                tx.send(Subscription::Account(wallet.clone()))
+                    .map_err(|e| anyhow!("Failed to send subscribe request: {}", e))?;
+            }
+            if !wallets.is_empty() {
+                info!("👀 Subscribing to trades of {} wallet(s)", wallets.len());
+            }
+        }
+        Ok(())
+    }
+
     /// Get current price for a token (from cache)
     pub async fn get_price(&self, token_mint: &str) -> Option<f64> {
         self.prices.read().await.get(token_mint).copied()
@@ -103,25 +253,28 @@ impl PumpPortalClient {
 
     /// WebSocket handler - maintains connection and processes messages
     async fn ws_handler(
-        mut subscribe_rx: mpsc::UnboundedReceiver<String>,
+        ws_url: String,
+        mut subscribe_rx: mpsc::UnboundedReceiver<Subscription>,
         price_tx: mpsc::UnboundedSender<PriceUpdate>,
+        wallet_sell_tx: mpsc::UnboundedSender<WalletSell>,
         prices: Arc<RwLock<HashMap<String, f64>>>,
         sol_price: Arc<RwLock<f64>>,
     ) {
         let mut subscribed_tokens: Vec<String> = Vec::new();
+        let mut subscribed_accounts: Vec<String> = Vec::new();
         let mut reconnect_delay = 1;
 
         loop {
             info!("🔌 Connecting to PumpPortal WebSocket...");
 
-            match connect_async(PUMPPORTAL_WS_URL).await {
+            match connect_async(ws_url.as_str()).await {
                 Ok((ws_stream, _)) => {
                     info!("✅ Connected to PumpPortal WebSocket");
                     reconnect_delay = 1; // Reset delay on successful connection
 
                     let (mut write, mut read) = ws_stream.split();
 
-                    // Re-subscribe to previously subscribed tokens
+                    // Re-subscribe to previously subscribed tokens and wallets
                     if !subscribed_tokens.is_empty() {
                         let msg = SubscribeMessage {
                             method: "subscribeTokenTrade".to_string(),
@@ -132,17 +285,31 @@ impl PumpPortalClient {
                             info!("📡 Re-subscribed to {} tokens", subscribed_tokens.len());
                         }
                     }
+                    if !subscribed_accounts.is_empty() {
+                        let msg = SubscribeMessage {
+                            method: "subscribeAccountTrade".to_string(),
+                            keys: subscribed_accounts.clone(),
+                        };
+                        if let Ok(json) = serde_json::to_string(&msg) {
+                            let _ = write.send(Message::Text(json)).await;
+                            info!("👀 Re-subscribed to {} wallets", subscribed_accounts.len());
+                        }
+                    }
 
                     loop {
                         tokio::select! {
                             // Handle new subscribe requests
-                            Some(token_mint) = subscribe_rx.recv() => {
-                                if !subscribed_tokens.contains(&token_mint) {
-                                    subscribed_tokens.push(token_mint.clone());
+                            Some(subscription) = subscribe_rx.recv() => {
+                                let (subscribed, method, key) = match subscription {
+                                    Subscription::Token(token_mint) => (&mut subscribed_tokens, "subscribeTokenTrade", token_mint),
+                                    Subscription::Account(wallet) => (&mut subscribed_accounts, "subscribeAccountTrade", wallet),
+                                };
+                                if !subscribed.contains(&key) {
+                                    subscribed.push(key.clone());
 
                                     let msg = SubscribeMessage {
-                                        method: "subscribeTokenTrade".to_string(),
-                                        keys: vec![token_mint],
+                                        method: method.to_string(),
+                                        keys: vec![key],
                                     };
 
                                     if let Ok(json) = serde_json::to_string(&msg) {
@@ -159,16 +326,13 @@ impl PumpPortalClient {
                                 match msg_result {
                                     Ok(Message::Text(text)) => {
                                         if let Ok(trade) = serde_json::from_str::<TradeEvent>(&text) {
-                                            // Calculate price from trade data
-                                            if let Some(price_update) = Self::calculate_price(&trade, &sol_price).await {
-                                                // Update cache
-                                                prices.write().await.insert(
-                                                    price_update.token_mint.clone(),
-                                                    price_update.price_usd
-                                                );
-
-                                                // Send update
-                                                let _ = price_tx.send(price_update);
+                                            capture::record(CaptureEvent::TradeEvent { event: trade.clone() });
+                                            if let Some(sell) = trade.wallet_sell(&subscribed_accounts) {
+                                                let _ = wallet_sell_tx.send(sell);
+                                            }
+                                            // Account trades also cover tokens we don't hold - only subscribed ones are priced
+                                            if subscribed_tokens.contains(&trade.mint) {
+                                                Self::handle_trade(&trade, &prices, &sol_price, &price_tx).await;
                                             }
                                         }
                                     }
@@ -201,6 +365,26 @@ impl PumpPortalClient {
         }
     }
 
+    /// Turn a trade event into a price update (cache + channel)
+    async fn handle_trade(
+        trade: &TradeEvent,
+        prices: &Arc<RwLock<HashMap<String, f64>>>,
+        sol_price: &Arc<RwLock<f64>>,
+        price_tx: &mpsc::UnboundedSender<PriceUpdate>,
+    ) {
+        // Calculate price from trade data
+        if let Some(price_update) = Self::calculate_price(trade, sol_price).await {
+            // Update cache
+            prices.write().await.insert(
+                price_update.token_mint.clone(),
+                price_update.price_usd
+            );
+
+            // Send update
+            let _ = price_tx.send(price_update);
+        }
+    }
+
     /// Calculate USD price from trade event
     async fn calculate_price(trade: &TradeEvent, sol_price: &Arc<RwLock<f64>>) -> Option<PriceUpdate> {
         let sol_usd = *sol_price.read().await;
@@ -1,8 +1,18 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
 use redis::AsyncCommands;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use tokio::sync::mpsc;
-use tracing::{info, warn, error};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, info, warn, error};
+
+use crate::advisory::ExitAdvisory;
+use crate::capture::{self, CaptureEvent};
+use crate::control::{CommandReply, ControlRequest};
+use crate::error::ErrorCode;
 
 /// Signal received from Node.js backend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +35,19 @@ pub struct SpectreSignal {
     // - Weak momentum: 500,000 lamports (0.0005 SOL)
     // Falls back to config default if not provided
     pub priority_fee_lamports: Option<u64>,
+    // Explicit buy size in SOL - overrides the sizing policy (still capped)
+    #[serde(default)]
+    pub amount_sol: Option<f64>,
+}
+
+impl SpectreSignal {
+    /// Milliseconds since the backend generated the signal (None if the timestamp doesn't parse)
+    pub fn age_ms(&self) -> Option<u64> {
+        let generated = chrono::DateTime::parse_from_rfc3339(&self.timestamp).ok()?;
+        let age = chrono::Utc::now().signed_duration_since(generated);
+        // Clock skew can put the timestamp slightly in the future
+        Some(age.num_milliseconds().max(0) as u64)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +69,12 @@ pub struct SpectrePreSignal {
     pub entry_price_usd: Option<f64>,
     pub timestamp: String,
     pub first_wallet: PreSignalWallet,
+    // "STRONG", "MEDIUM", "WEAK" if already known - sizes the prepared TX as the signal will be
+    #[serde(default)]
+    pub strength: Option<String>,
+    // Explicit buy size in SOL, as on the signal that will follow
+    #[serde(default)]
+    pub amount_sol: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,173 +85,612 @@ pub struct PreSignalWallet {
     pub score: Option<f64>,
 }
 
+/// Processing list of a queue: items popped but not acked yet (requeued on the next start)
+pub fn processing_key(queue_name: &str) -> String {
+    format!("{}:processing", queue_name)
+}
+
+/// Results/replies kept while Redis is unavailable (oldest dropped beyond this)
+const OUTBOX_CAPACITY: usize = 10_000;
+
+/// Redis downtime a listener tolerates before giving up (unless configured otherwise)
+pub const DEFAULT_MAX_DOWNTIME: Duration = Duration::from_secs(120);
+
+/// Tries per ack (a reconnect can cost one)
+const ACK_ATTEMPTS: u32 = 3;
+
+/// How often buffered results are retried
+pub const OUTBOX_RETRY_SECS: u64 = 5;
+
+/// Item taken from a queue - it stays in the queue's processing list until acked,
+/// so a crash before `ack` redelivers it on the next start (at-least-once)
+pub struct Delivery<T> {
+    pub item: T,
+    receipt: Option<Receipt>,
+}
+
+struct Receipt {
+    processing_key: String,
+    payload: String,
+    connection: redis::aio::ConnectionManager,
+}
+
+impl<T> Delivery<T> {
+    /// Item that didn't come from a queue (capture replay, tests) - acking it does nothing
+    pub fn local(item: T) -> Self {
+        Self { item, receipt: None }
+    }
+
+    /// Done with the item: drop it from the processing list
+    pub async fn ack(self) {
+        let Some(mut receipt) = self.receipt else {
+            return;
+        };
+        // The connection manager only notices a dead connection when a command fails on it,
+        // and reconnects for the next one
+        for attempt in 1..=ACK_ATTEMPTS {
+            let removed: redis::RedisResult<i64> = receipt.connection.lrem(&receipt.processing_key, 1, &receipt.payload).await;
+            match removed {
+                Ok(_) => return,
+                Err(e) if attempt < ACK_ATTEMPTS && (e.is_connection_dropped() || e.is_io_error()) => {}
+                Err(e) => {
+                    warn!("⚠️ Failed to ack item on {} (redelivered on restart): {}", receipt.processing_key, e);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl<T> From<T> for Delivery<T> {
+    fn from(item: T) -> Self {
+        Self::local(item)
+    }
+}
+
+impl<T> std::ops::Deref for Delivery<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.item
+    }
+}
+
 pub struct RedisListener {
-    redis_url: String,
+    /// None when running offline (capture replay) - results are not published
+    client: Option<redis::Client>,
     queue_name: String,
-    connection: redis::aio::MultiplexedConnection,
+    /// Where results/replies go out (its own lock and connection)
+    outbox: Arc<Outbox>,
+    /// Connection state of the queue listeners
+    health: Arc<RedisHealth>,
 }
 
 impl RedisListener {
-    pub async fn new(redis_url: &str, queue_name: &str) -> Result<Self> {
+    /// Nothing connects yet: the queue listeners make the first connection (with backoff,
+    /// see `consume`) and publishing connects on first use, so Redis may still be down at startup
+    pub fn new(redis_url: &str, queue_name: &str) -> Result<Self> {
         let client = redis::Client::open(redis_url)?;
-        let connection = client.get_multiplexed_async_connection().await?;
-
-        info!("✅ Connected to Redis at {}", redis_url);
 
         Ok(Self {
-            redis_url: redis_url.to_string(),
+            outbox: Arc::new(Outbox::new(Some(client.clone()))),
+            client: Some(client),
             queue_name: queue_name.to_string(),
-            connection,
+            health: Arc::new(RedisHealth::new(Some(DEFAULT_MAX_DOWNTIME))),
         })
     }
 
-    /// Listen for signals using Redis LIST (BRPOP) and return a receiver channel
-    /// This is more reliable than pubsub and ensures no signal is lost
-    pub async fn subscribe(&self) -> Result<mpsc::UnboundedReceiver<SpectreSignal>> {
-        let (tx, rx) = mpsc::unbounded_channel();
+    /// Give up (fatal error, see `RedisHealth::fatal`) once Redis has been unavailable this long
+    /// (None = keep retrying forever). Applies to listeners subscribed afterwards
+    pub fn with_max_downtime(mut self, max_downtime: Option<Duration>) -> Self {
+        self.health = Arc::new(RedisHealth::new(max_downtime));
+        self
+    }
+
+    /// Connection state of the queue listeners
+    pub fn health(&self) -> Arc<RedisHealth> {
+        self.health.clone()
+    }
+
+    /// Publisher for trade results and command replies
+    pub fn outbox(&self) -> Arc<Outbox> {
+        self.outbox.clone()
+    }
+
+    /// Listener without a Redis connection (capture replay feeds the signals itself)
+    pub fn offline() -> Self {
+        Self {
+            client: None,
+            queue_name: String::new(),
+            outbox: Arc::new(Outbox::new(None)),
+            health: Arc::new(RedisHealth::new(None)),
+        }
+    }
+
+    /// Listen for signals on the signal queue and return a receiver channel
+    /// Each signal must be acked once handled (see `Delivery`)
+    pub async fn subscribe(&self) -> Result<mpsc::UnboundedReceiver<Delivery<SpectreSignal>>> {
+        info!("📡 Listening on Redis queue: {}", self.queue_name);
 
-        let redis_url = self.redis_url.clone();
-        let queue_name = self.queue_name.clone();
+        Ok(self.consume(&self.queue_name, "signal", |signal: &SpectreSignal| {
+            info!(
+                "👻 Received signal [{}]: {} ({}) MCap: ${:.0}",
+                signal.signal_type.to_uppercase(),
+                signal.token_symbol,
+                &signal.token_mint[..16.min(signal.token_mint.len())],
+                signal.market_cap_usd.unwrap_or(0.0)
+            );
+            capture::record(CaptureEvent::Signal { signal: signal.clone() });
+        }, |_, _| None))
+    }
+
+    /// Listen for operator commands (sell, close-all, pause, ...) on the control queue
+    /// Payloads that aren't a valid command are rejected right away on their reply key
+    pub async fn subscribe_commands(&self, queue_name: &str, default_reply_key: &str) -> Result<mpsc::UnboundedReceiver<Delivery<ControlRequest>>> {
+        info!("🎛️ Listening for commands on: {}", queue_name);
+
+        let default_reply_key = default_reply_key.to_string();
+        Ok(self.consume(queue_name, "command", |_: &ControlRequest| {}, move |payload, e| {
+            // Answer if we can tell who's asking
+            let raw: serde_json::Value = serde_json::from_str(payload).unwrap_or_default();
+            let id = raw["id"].as_str()?;
+            let reply_key = raw["replyTo"].as_str().unwrap_or(&default_reply_key);
+            let reply = CommandReply::rejected(id, &format!("Invalid command: {}", e));
+            Some((reply_key.to_string(), serde_json::to_string(&reply).ok()?))
+        }))
+    }
+
+    /// Listen for exit advisories from the backend (wallet exits, consensus reversal)
+    pub async fn subscribe_exit_advisories(&self, queue_name: &str) -> Result<mpsc::UnboundedReceiver<Delivery<ExitAdvisory>>> {
+        info!("📨 Listening for exit advisories on: {}", queue_name);
+
+        Ok(self.consume(queue_name, "exit advisory", |advisory: &ExitAdvisory| {
+            info!(
+                "📨 Exit advisory {:?} for {} ({})",
+                advisory.kind,
+                &advisory.token_mint[..16.min(advisory.token_mint.len())],
+                advisory.recommendation.as_deref().unwrap_or("no recommendation")
+            );
+        }, |_, _| None))
+    }
+
+    /// Listen for pre-signals (after 1st wallet buy) to prepare TX in advance
+    pub async fn subscribe_pre_signals(&self) -> Result<mpsc::UnboundedReceiver<Delivery<SpectrePreSignal>>> {
+        let queue_name = "spectre_pre_signals";
+
+        info!("⚡ Listening for pre-signals on: {}", queue_name);
+
+        Ok(self.consume(queue_name, "pre-signal", |pre_signal: &SpectrePreSignal| {
+            info!(
+                "⚡ Pre-signal received: {} ({}) - preparing TX",
+                pre_signal.token_symbol,
+                &pre_signal.token_mint[..16.min(pre_signal.token_mint.len())]
+            );
+            capture::record(CaptureEvent::PreSignal { pre_signal: pre_signal.clone() });
+        }, |_, _| None))
+    }
+
+    /// Consume a queue reliably: BLMOVE each item into the processing list, hand it over
+    /// as a `Delivery` and LREM it on ack. Items a previous run popped but never acked
+    /// are put back on the queue first. Unparseable items are dropped (after `on_invalid`,
+    /// which may return a (key, payload) to push in response)
+    ///
+    /// The listener is supervised: a lost (or never made) connection is rebuilt with backoff,
+    /// its state goes to `health`, and it gives up with a fatal error once Redis has been
+    /// unavailable for longer than the health's max downtime
+    fn consume<T, R, I>(&self, queue_name: &str, kind: &'static str, on_received: R, on_invalid: I) -> mpsc::UnboundedReceiver<Delivery<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+        R: Fn(&T) + Send + 'static,
+        I: Fn(&str, &serde_json::Error) -> Option<(String, String)> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
 
-        info!("📡 Listening on Redis queue: {}", queue_name);
+        let client = self.client.clone();
+        let queue_name = queue_name.to_string();
+        let processing_key = processing_key(&queue_name);
+        let health = self.health.clone();
 
-        // Spawn a task that polls Redis LIST using BRPOP
         tokio::spawn(async move {
-            let client = match redis::Client::open(redis_url.as_str()) {
-                Ok(c) => c,
-                Err(e) => {
-                    error!("Failed to open Redis client: {}", e);
-                    return;
-                }
+            let Some(client) = client else {
+                health.fail(&queue_name, format!("no Redis to listen for {}s on (offline)", kind));
+                return;
             };
 
-            let mut conn = match client.get_multiplexed_async_connection().await {
-                Ok(c) => c,
-                Err(e) => {
-                    error!("Failed to get Redis connection: {}", e);
-                    return;
-                }
-            };
+            health.set(&queue_name, ConnectionState::Connecting);
+            let mut outage = Outage::default();
+            // Acks go over a connection manager (BLMOVE blocks its own connection), which reconnects by itself
+            let mut ack_conn: Option<redis::aio::ConnectionManager> = None;
+            let mut requeued = false;
+            // Connecting until the first BLMOVE is answered
+            let mut answered = false;
 
             loop {
-                // BRPOP with 0 timeout - blocks INDEFINITELY until message available
-                // This is the most efficient way - no polling, instant response
-                let result: redis::RedisResult<Option<(String, String)>> =
-                    redis::cmd("BRPOP")
-                        .arg(&queue_name)
-                        .arg(0) // 0 = block forever until message arrives (instant response)
-                        .query_async(&mut conn)
-                        .await;
-
-                match result {
-                    Ok(Some((_key, payload))) => {
-                        match serde_json::from_str::<SpectreSignal>(&payload) {
-                            Ok(signal) => {
-                                info!(
-                                    "👻 Received signal [{}]: {} ({}) MCap: ${:.0}",
-                                    signal.signal_type.to_uppercase(),
-                                    signal.token_symbol,
-                                    &signal.token_mint[..16.min(signal.token_mint.len())],
-                                    signal.market_cap_usd.unwrap_or(0.0)
-                                );
-
-                                if tx.send(signal).is_err() {
-                                    error!("Signal receiver dropped, stopping listener");
-                                    break;
+                let connected = tokio::time::timeout(CONNECT_TIMEOUT, async {
+                    let conn = client.get_multiplexed_async_connection().await?;
+                    let ack_conn = match ack_conn.take() {
+                        Some(ack_conn) => ack_conn,
+                        None => client.get_connection_manager().await?,
+                    };
+                    Ok::<_, redis::RedisError>((conn, ack_conn))
+                })
+                .await
+                .unwrap_or_else(|_| Err(redis::RedisError::from((redis::ErrorKind::IoError, "connect timed out"))));
+
+                let mut conn = match connected {
+                    Ok((conn, connected_ack_conn)) => {
+                        ack_conn = Some(connected_ack_conn);
+                        conn
+                    }
+                    Err(e) => {
+                        if !outage.wait(&health, &queue_name, kind, e.to_string()).await {
+                            return;
+                        }
+                        continue;
+                    }
+                };
+                let ack_conn = ack_conn.clone().expect("set on connect");
+                // The outage only ends with a BLMOVE answered on this connection (below) - a connection
+                // that drops right away, or Redis refusing the command, keeps counting toward the max downtime
+
+                // Once per run - after a reconnect the processing list holds items still being handled
+                if !requeued {
+                    requeued = true;
+                    Self::requeue_unacked(&mut conn, &queue_name, &processing_key, kind).await;
+                }
+
+                loop {
+                    // BLMOVE blocks up to BLOCK_SECS waiting for an item; the item moves to the
+                    // processing list in the same step, so nothing is lost in between
+                    let result: redis::RedisResult<Option<String>> = tokio::time::timeout(
+                        BLOCK_TIMEOUT,
+                        redis::cmd("BLMOVE")
+                            .arg(&queue_name)
+                            .arg(&processing_key)
+                            .arg("RIGHT")
+                            .arg("LEFT")
+                            .arg(BLOCK_SECS)
+                            .query_async(&mut conn),
+                    )
+                    .await
+                    .unwrap_or_else(|_| Err(redis::RedisError::from((redis::ErrorKind::IoError, "BLMOVE got no answer"))));
+
+                    if result.is_ok() && (outage.attempts > 0 || !answered) {
+                        if outage.attempts > 0 {
+                            info!("✅ Redis listener for {}s recovered after {} failed attempt(s)", kind, outage.attempts);
+                            outage = Outage::default();
+                        }
+                        answered = true;
+                        health.set(&queue_name, ConnectionState::Connected);
+                    }
+
+                    match result {
+                        Ok(Some(payload)) => {
+                            let receipt = Receipt {
+                                processing_key: processing_key.clone(),
+                                payload: payload.clone(),
+                                connection: ack_conn.clone(),
+                            };
+
+                            match serde_json::from_str::<T>(&payload) {
+                                Ok(item) => {
+                                    on_received(&item);
+                                    if tx.send(Delivery { item, receipt: Some(receipt) }).is_err() {
+                                        error!("{} receiver dropped, stopping listener", kind);
+                                        health.remove(&queue_name);
+                                        return;
+                                    }
+                                }
+                                Err(e) => {
+                                    let preview = &payload[..100.min(payload.len())];
+                                    warn!("Failed to parse {}: {} - payload: {}", kind, e, preview);
+
+                                    if let Some((key, response)) = on_invalid(&payload, &e) {
+                                        let pushed: redis::RedisResult<()> = conn.lpush(&key, response).await;
+                                        if let Err(e) = pushed {
+                                            warn!("Failed to answer invalid {} on {}: {}", kind, key, e);
+                                        }
+                                    }
+                                    // Redelivering it wouldn't parse any better
+                                    Delivery { item: (), receipt: Some(receipt) }.ack().await;
                                 }
                             }
-                            Err(e) => {
-                                let preview = &payload[..100.min(payload.len())];
-                                warn!("Failed to parse signal: {} - payload: {}", e, preview);
+                        }
+                        Ok(None) => {
+                            // Timeout - no message, continue polling
+                        }
+                        Err(e) if e.is_io_error() || e.is_connection_dropped() || e.is_connection_refusal() || e.is_timeout() => {
+                            warn!("🔌 Redis connection lost ({}s): {}", kind, e);
+                            if !outage.wait(&health, &queue_name, kind, e.to_string()).await {
+                                return;
+                            }
+                            break;
+                        }
+                        Err(e) => {
+                            // Redis refuses the command (WRONGTYPE, no BLMOVE before 6.2): no better than no Redis
+                            let error = if e.to_string().to_lowercase().contains("unknown command") {
+                                error!("❌ Redis has no BLMOVE ({}s) - Redis 6.2+ is required", kind);
+                                format!("BLMOVE refused (Redis 6.2+ required): {}", e)
+                            } else {
+                                format!("BLMOVE refused: {}", e)
+                            };
+                            if !outage.wait(&health, &queue_name, kind, error).await {
+                                return;
                             }
                         }
-                    }
-                    Ok(None) => {
-                        // Timeout - no message, continue polling
-                    }
-                    Err(e) => {
-                        warn!("Redis BRPOP error: {}", e);
-                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
                     }
                 }
             }
         });
 
-        Ok(rx)
+        rx
     }
 
-    /// Publish a trade result back to Node.js
-    pub async fn publish_trade_result(&mut self, result: &TradeResult) -> Result<()> {
-        let payload = serde_json::to_string(result)?;
-        let _: () = self.connection.lpush("spectre_trade_results", payload).await?;
+    /// Put items a previous run popped but never acked back on the queue
+    /// Newest first onto the consuming end, so the oldest comes out first again
+    async fn requeue_unacked(conn: &mut redis::aio::MultiplexedConnection, queue_name: &str, processing_key: &str, kind: &str) {
+        let mut requeued = 0;
+        loop {
+            let moved: redis::RedisResult<Option<String>> = redis::cmd("LMOVE")
+                .arg(processing_key)
+                .arg(queue_name)
+                .arg("LEFT")
+                .arg("RIGHT")
+                .query_async(conn)
+                .await;
+            match moved {
+                Ok(Some(_)) => requeued += 1,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("Failed to requeue unacked {}s from {}: {}", kind, processing_key, e);
+                    break;
+                }
+            }
+        }
+        if requeued > 0 {
+            info!("♻️ Requeued {} unacked {}(s) from {}", requeued, kind, processing_key);
+        }
+    }
+}
+
+/// Connection state of one queue listener
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    /// Redis unavailable since `since`, `attempts` reconnects failed so far
+    Reconnecting {
+        since: chrono::DateTime<chrono::Utc>,
+        attempts: u32,
+        error: String,
+    },
+}
+
+/// Connection state of every queue listener, and the fatal error once one of them gave up
+pub struct RedisHealth {
+    states: std::sync::Mutex<HashMap<String, ConnectionState>>,
+    /// How long Redis may stay unavailable before it's fatal (None = retry forever)
+    max_downtime: Option<Duration>,
+    fatal: watch::Sender<Option<String>>,
+}
+
+impl RedisHealth {
+    pub fn new(max_downtime: Option<Duration>) -> Self {
+        Self {
+            states: std::sync::Mutex::new(HashMap::new()),
+            max_downtime,
+            fatal: watch::channel(None).0,
+        }
+    }
+
+    /// State per queue
+    pub fn states(&self) -> HashMap<String, ConnectionState> {
+        self.states.lock().unwrap().clone()
+    }
+
+    /// Every listener is connected
+    pub fn is_healthy(&self) -> bool {
+        self.states.lock().unwrap().values().all(|state| *state == ConnectionState::Connected)
+    }
+
+    /// Wait until a listener gives up on Redis, returns why
+    pub async fn fatal(&self) -> String {
+        let mut fatal = self.fatal.subscribe();
+        let reason = fatal.wait_for(Option::is_some).await.expect("sender lives in self");
+        reason.clone().unwrap_or_default()
+    }
+
+    fn set(&self, queue_name: &str, state: ConnectionState) {
+        self.states.lock().unwrap().insert(queue_name.to_string(), state);
+    }
+
+    fn remove(&self, queue_name: &str) {
+        self.states.lock().unwrap().remove(queue_name);
+    }
+
+    fn fail(&self, queue_name: &str, reason: String) {
+        error!("💀 Redis listener on {} gave up: {}", queue_name, reason);
+        self.remove(queue_name);
+        self.fatal.send_replace(Some(reason));
+    }
+}
+
+/// Time to (re)connect a listener
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long one BLMOVE blocks server-side, and how long we wait for its answer
+/// (a silent connection is treated as lost)
+const BLOCK_SECS: u64 = 5;
+const BLOCK_TIMEOUT: Duration = Duration::from_secs(BLOCK_SECS + 5);
+
+/// Reconnect backoff: doubles from the first delay up to the max
+const RECONNECT_FIRST_DELAY: Duration = Duration::from_millis(500);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// A listener's current stretch without Redis
+#[derive(Default)]
+struct Outage {
+    since: Option<(std::time::Instant, chrono::DateTime<chrono::Utc>)>,
+    attempts: u32,
+}
+
+impl Outage {
+    /// Record a failure (connect, lost connection, refused command) and wait before the next try
+    /// Returns false (after reporting it as fatal) once Redis has been gone longer than allowed
+    async fn wait(&mut self, health: &RedisHealth, queue_name: &str, kind: &str, error: String) -> bool {
+        let (started, since) = *self.since.get_or_insert_with(|| (std::time::Instant::now(), chrono::Utc::now()));
+        self.attempts += 1;
+
+        if let Some(max_downtime) = health.max_downtime {
+            if started.elapsed() >= max_downtime {
+                health.fail(queue_name, format!("Redis unavailable for {}s ({}s): {}", started.elapsed().as_secs(), kind, error));
+                return false;
+            }
+        }
+
+        let delay = RECONNECT_FIRST_DELAY
+            .saturating_mul(2u32.saturating_pow(self.attempts - 1))
+            .min(RECONNECT_MAX_DELAY);
+        warn!(
+            "🔌 Redis listener for {}s unavailable ({}), retry #{} in {:.1}s: {}",
+            kind, queue_name, self.attempts, delay.as_secs_f64(), error
+        );
+        health.set(queue_name, ConnectionState::Reconnecting { since, attempts: self.attempts, error });
+        tokio::time::sleep(delay).await;
+        true
+    }
+}
+
+/// Trade results and command replies on their way to Redis
+/// Publishing only queues the message (it never waits on Redis); `run` pushes them out in order
+/// on a connection of its own, and keeps them while Redis is unavailable
+pub struct Outbox {
+    /// None when running offline (capture replay) - nothing is published
+    client: Option<redis::Client>,
+    /// (key, payload) pushes not out yet, oldest first
+    pending: std::sync::Mutex<VecDeque<(String, String)>>,
+    /// Publishing connection, made on the first flush; held for a whole flush so flushes don't interleave
+    connection: tokio::sync::Mutex<Option<redis::aio::ConnectionManager>>,
+    queued: tokio::sync::Notify,
+}
+
+impl Outbox {
+    fn new(client: Option<redis::Client>) -> Self {
+        Self {
+            client,
+            pending: std::sync::Mutex::new(VecDeque::new()),
+            connection: tokio::sync::Mutex::new(None),
+            queued: tokio::sync::Notify::new(),
+        }
+    }
+
+    /// Queue the reply to an operator command for its reply key
+    pub fn publish_command_reply(&self, reply_key: &str, reply: &CommandReply) -> Result<()> {
+        let payload = serde_json::to_string(reply)?;
+        self.push(reply_key, payload);
         Ok(())
     }
 
-    /// Listen for pre-signals (after 1st wallet buy) to prepare TX in advance
-    pub async fn subscribe_pre_signals(&self) -> Result<mpsc::UnboundedReceiver<SpectrePreSignal>> {
-        let (tx, rx) = mpsc::unbounded_channel();
+    /// LPUSH (by `run`) behind whatever is still queued
+    fn push(&self, key: &str, payload: String) {
+        if self.client.is_none() {
+            return;
+        }
+        self.pending.lock().unwrap().push_back((key.to_string(), payload));
+        self.queued.notify_one();
+    }
 
-        let redis_url = self.redis_url.clone();
-        let queue_name = "spectre_pre_signals".to_string();
+    /// Messages not published yet
+    pub fn pending(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
 
-        info!("⚡ Listening for pre-signals on: {}", queue_name);
+    /// Push queued messages in order, returns how many went out
+    /// Stops at the first failure (the rest stays queued)
+    pub async fn flush(&self) -> Result<usize> {
+        let Some(client) = self.client.as_ref() else {
+            return Ok(0);
+        };
+        let mut connection = self.connection.lock().await;
+        if self.pending() == 0 {
+            return Ok(0);
+        }
 
-        tokio::spawn(async move {
-            let client = match redis::Client::open(redis_url.as_str()) {
-                Ok(c) => c,
-                Err(e) => {
-                    error!("Failed to open Redis client for pre-signals: {}", e);
-                    return;
-                }
-            };
+        if connection.is_none() {
+            let connected = tokio::time::timeout(CONNECT_TIMEOUT, client.get_connection_manager())
+                .await
+                .unwrap_or_else(|_| Err(redis::RedisError::from((redis::ErrorKind::IoError, "connect timed out"))));
+            match connected {
+                Ok(connected) => *connection = Some(connected),
+                Err(e) => return Err(self.unavailable(e)),
+            }
+        }
+        let connection = connection.as_mut().expect("connected above");
 
-            let mut conn = match client.get_multiplexed_async_connection().await {
-                Ok(c) => c,
-                Err(e) => {
-                    error!("Failed to get Redis connection for pre-signals: {}", e);
-                    return;
-                }
+        let mut sent = 0;
+        loop {
+            // Only this flush (holding the connection) takes messages off the front
+            let Some((key, payload)) = self.pending.lock().unwrap().front().cloned() else {
+                break;
             };
+            let pushed: redis::RedisResult<()> = connection.lpush(&key, &payload).await;
+            if let Err(e) = pushed {
+                return Err(self.unavailable(e));
+            }
+            self.pending.lock().unwrap().pop_front();
+            sent += 1;
+        }
+        Ok(sent)
+    }
+
+    /// Publishing failed: trim the queue to its capacity, describe what's buffered
+    fn unavailable(&self, e: redis::RedisError) -> anyhow::Error {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.len() > OUTBOX_CAPACITY {
+            let dropped = pending.len() - OUTBOX_CAPACITY;
+            pending.drain(..dropped);
+            error!("❌ Outbox full - dropped the {} oldest unpublished message(s)", dropped);
+        }
+        anyhow!("Redis unavailable, {} message(s) buffered: {}", pending.len(), e)
+    }
 
+    /// Publish queued messages as they come, retrying every OUTBOX_RETRY_SECS while Redis
+    /// is unavailable (runs for the life of the process)
+    pub async fn run(self: Arc<Self>) {
+        loop {
+            self.queued.notified().await;
+
+            let mut failures = 0;
             loop {
-                let result: redis::RedisResult<Option<(String, String)>> =
-                    redis::cmd("BRPOP")
-                        .arg(&queue_name)
-                        .arg(0)
-                        .query_async(&mut conn)
-                        .await;
-
-                match result {
-                    Ok(Some((_key, payload))) => {
-                        match serde_json::from_str::<SpectrePreSignal>(&payload) {
-                            Ok(pre_signal) => {
-                                info!(
-                                    "⚡ Pre-signal received: {} ({}) - preparing TX",
-                                    pre_signal.token_symbol,
-                                    &pre_signal.token_mint[..16.min(pre_signal.token_mint.len())]
-                                );
-
-                                if tx.send(pre_signal).is_err() {
-                                    error!("Pre-signal receiver dropped, stopping listener");
-                                    break;
-                                }
-                            }
-                            Err(e) => {
-                                let preview = &payload[..100.min(payload.len())];
-                                warn!("Failed to parse pre-signal: {} - payload: {}", e, preview);
-                            }
+                match self.flush().await {
+                    Ok(sent) => {
+                        if failures > 0 {
+                            info!("📦 Published {} buffered message(s)", sent);
                         }
+                        break;
                     }
-                    Ok(None) => {}
-                    Err(e) => {
-                        warn!("Redis BRPOP error (pre-signals): {}", e);
-                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                    }
+                    Err(e) if failures == 0 => warn!("📦 {} - retrying every {}s", e, OUTBOX_RETRY_SECS),
+                    Err(e) => debug!("📦 {}", e),
                 }
+                failures += 1;
+                tokio::time::sleep(Duration::from_secs(OUTBOX_RETRY_SECS)).await;
             }
-        });
+        }
+    }
+}
 
-        Ok(rx)
+/// Where trade results are reported (the Node.js backend via Redis)
+#[async_trait]
+pub trait ResultPublisher: Send + Sync {
+    async fn publish_trade_result(&self, result: &TradeResult) -> Result<()>;
+}
+
+#[async_trait]
+impl ResultPublisher for Outbox {
+    async fn publish_trade_result(&self, result: &TradeResult) -> Result<()> {
+        let payload = serde_json::to_string(result)?;
+        self.push("spectre_trade_results", payload);
+        Ok(())
     }
 }
 
@@ -236,8 +704,12 @@ pub struct TradeResult {
     pub amount_sol: f64,
     pub amount_tokens: Option<f64>,
     pub price_per_token: Option<f64>,
-    pub tx_signature: Option<String>,
+    pub tx_signature: Option<String>,     // Real on-chain signature
+    pub bundle_id: Option<String>,        // Jito bundle ID (if sent via Jito)
     pub error: Option<String>,
+    // Machine-readable failure category: "no_route", "slippage_exceeded", ... (None on success)
+    #[serde(default)]
+    pub error_code: Option<ErrorCode>,
     pub latency_ms: u64,
     pub timestamp: String,
 
@@ -259,4 +731,18 @@ pub struct TradeResult {
 
     // Signal timestamp (when signal was generated by backend)
     pub signal_timestamp: Option<String>,
+
+    // Signal age when the buy started, and at the moment the buy TX was submitted (ms)
+    #[serde(default)]
+    pub signal_age_ms: Option<u64>,
+    #[serde(default)]
+    pub signal_to_submit_ms: Option<u64>,
+
+    // Why a sell was triggered: "stop_loss", "take_profit", "trailing_stop", ... (None for buys)
+    #[serde(default)]
+    pub exit_reason: Option<String>,
+
+    // Simulated fill (paper mode) - nothing was signed or sent
+    #[serde(default)]
+    pub paper: bool,
 }
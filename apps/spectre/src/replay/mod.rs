@@ -0,0 +1,242 @@
+use anyhow::{anyhow, bail, Result};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::birdeye::BirdeyeClient;
+use crate::capture::{self, CaptureEvent, CaptureRecord};
+use crate::config::Config;
+use crate::pumpportal::TradeEvent;
+use crate::redis::{Delivery, SpectrePreSignal, SpectreSignal, TradeResult};
+
+const USAGE: &str = "usage: spectre replay <capture.jsonl> [--speed X] [--step]";
+
+/// Replay positions/journal live here and are wiped at the start of every replay
+const REPLAY_DATA_DIR: &str = "data/replay";
+
+/// Time given to the position monitor to drain the last price updates after the capture ends
+pub const SETTLE_SECS: u64 = 1;
+
+pub struct ReplayOptions {
+    pub capture_path: PathBuf,
+    /// Playback speed (1 = recorded timing, 10 = 10x faster, 0 = no delays)
+    pub speed: f64,
+    /// Wait for Enter before each event
+    pub step: bool,
+}
+
+impl ReplayOptions {
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let mut capture_path = None;
+        let mut speed = 1.0;
+        let mut step = false;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--speed" => {
+                    speed = args
+                        .next()
+                        .and_then(|v| v.parse::<f64>().ok())
+                        .filter(|s| *s >= 0.0)
+                        .ok_or_else(|| anyhow!("--speed expects a number >= 0\n{}", USAGE))?;
+                }
+                "--step" => step = true,
+                other if other.starts_with("--") => bail!("Unknown replay option: {}\n{}", other, USAGE),
+                path => capture_path = Some(PathBuf::from(path)),
+            }
+        }
+
+        Ok(Self {
+            capture_path: capture_path.ok_or_else(|| anyhow!("missing capture file\n{}", USAGE))?,
+            speed,
+            step,
+        })
+    }
+}
+
+/// Recorded session fed back through the live input channels
+///
+/// Signals and pre-signals go to the main loop / pre-signal handler, trade events through
+/// PumpPortal's price calculation, price responses answer `BirdeyeClient::get_price`.
+/// Time-based exits use the wall clock, so they only match the capture at `--speed 1`.
+pub struct Replay {
+    options: ReplayOptions,
+    inputs: Vec<CaptureRecord>,
+    expected: Vec<TradeResult>,
+}
+
+impl Replay {
+    pub fn load(options: ReplayOptions) -> Result<Self> {
+        let records = capture::load(&options.capture_path)?;
+        let expected = capture::trade_results(&records);
+        let inputs = records
+            .into_iter()
+            .filter(|r| !matches!(r.event, CaptureEvent::TradeResult { .. }))
+            .collect();
+
+        Ok(Self { options, inputs, expected })
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "{} ({} inputs, {} recorded results, speed {}{})",
+            self.options.capture_path.display(),
+            self.inputs.len(),
+            self.expected.len(),
+            self.options.speed,
+            if self.options.step { ", step" } else { "" }
+        )
+    }
+
+    /// Trade results recorded in the capture (compared against the replayed ones)
+    pub fn expected_results(&self) -> Vec<TradeResult> {
+        self.expected.clone()
+    }
+
+    /// Simulated execution on a fresh data dir (never touches live or paper positions)
+    pub fn prepare_config(config: &mut Config) -> Result<()> {
+        config.paper_mode = true;
+        // Results are compared in order - execute signals one at a time like the capture did
+        config.max_concurrent_signals = 1;
+        // Recorded signals are as old as the capture
+        config.max_signal_age_secs = 0;
+        config.max_signal_age_by_type.clear();
+        config.positions_file = format!("{}/positions.jsonl", REPLAY_DATA_DIR);
+        config.journal_db = format!("{}/journal.db", REPLAY_DATA_DIR);
+
+        for file in [
+            config.positions_file.clone(),
+            config.journal_db.clone(),
+            format!("{}-wal", config.journal_db),
+            format!("{}-shm", config.journal_db),
+        ] {
+            match std::fs::remove_file(&file) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(anyhow!("Failed to reset {}: {}", file, e)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply price responses recorded before any other input (startup SOL price lookup)
+    pub async fn prime(&mut self, birdeye: &BirdeyeClient) {
+        let leading = self
+            .inputs
+            .iter()
+            .take_while(|r| matches!(r.event, CaptureEvent::PriceResponse { .. }))
+            .count();
+
+        for record in self.inputs.drain(..leading) {
+            apply_price(birdeye, record.event).await;
+        }
+    }
+
+    /// Start feeding the capture; the returned channels close once it has been fully replayed
+    pub fn start(
+        self,
+        birdeye: Arc<BirdeyeClient>,
+        trade_tx: mpsc::UnboundedSender<TradeEvent>,
+    ) -> (mpsc::UnboundedReceiver<Delivery<SpectreSignal>>, mpsc::UnboundedReceiver<Delivery<SpectrePreSignal>>) {
+        let (signal_tx, signal_rx) = mpsc::unbounded_channel();
+        let (pre_signal_tx, pre_signal_rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let total = self.inputs.len();
+            let mut previous: Option<chrono::DateTime<chrono::Utc>> = None;
+
+            for (index, record) in self.inputs.into_iter().enumerate() {
+                if self.options.step {
+                    info!("⏯️ [{}/{}] {} - press Enter", index + 1, total, describe_event(&record.event));
+                    let _ = tokio::task::spawn_blocking(|| std::io::stdin().read_line(&mut String::new())).await;
+                } else if let Some(previous) = previous {
+                    let gap = (record.ts - previous).to_std().unwrap_or_default();
+                    if self.options.speed > 0.0 && !gap.is_zero() {
+                        tokio::time::sleep(gap.div_f64(self.options.speed)).await;
+                    }
+                }
+                previous = Some(record.ts);
+
+                match record.event {
+                    CaptureEvent::Signal { signal } => {
+                        let _ = signal_tx.send(Delivery::local(signal));
+                    }
+                    CaptureEvent::PreSignal { pre_signal } => {
+                        let _ = pre_signal_tx.send(Delivery::local(pre_signal));
+                    }
+                    CaptureEvent::TradeEvent { event } => {
+                        let _ = trade_tx.send(event);
+                    }
+                    event @ CaptureEvent::PriceResponse { .. } => apply_price(&birdeye, event).await,
+                    CaptureEvent::TradeResult { .. } => {}
+                }
+            }
+
+            info!("🎬 Capture replayed ({} inputs)", total);
+        });
+
+        (signal_rx, pre_signal_rx)
+    }
+}
+
+async fn apply_price(birdeye: &BirdeyeClient, event: CaptureEvent) {
+    if let CaptureEvent::PriceResponse { token_mint, price_usd, error } = event {
+        let response = price_usd.ok_or_else(|| error.unwrap_or_else(|| "no price".to_string()));
+        birdeye.set_replay_price(&token_mint, response).await;
+    }
+}
+
+fn describe_event(event: &CaptureEvent) -> String {
+    match event {
+        CaptureEvent::Signal { signal } => format!("signal {} [{}]", signal.token_symbol, signal.signal_type),
+        CaptureEvent::PreSignal { pre_signal } => format!("pre-signal {}", pre_signal.token_symbol),
+        CaptureEvent::TradeEvent { event } => format!("trade event {}", short_mint(&event.mint)),
+        CaptureEvent::PriceResponse { token_mint, price_usd, .. } => match price_usd {
+            Some(price) => format!("price {} ${:.10}", short_mint(token_mint), price),
+            None => format!("price {} (error)", short_mint(token_mint)),
+        },
+        CaptureEvent::TradeResult { result } => format!("result {} {}", result.action, result.token_symbol),
+    }
+}
+
+fn short_mint(mint: &str) -> &str {
+    &mint[..8.min(mint.len())]
+}
+
+/// What a successful trade result must match between capture and replay
+fn outcome_key(result: &TradeResult) -> String {
+    match result.exit_reason {
+        Some(ref reason) => format!("{} {} ({})", result.action, result.token_symbol, reason),
+        None => format!("{} {}", result.action, result.token_symbol),
+    }
+}
+
+/// Compare successful buys/sells of the replay with the capture, in order
+/// Failed live trades are skipped - simulated execution never fails on-chain
+pub fn compare(expected: &[TradeResult], actual: &[TradeResult]) -> Result<()> {
+    let expected: Vec<String> = expected.iter().filter(|r| r.success).map(outcome_key).collect();
+    let actual: Vec<String> = actual.iter().filter(|r| r.success).map(outcome_key).collect();
+
+    let mut mismatches = 0;
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => info!("   ✅ {}", a),
+            (e, a) => {
+                mismatches += 1;
+                warn!(
+                    "   ❌ expected {} | replayed {}",
+                    e.map(String::as_str).unwrap_or("nothing"),
+                    a.map(String::as_str).unwrap_or("nothing")
+                );
+            }
+        }
+    }
+
+    if mismatches > 0 {
+        bail!("Replay diverged from the capture: {} mismatch(es)", mismatches);
+    }
+    info!("🎬 Replay matches the capture ({} trade(s))", expected.len());
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use thiserror::Error;
+use tracing::warn;
+
+/// Portfolio limits checked before every buy (0 = no limit)
+#[derive(Debug, Clone, Default)]
+pub struct RiskLimits {
+    pub max_open_positions: usize,
+    pub max_deployed_sol: f64,
+    /// New entries halt once today's realized PnL (UTC) drops to -this
+    pub daily_loss_limit_sol: f64,
+    /// SOL left in the wallet after a buy (fees, sells, ATA rent)
+    pub min_sol_reserve: f64,
+}
+
+/// Portfolio state a buy is checked against
+#[derive(Debug, Clone, Default)]
+pub struct Exposure {
+    /// Cost basis of what is still held, by mint
+    pub open_positions: HashMap<String, f64>,
+    /// `RiskManager::in_flight` taken before `open_positions` was read - a buy that finishes
+    /// in between is in one or the other, never in neither
+    pub in_flight: HashMap<String, f64>,
+    pub realized_today_sol: f64,
+    /// None = not checked (paper mode never spends the wallet)
+    pub wallet_sol: Option<f64>,
+}
+
+/// Why a buy was refused
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum RiskRejection {
+    #[error("daily loss limit hit ({realized_sol:+.4} SOL realized today, limit -{limit_sol} SOL)")]
+    DailyLossLimit { realized_sol: f64, limit_sol: f64 },
+
+    #[error("max open positions reached ({open}/{limit})")]
+    MaxOpenPositions { open: usize, limit: usize },
+
+    #[error("max deployed SOL exceeded ({deployed_sol:.4} + {amount_sol} SOL > {limit_sol} SOL)")]
+    MaxDeployedSol { deployed_sol: f64, amount_sol: f64, limit_sol: f64 },
+
+    #[error("SOL reserve too low ({available_sol:.4} SOL - {amount_sol} SOL buy < {reserve_sol} SOL reserve)")]
+    SolReserve { available_sol: f64, amount_sol: f64, reserve_sol: f64 },
+}
+
+/// Consulted before every buy
+/// Admitted buys hold a reservation until they finish, so concurrent buys can't
+/// all squeeze through the same remaining headroom
+pub struct RiskManager {
+    limits: RiskLimits,
+    /// SOL committed by buys that are admitted but not finished, per mint
+    pending: Mutex<HashMap<String, f64>>,
+}
+
+impl RiskManager {
+    pub fn new(limits: RiskLimits) -> Self {
+        Self { limits, pending: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// SOL committed by admitted buys that haven't finished, by mint
+    pub fn in_flight(&self) -> HashMap<String, f64> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Admit a buy of `amount_sol`, or say which limit it would break
+    pub fn admit(&self, token_mint: &str, amount_sol: f64, exposure: &Exposure) -> Result<BuyReservation<'_>, RiskRejection> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let pending_sol: f64 = pending.values().sum();
+        let limits = &self.limits;
+
+        if limits.daily_loss_limit_sol > 0.0 && exposure.realized_today_sol <= -limits.daily_loss_limit_sol {
+            return Err(RiskRejection::DailyLossLimit {
+                realized_sol: exposure.realized_today_sol,
+                limit_sol: limits.daily_loss_limit_sol,
+            });
+        }
+
+        // Positions plus buys in flight, each mint once (a buy that just finished can be in both)
+        let mut committed = exposure.open_positions.clone();
+        for (mint, sol) in exposure.in_flight.iter().chain(pending.iter()) {
+            committed.entry(mint.clone()).or_insert(*sol);
+        }
+
+        let open = committed.len();
+        if limits.max_open_positions > 0 && open >= limits.max_open_positions {
+            return Err(RiskRejection::MaxOpenPositions { open, limit: limits.max_open_positions });
+        }
+
+        let deployed_sol: f64 = committed.values().sum();
+        if limits.max_deployed_sol > 0.0 && deployed_sol + amount_sol > limits.max_deployed_sol {
+            return Err(RiskRejection::MaxDeployedSol { deployed_sol, amount_sol, limit_sol: limits.max_deployed_sol });
+        }
+
+        if let Some(wallet_sol) = exposure.wallet_sol {
+            let available_sol = wallet_sol - pending_sol;
+            if available_sol - amount_sol < limits.min_sol_reserve {
+                return Err(RiskRejection::SolReserve { available_sol, amount_sol, reserve_sol: limits.min_sol_reserve });
+            }
+        }
+
+        if pending.insert(token_mint.to_string(), amount_sol).is_some() {
+            warn!("⚠️ Risk reservation for {} replaced - concurrent buys of one mint", token_mint);
+        }
+        Ok(BuyReservation { manager: self, token_mint: token_mint.to_string() })
+    }
+}
+
+/// Admitted buy; its SOL counts against the limits until dropped
+/// Drop it only once the bought position is in the position manager.
+pub struct BuyReservation<'a> {
+    manager: &'a RiskManager,
+    token_mint: String,
+}
+
+impl Drop for BuyReservation<'_> {
+    fn drop(&mut self) {
+        let mut pending = self.manager.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.remove(&self.token_mint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(max_open_positions: usize, max_deployed_sol: f64) -> RiskManager {
+        RiskManager::new(RiskLimits { max_open_positions, max_deployed_sol, ..RiskLimits::default() })
+    }
+
+    #[test]
+    fn in_flight_buys_count_against_the_limits() {
+        let risk = manager(2, 0.0);
+        let _a = risk.admit("A", 0.1, &Exposure::default()).unwrap();
+        let _b = risk.admit("B", 0.1, &Exposure::default()).unwrap();
+
+        let rejection = risk.admit("C", 0.1, &Exposure::default()).err();
+        assert_eq!(rejection, Some(RiskRejection::MaxOpenPositions { open: 2, limit: 2 }));
+    }
+
+    #[test]
+    fn buy_finishing_between_snapshots_is_still_counted() {
+        let risk = manager(1, 0.0);
+        let a = risk.admit("A", 0.1, &Exposure::default()).unwrap();
+
+        // B snapshots the reservations, A's position lands and A lets go before B reads positions
+        let in_flight = risk.in_flight();
+        drop(a);
+        let exposure = Exposure { in_flight, ..Exposure::default() };
+
+        assert!(matches!(risk.admit("B", 0.1, &exposure), Err(RiskRejection::MaxOpenPositions { .. })));
+    }
+
+    #[test]
+    fn a_mint_in_positions_and_in_flight_counts_once() {
+        let risk = manager(2, 0.25);
+        let a = risk.admit("A", 0.1, &Exposure::default()).unwrap();
+        let exposure = Exposure {
+            open_positions: HashMap::from([("A".to_string(), 0.1)]),
+            in_flight: risk.in_flight(),
+            ..Exposure::default()
+        };
+
+        let b = risk.admit("B", 0.1, &exposure).expect("A counted once");
+        drop((a, b));
+        let rejection = risk.admit("C", 0.2, &exposure).err();
+        assert!(matches!(rejection, Some(RiskRejection::MaxDeployedSol { .. })), "{:?}", rejection);
+    }
+}
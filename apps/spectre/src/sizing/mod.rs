@@ -0,0 +1,177 @@
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+
+use crate::redis::{SpectrePreSignal, SpectreSignal};
+
+/// What a buy's size is derived from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizingMode {
+    /// Always `base_sol`
+    Fixed,
+    /// STRONG / MEDIUM / WEAK tiers
+    Strength,
+    /// `base_sol` scaled by the mean trigger-wallet score
+    ScoreMean,
+    /// `base_sol` scaled by the best trigger-wallet score
+    ScoreMax,
+}
+
+impl FromStr for SizingMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "fixed" => Ok(SizingMode::Fixed),
+            "strength" => Ok(SizingMode::Strength),
+            "score_mean" => Ok(SizingMode::ScoreMean),
+            "score_max" => Ok(SizingMode::ScoreMax),
+            other => Err(anyhow!("Unknown sizing mode: {} (fixed, strength, score_mean, score_max)", other)),
+        }
+    }
+}
+
+/// Signal fields a buy is sized from
+#[derive(Debug, Clone, Default)]
+pub struct SizingInput<'a> {
+    pub strength: Option<&'a str>,
+    pub wallet_scores: Vec<f64>,
+    /// `amountSol` from the payload - wins over the policy (still capped)
+    pub amount_sol: Option<f64>,
+}
+
+impl<'a> From<&'a SpectreSignal> for SizingInput<'a> {
+    fn from(signal: &'a SpectreSignal) -> Self {
+        Self {
+            strength: Some(&signal.strength),
+            wallet_scores: signal.wallets.iter().filter_map(|w| w.score).collect(),
+            amount_sol: signal.amount_sol,
+        }
+    }
+}
+
+impl<'a> From<&'a SpectrePreSignal> for SizingInput<'a> {
+    fn from(pre_signal: &'a SpectrePreSignal) -> Self {
+        Self {
+            strength: pre_signal.strength.as_deref(),
+            wallet_scores: pre_signal.first_wallet.score.into_iter().collect(),
+            amount_sol: pre_signal.amount_sol,
+        }
+    }
+}
+
+/// Default max buy size, as a multiple of the base size
+pub const DEFAULT_MAX_MULTIPLE: f64 = 3.0;
+
+/// Chosen buy size and what it was based on (for logs)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BuySize {
+    pub amount_sol: f64,
+    pub basis: &'static str,
+}
+
+/// How much SOL each buy commits
+#[derive(Debug, Clone)]
+pub struct SizingPolicy {
+    pub mode: SizingMode,
+    /// Fixed size, and the fallback when a signal has nothing to size by
+    pub base_sol: f64,
+    pub strong_sol: f64,
+    pub medium_sol: f64,
+    pub weak_sol: f64,
+    /// Wallet score that gets exactly `base_sol` (score 2x this = 2x the size)
+    pub score_reference: f64,
+    /// Caps applied to every size, overrides included (max 0 = no cap, and then
+    /// `amountSol` overrides are ignored - nothing would bound them)
+    pub min_sol: f64,
+    pub max_sol: f64,
+}
+
+impl SizingPolicy {
+    /// Flat `amount_sol` for every buy (the pre-sizing behaviour), overrides capped at the default max
+    pub fn fixed(amount_sol: f64) -> Self {
+        Self {
+            mode: SizingMode::Fixed,
+            base_sol: amount_sol,
+            strong_sol: amount_sol,
+            medium_sol: amount_sol,
+            weak_sol: amount_sol,
+            score_reference: 50.0,
+            min_sol: 0.0,
+            max_sol: amount_sol * DEFAULT_MAX_MULTIPLE,
+        }
+    }
+
+    /// Caps that can't all hold (checked at config load)
+    pub fn validate(&self) -> Result<()> {
+        if self.min_sol < 0.0 || self.max_sol < 0.0 {
+            return Err(anyhow!("Sizing caps can't be negative (min {} / max {} SOL)", self.min_sol, self.max_sol));
+        }
+        if self.max_sol > 0.0 && self.min_sol > self.max_sol {
+            return Err(anyhow!("SIZING_MIN_SOL ({}) is above SIZING_MAX_SOL ({})", self.min_sol, self.max_sol));
+        }
+        Ok(())
+    }
+
+    pub fn size(&self, input: &SizingInput) -> BuySize {
+        let (amount_sol, basis) = match input.amount_sol.filter(|a| *a > 0.0) {
+            Some(amount_sol) if self.max_sol > 0.0 => (amount_sol, "signal override"),
+            Some(_) => {
+                let (amount_sol, _) = self.policy_size(input);
+                (amount_sol, "policy (signal override ignored - no max size)")
+            }
+            None => self.policy_size(input),
+        };
+        BuySize { amount_sol: self.cap(amount_sol), basis }
+    }
+
+    fn policy_size(&self, input: &SizingInput) -> (f64, &'static str) {
+        match self.mode {
+            SizingMode::Fixed => (self.base_sol, "fixed"),
+            SizingMode::Strength => match input.strength.map(str::to_uppercase).as_deref() {
+                Some("STRONG") => (self.strong_sol, "STRONG tier"),
+                Some("MEDIUM") => (self.medium_sol, "MEDIUM tier"),
+                Some("WEAK") => (self.weak_sol, "WEAK tier"),
+                _ => (self.base_sol, "fixed (no strength)"),
+            },
+            SizingMode::ScoreMean | SizingMode::ScoreMax => {
+                let scores = &input.wallet_scores;
+                if scores.is_empty() || self.score_reference <= 0.0 {
+                    return (self.base_sol, "fixed (no wallet scores)");
+                }
+                let (score, basis) = match self.mode {
+                    SizingMode::ScoreMax => (scores.iter().copied().fold(f64::MIN, f64::max), "max wallet score"),
+                    _ => (scores.iter().sum::<f64>() / scores.len() as f64, "mean wallet score"),
+                };
+                (self.base_sol * score.max(0.0) / self.score_reference, basis)
+            }
+        }
+    }
+
+    fn cap(&self, amount_sol: f64) -> f64 {
+        let amount_sol = amount_sol.max(self.min_sol);
+        if self.max_sol > 0.0 {
+            amount_sol.min(self.max_sol)
+        } else {
+            amount_sol
+        }
+    }
+
+    /// One-line summary for the startup log
+    pub fn describe(&self) -> String {
+        let caps = format!("min {} / max {} SOL", self.min_sol, self.max_sol);
+        match self.mode {
+            SizingMode::Fixed => format!("fixed {} SOL ({})", self.base_sol, caps),
+            SizingMode::Strength => format!(
+                "by strength STRONG {} / MEDIUM {} / WEAK {} SOL ({})",
+                self.strong_sol, self.medium_sol, self.weak_sol, caps
+            ),
+            SizingMode::ScoreMean | SizingMode::ScoreMax => format!(
+                "{} SOL per {} {} wallet score ({})",
+                self.base_sol,
+                self.score_reference,
+                if self.mode == SizingMode::ScoreMax { "max" } else { "mean" },
+                caps
+            ),
+        }
+    }
+}
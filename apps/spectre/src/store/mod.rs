@@ -0,0 +1,272 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::position::Position;
+
+/// Single line in the position journal
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum JournalEntry {
+    /// Position was opened or changed (full snapshot)
+    Upsert { position: Box<Position> },
+    /// Position was closed
+    Remove { token_mint: String },
+}
+
+/// Work for the store thread
+enum Job {
+    Append(JournalEntry),
+    /// Reply once everything queued before it has been written
+    Flush(mpsc::Sender<()>),
+}
+
+/// Append-only journal of open positions (JSON lines)
+/// Every add/advance/remove is queued immediately and written (fsynced) in order
+/// by a dedicated thread, so a crash or redeploy never leaves tokens in the wallet
+/// without SL/TP monitoring and callers never wait on the disk.
+/// The file is compacted to one snapshot per open position on every load.
+pub struct PositionStore {
+    path: PathBuf,
+    file: Arc<Mutex<File>>,
+    jobs: mpsc::Sender<Job>,
+}
+
+impl PositionStore {
+    /// Open (or create) the journal file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+        }
+
+        let file = Arc::new(Mutex::new(Self::open_append(&path)?));
+
+        let (jobs, queue) = mpsc::channel();
+        let (writer_path, writer_file) = (path.clone(), file.clone());
+        std::thread::Builder::new()
+            .name("position-store".to_string())
+            .spawn(move || {
+                for job in queue {
+                    match job {
+                        Job::Append(entry) => Self::write_entry(&writer_path, &writer_file, &entry),
+                        Job::Flush(done) => {
+                            let _ = done.send(());
+                        }
+                    }
+                }
+            })
+            .context("Failed to start position store thread")?;
+
+        Ok(Self { path, file, jobs })
+    }
+
+    fn open_append(path: &Path) -> Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open position journal {}", path.display()))
+    }
+
+    /// Replay the journal and return all positions that are still open
+    /// Rewrites the file afterwards so it doesn't grow forever
+    pub fn load(&self) -> Result<Vec<Position>> {
+        self.flush();
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+
+        let reader = BufReader::new(File::open(&self.path)?);
+        let mut positions: HashMap<String, Position> = HashMap::new();
+
+        for (line_no, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str::<JournalEntry>(&line) {
+                Ok(JournalEntry::Upsert { position }) => {
+                    positions.insert(position.token_mint.clone(), *position);
+                }
+                Ok(JournalEntry::Remove { token_mint }) => {
+                    positions.remove(&token_mint);
+                }
+                Err(e) => {
+                    // Most likely a torn write from a crash - skip it
+                    warn!("⚠️ Skipping corrupt position journal line {}: {}", line_no + 1, e);
+                }
+            }
+        }
+
+        // Compact: write current state to a temp file and swap it in
+        let tmp_path = self.path.with_extension("tmp");
+        {
+            let mut tmp = File::create(&tmp_path)?;
+            for position in positions.values() {
+                let entry = JournalEntry::Upsert { position: Box::new(position.clone()) };
+                writeln!(tmp, "{}", serde_json::to_string(&entry)?)?;
+            }
+            tmp.sync_all()?;
+        }
+        std::fs::rename(&tmp_path, &self.path)?;
+        *file = Self::open_append(&self.path)?;
+
+        info!(
+            "💾 Loaded {} open position(s) from {}",
+            positions.len(),
+            self.path.display()
+        );
+
+        Ok(positions.into_values().collect())
+    }
+
+    /// Record a new or updated position (queued, written in call order)
+    pub fn record_upsert(&self, position: &Position) {
+        self.append(JournalEntry::Upsert { position: Box::new(position.clone()) });
+    }
+
+    /// Record a closed position (queued, written in call order)
+    pub fn record_remove(&self, token_mint: &str) {
+        self.append(JournalEntry::Remove { token_mint: token_mint.to_string() });
+    }
+
+    /// Block until every queued entry has been written
+    pub fn flush(&self) {
+        let (done, written) = mpsc::channel();
+        if self.jobs.send(Job::Flush(done)).is_ok() {
+            let _ = written.recv();
+        }
+    }
+
+    fn append(&self, entry: JournalEntry) {
+        if self.jobs.send(Job::Append(entry)).is_err() {
+            warn!("⚠️ Failed to write position journal {}: store thread is gone", self.path.display());
+        }
+    }
+
+    fn write_entry(path: &Path, file: &Mutex<File>, entry: &JournalEntry) {
+        let line = match serde_json::to_string(entry) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("⚠️ Failed to serialize position journal entry: {}", e);
+                return;
+            }
+        };
+
+        let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = writeln!(file, "{}", line).and_then(|_| file.sync_data()) {
+            // Never block trading on disk errors, but make it loud
+            warn!("⚠️ Failed to write position journal {}: {}", path.display(), e);
+        }
+    }
+}
+
+impl Drop for PositionStore {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(token_mint: &str, amount_tokens: u64) -> Position {
+        Position::new(token_mint.into(), "TEST".into(), 0.001, amount_tokens, 0.1, 25.0, 100.0, "sig".into(), false)
+    }
+
+    fn store() -> (tempfile::TempDir, PathBuf, PositionStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("positions.jsonl");
+        let store = PositionStore::open(&path).unwrap();
+        (dir, path, store)
+    }
+
+    /// (token_mint, amount_tokens) of every loaded position, sorted by mint
+    fn loaded(store: &PositionStore) -> Vec<(String, u64)> {
+        let mut positions: Vec<_> = store.load().unwrap().into_iter().map(|p| (p.token_mint, p.amount_tokens)).collect();
+        positions.sort();
+        positions
+    }
+
+    fn lines(path: &Path) -> Vec<String> {
+        std::fs::read_to_string(path).unwrap().lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn empty_journal_loads_nothing() {
+        let (_dir, path, store) = store();
+        assert!(loaded(&store).is_empty());
+        assert!(lines(&path).is_empty());
+    }
+
+    #[test]
+    fn replay_keeps_the_latest_snapshot_of_open_positions() {
+        let (_dir, _path, store) = store();
+        store.record_upsert(&position("A", 1_000));
+        store.record_upsert(&position("B", 2_000));
+        store.record_upsert(&position("A", 400));
+        store.record_remove("B");
+        store.record_remove("UNKNOWN");
+
+        assert_eq!(loaded(&store), vec![("A".to_string(), 400)]);
+    }
+
+    #[test]
+    fn load_compacts_to_one_line_per_open_position() {
+        let (_dir, path, store) = store();
+        for amount in [1_000, 900, 800] {
+            store.record_upsert(&position("A", amount));
+        }
+        store.record_upsert(&position("B", 2_000));
+        store.record_upsert(&position("C", 3_000));
+        store.record_remove("C");
+        store.flush();
+        assert_eq!(lines(&path).len(), 6);
+
+        let expected = vec![("A".to_string(), 800), ("B".to_string(), 2_000)];
+        assert_eq!(loaded(&store), expected);
+        assert_eq!(lines(&path).len(), 2);
+        assert!(!path.with_extension("tmp").exists());
+
+        // Appends go to the compacted file and survive a reopen
+        store.record_remove("B");
+        drop(store);
+        let reopened = PositionStore::open(&path).unwrap();
+        assert_eq!(loaded(&reopened), vec![("A".to_string(), 800)]);
+    }
+
+    #[test]
+    fn corrupt_and_truncated_lines_are_skipped() {
+        let (_dir, path, store) = store();
+        store.record_upsert(&position("A", 1_000));
+        store.record_upsert(&position("B", 2_000));
+        store.flush();
+        let full = lines(&path);
+
+        // Garbage and a torn write in the middle, a blank line, and a torn write at the end (no newline)
+        let torn = &full[1][..full[1].len() / 2];
+        let content = format!(
+            "{}\nnot json\n{}\n\n{{\"op\":\"explode\"}}\n{}\n{{\"op\":\"remove\",\"token_mint\":\"A\"}}\n{}",
+            full[0], torn, full[1], torn
+        );
+        std::fs::write(&path, content).unwrap();
+
+        assert_eq!(loaded(&store), vec![("B".to_string(), 2_000)]);
+        assert_eq!(lines(&path).len(), 1, "compaction drops the corrupt lines");
+
+        // The torn tail is gone, so the next append starts on its own line
+        store.record_upsert(&position("C", 3_000));
+        let expected = vec![("B".to_string(), 2_000), ("C".to_string(), 3_000)];
+        assert_eq!(loaded(&store), expected);
+    }
+}
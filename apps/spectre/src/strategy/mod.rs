@@ -0,0 +1,381 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// One rung of a scaled take-profit ladder
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExitRung {
+    /// Profit % (from entry) that triggers this rung, e.g. 30 = +30%
+    pub trigger_percent: f64,
+    /// Percentage of the *remaining* position to sell, e.g. 80 = sell 80% of what's left
+    pub sell_percent: f64,
+}
+
+// NINJA scaled exit ladder (conservative: +30%→80%, +50%→15%, +80%→5%)
+const NINJA_LADDER: &[ExitRung] = &[
+    ExitRung { trigger_percent: 30.0, sell_percent: 80.0 },  // +30% -> sell 80% of position
+    ExitRung { trigger_percent: 50.0, sell_percent: 75.0 },  // +50% -> sell 75% of remaining (= 15% of original)
+    ExitRung { trigger_percent: 80.0, sell_percent: 100.0 }, // +80% -> sell remaining 5%
+];
+
+/// Exit strategy definition: ordered take-profit ladder + stop-loss
+/// Snapshotted onto each `Position` at entry, so config changes only affect new positions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExitStrategy {
+    /// Name for logs/results (e.g. "ninja", "ninja-tight-b")
+    pub name: String,
+    /// Ordered take-profit rungs (empty = single full exit at take-profit)
+    #[serde(default)]
+    pub ladder: Vec<ExitRung>,
+    /// Overrides the signal's stop-loss % (positive number, 25 = -25%)
+    #[serde(default)]
+    pub stop_loss_percent: Option<f64>,
+    /// Overrides the signal's take-profit % for the full exit after the ladder
+    #[serde(default)]
+    pub take_profit_percent: Option<f64>,
+    /// Trailing stop: full exit when price drops this % below the high-water mark
+    #[serde(default)]
+    pub trailing_stop_percent: Option<f64>,
+    /// Trailing stop only arms once the high is this % above entry (0 = from entry)
+    #[serde(default)]
+    pub trailing_activation_percent: f64,
+    /// Move the stop-loss up once this ladder stage has been sold (1 = after TP1)
+    #[serde(default)]
+    pub breakeven_after_stage: Option<u8>,
+    /// Where the moved stop sits relative to entry (0 = breakeven, 10 = lock +10%)
+    #[serde(default)]
+    pub profit_lock_percent: f64,
+    /// Full exit once the position has been held this long (minutes)
+    #[serde(default)]
+    pub max_hold_minutes: Option<u64>,
+    /// Full exit if price stays inside the stagnation band this long (minutes)
+    #[serde(default)]
+    pub stagnation_minutes: Option<u64>,
+    /// Stagnation band around the last anchor price, e.g. 5 = ±5%
+    #[serde(default = "default_stagnation_band_percent")]
+    pub stagnation_band_percent: f64,
+}
+
+fn default_stagnation_band_percent() -> f64 {
+    5.0
+}
+
+/// Slack for comparing percents computed from price ratios (exactly +30% can come out as 29.999...)
+pub const PERCENT_EPSILON: f64 = 1e-9;
+
+/// Profit % has reached a target %
+pub fn percent_reached(percent: f64, target_percent: f64) -> bool {
+    percent >= target_percent - PERCENT_EPSILON
+}
+
+impl ExitStrategy {
+    /// Built-in NINJA ladder (used when no config overrides it)
+    pub fn ninja() -> Self {
+        Self {
+            name: "ninja".to_string(),
+            ladder: NINJA_LADDER.to_vec(),
+            stop_loss_percent: None,
+            take_profit_percent: None,
+            trailing_stop_percent: None,
+            trailing_activation_percent: 0.0,
+            breakeven_after_stage: None,
+            profit_lock_percent: 0.0,
+            max_hold_minutes: None,
+            stagnation_minutes: None,
+            stagnation_band_percent: default_stagnation_band_percent(),
+        }
+    }
+
+    /// Rung for the next scaled exit stage (stage 0 = nothing sold yet)
+    pub fn rung(&self, stage: u8) -> Option<&ExitRung> {
+        self.ladder.get(stage as usize)
+    }
+
+    /// Trailing stop price for a given high-water mark (None = not configured or not armed yet)
+    pub fn trailing_stop_price(&self, entry_price: f64, high_price: f64) -> Option<f64> {
+        let trail_percent = self.trailing_stop_percent?;
+        let high_profit_percent = (high_price / entry_price - 1.0) * 100.0;
+        if !percent_reached(high_profit_percent, self.trailing_activation_percent) {
+            return None;
+        }
+        Some(high_price * (1.0 - trail_percent.abs() / 100.0))
+    }
+
+    /// Locked stop-loss price after a ladder stage was sold (None = keep current SL)
+    pub fn locked_stop_price(&self, entry_price: f64, stage: u8) -> Option<f64> {
+        let after_stage = self.breakeven_after_stage?;
+        if stage < after_stage {
+            return None;
+        }
+        Some(entry_price * (1.0 + self.profit_lock_percent / 100.0))
+    }
+
+    /// Ladder and stops that can't work: sells outside (0, 100]%, targets that don't rise
+    /// rung over rung, stops outside (0, 100)%, a take-profit that isn't above 0%
+    /// (same ranges `Position::set_exit_levels` enforces)
+    pub fn validate(&self) -> Result<()> {
+        for (key, percent) in [("stop_loss_percent", self.stop_loss_percent), ("trailing_stop_percent", self.trailing_stop_percent)] {
+            if let Some(percent) = percent {
+                if !(percent > 0.0 && percent < 100.0) {
+                    bail!("strategy '{}': {} must be in (0, 100), got {}", self.name, key, percent);
+                }
+            }
+        }
+        if let Some(percent) = self.take_profit_percent {
+            if !(percent > 0.0 && percent.is_finite()) {
+                bail!("strategy '{}': take_profit_percent must be above 0, got {}", self.name, percent);
+            }
+        }
+
+        let mut previous_trigger: Option<f64> = None;
+        for (index, rung) in self.ladder.iter().enumerate() {
+            let stage = index + 1;
+            if !(rung.sell_percent > 0.0 && rung.sell_percent <= 100.0) {
+                bail!("strategy '{}' rung {}: sell_percent must be in (0, 100], got {}", self.name, stage, rung.sell_percent);
+            }
+            if !rung.trigger_percent.is_finite() {
+                bail!("strategy '{}' rung {}: trigger_percent must be a number, got {}", self.name, stage, rung.trigger_percent);
+            }
+            if let Some(previous) = previous_trigger {
+                if rung.trigger_percent <= previous {
+                    bail!(
+                        "strategy '{}' rung {}: trigger_percent {} must be above rung {}'s {}",
+                        self.name, stage, rung.trigger_percent, stage - 1, previous
+                    );
+                }
+            }
+            previous_trigger = Some(rung.trigger_percent);
+        }
+        Ok(())
+    }
+
+    /// Short human-readable ladder, e.g. "80%@+30%, 75%@+50%, 100%@+80%"
+    pub fn describe_ladder(&self) -> String {
+        if self.ladder.is_empty() {
+            return "none".to_string();
+        }
+        self.ladder
+            .iter()
+            .map(|r| format!("{:.0}%@+{:.0}%", r.sell_percent, r.trigger_percent))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Config entry: a single strategy or several variants picked at random (A/B testing)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum StrategyEntry {
+    One(ExitStrategy),
+    Variants(Vec<ExitStrategy>),
+}
+
+/// Exit strategies keyed by signal type and/or strength
+///
+/// Lookup order for a signal: `"<type>:<STRENGTH>"`, `"<type>"`, `"default"`,
+/// e.g. `"ninja:STRONG"`, then `"ninja"`, then `"default"`.
+/// Loaded from a JSON file (`EXIT_STRATEGIES_FILE`) shaped like:
+/// `{ "ninja": { "name": "ninja", "ladder": [{ "trigger_percent": 30, "sell_percent": 80 }] } }`
+#[derive(Debug, Clone, Default)]
+pub struct ExitStrategies {
+    entries: HashMap<String, StrategyEntry>,
+}
+
+impl ExitStrategies {
+    /// Built-in strategies only (NINJA ladder)
+    pub fn builtin() -> Self {
+        let mut entries = HashMap::new();
+        entries.insert("ninja".to_string(), StrategyEntry::One(ExitStrategy::ninja()));
+        Self { entries }
+    }
+
+    /// Built-ins overridden/extended by a JSON config file
+    pub fn from_file(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read exit strategies file {}", path))?;
+        let loaded: HashMap<String, StrategyEntry> = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid exit strategies file {}", path))?;
+
+        let mut strategies = Self::builtin();
+        for (key, entry) in loaded {
+            let variants = match &entry {
+                StrategyEntry::One(strategy) => std::slice::from_ref(strategy),
+                StrategyEntry::Variants(variants) => variants.as_slice(),
+            };
+            for strategy in variants {
+                strategy
+                    .validate()
+                    .with_context(|| format!("Invalid exit strategy '{}' in {}", key, path))?;
+            }
+            strategies.entries.insert(Self::normalize_key(&key), entry);
+        }
+
+        info!("📐 Loaded {} exit strategy key(s) from {}", strategies.entries.len(), path);
+        Ok(strategies)
+    }
+
+    /// Keys are matched as lowercase type + uppercase strength
+    fn normalize_key(key: &str) -> String {
+        match key.split_once(':') {
+            Some((signal_type, strength)) => {
+                format!("{}:{}", signal_type.to_lowercase(), strength.to_uppercase())
+            }
+            None => key.to_lowercase(),
+        }
+    }
+
+    /// Find the strategy for a signal (None = plain SL/TP from the signal)
+    pub fn resolve(&self, signal_type: &str, strength: &str) -> Option<ExitStrategy> {
+        let signal_type = signal_type.to_lowercase();
+        let keys = [
+            format!("{}:{}", signal_type, strength.to_uppercase()),
+            signal_type,
+            "default".to_string(),
+        ];
+
+        let entry = keys.iter().find_map(|k| self.entries.get(k))?;
+
+        match entry {
+            StrategyEntry::One(strategy) => Some(strategy.clone()),
+            StrategyEntry::Variants(variants) if !variants.is_empty() => {
+                let index = rand::random::<usize>() % variants.len();
+                Some(variants[index].clone())
+            }
+            StrategyEntry::Variants(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn strategy(ladder: &[(f64, f64)]) -> ExitStrategy {
+        ExitStrategy {
+            name: "test".to_string(),
+            ladder: ladder.iter().map(|&(trigger_percent, sell_percent)| ExitRung { trigger_percent, sell_percent }).collect(),
+            ..ExitStrategy::ninja()
+        }
+    }
+
+    fn strategies_file(json: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(json.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn builtin_ladder_is_valid() {
+        assert!(ExitStrategy::ninja().validate().is_ok());
+        assert!(strategy(&[]).validate().is_ok());
+    }
+
+    #[test]
+    fn invalid_ladders_name_strategy_and_rung() {
+        let error = strategy(&[(30.0, 50.0), (50.0, 120.0)]).validate().unwrap_err().to_string();
+        assert!(error.contains("'test' rung 2") && error.contains("sell_percent"), "{}", error);
+
+        let error = strategy(&[(30.0, 50.0), (30.0, 100.0)]).validate().unwrap_err().to_string();
+        assert!(error.contains("rung 2") && error.contains("above rung 1"), "{}", error);
+
+        let error = strategy(&[(50.0, 0.0)]).validate().unwrap_err().to_string();
+        assert!(error.contains("rung 1"), "{}", error);
+
+        assert!(strategy(&[(f64::NAN, 50.0)]).validate().is_err());
+        assert!(strategy(&[(30.0, f64::NAN)]).validate().is_err());
+    }
+
+    #[test]
+    fn negative_stops_are_rejected() {
+        let negative_stop = ExitStrategy { stop_loss_percent: Some(-25.0), ..strategy(&[]) };
+        assert!(negative_stop.validate().unwrap_err().to_string().contains("stop_loss_percent"));
+
+        let negative_trail = ExitStrategy { trailing_stop_percent: Some(-10.0), ..strategy(&[]) };
+        assert!(negative_trail.validate().unwrap_err().to_string().contains("trailing_stop_percent"));
+    }
+
+    #[test]
+    fn stops_outside_0_to_100_are_rejected() {
+        for percent in [0.0, 100.0, 150.0, f64::NAN, f64::INFINITY] {
+            let stop = ExitStrategy { stop_loss_percent: Some(percent), ..strategy(&[]) };
+            assert!(stop.validate().unwrap_err().to_string().contains("stop_loss_percent"), "SL {}", percent);
+
+            let trail = ExitStrategy { trailing_stop_percent: Some(percent), ..strategy(&[]) };
+            assert!(trail.validate().unwrap_err().to_string().contains("trailing_stop_percent"), "trail {}", percent);
+        }
+
+        let ok = ExitStrategy { stop_loss_percent: Some(25.0), trailing_stop_percent: Some(99.5), ..strategy(&[]) };
+        assert!(ok.validate().is_ok());
+    }
+
+    #[test]
+    fn take_profit_must_be_a_positive_number() {
+        for percent in [0.0, -50.0, f64::NAN, f64::INFINITY] {
+            let take_profit = ExitStrategy { take_profit_percent: Some(percent), ..strategy(&[]) };
+            assert!(take_profit.validate().unwrap_err().to_string().contains("take_profit_percent"), "TP {}", percent);
+        }
+
+        let ok = ExitStrategy { take_profit_percent: Some(250.0), ..strategy(&[]) };
+        assert!(ok.validate().is_ok());
+    }
+
+    #[test]
+    fn resolve_falls_back_from_strength_to_type_to_default() {
+        let file = strategies_file(
+            r#"{
+                "ninja:strong": { "name": "ninja-strong" },
+                "Consensus": { "name": "consensus" },
+                "default": { "name": "fallback" }
+            }"#,
+        );
+        let strategies = ExitStrategies::from_file(file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(strategies.resolve("NINJA", "strong").unwrap().name, "ninja-strong");
+        assert_eq!(strategies.resolve("ninja", "WEAK").unwrap().name, "ninja", "built-in kept");
+        assert_eq!(strategies.resolve("consensus", "WEAK").unwrap().name, "consensus");
+        assert_eq!(strategies.resolve("whale", "STRONG").unwrap().name, "fallback");
+
+        assert!(ExitStrategies::builtin().resolve("whale", "STRONG").is_none());
+    }
+
+    #[test]
+    fn variants_are_picked_among_and_empty_lists_mean_none() {
+        let file = strategies_file(r#"{ "ninja": [{ "name": "a" }, { "name": "b" }], "consensus": [] }"#);
+        let strategies = ExitStrategies::from_file(file.path().to_str().unwrap()).unwrap();
+
+        for _ in 0..20 {
+            let name = strategies.resolve("ninja", "").unwrap().name;
+            assert!(name == "a" || name == "b", "{}", name);
+        }
+        assert!(strategies.resolve("consensus", "").is_none());
+    }
+
+    #[test]
+    fn from_file_rejects_invalid_ladders_and_bad_json() {
+        let file = strategies_file(
+            r#"{ "ninja:STRONG": [
+                { "name": "ok", "ladder": [{ "trigger_percent": 30, "sell_percent": 50 }] },
+                { "name": "broken", "ladder": [
+                    { "trigger_percent": 50, "sell_percent": 50 },
+                    { "trigger_percent": 40, "sell_percent": 100 }
+                ] }
+            ] }"#,
+        );
+        let error = format!("{:#}", ExitStrategies::from_file(file.path().to_str().unwrap()).unwrap_err());
+        assert!(error.contains("ninja:STRONG") && error.contains("'broken' rung 2"), "{}", error);
+
+        let file = strategies_file(r#"{ "consensus": { "name": "no-stop", "stop_loss_percent": 0 } }"#);
+        let error = format!("{:#}", ExitStrategies::from_file(file.path().to_str().unwrap()).unwrap_err());
+        assert!(error.contains("'no-stop'") && error.contains("stop_loss_percent"), "{}", error);
+
+        let file = strategies_file(r#"{ "consensus": { "name": "instant-tp", "take_profit_percent": 0 } }"#);
+        let error = format!("{:#}", ExitStrategies::from_file(file.path().to_str().unwrap()).unwrap_err());
+        assert!(error.contains("take_profit_percent"), "{}", error);
+
+        let file = strategies_file(r#"{ "ninja": { "ladder": 5 } }"#);
+        assert!(ExitStrategies::from_file(file.path().to_str().unwrap()).is_err());
+        assert!(ExitStrategies::from_file("/nonexistent/strategies.json").is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{hash::Hash, transaction::VersionedTransaction};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::confirm::{transaction_signature, TxConfirmer, TxOutcome};
+use crate::error::SpectreError;
+use crate::jito::JitoClient;
+
+/// Submitted transaction and its final on-chain state
+#[derive(Debug, Clone)]
+pub struct Submission {
+    pub signature: String,
+    pub bundle_id: Option<String>,
+    pub outcome: TxOutcome,
+    /// Time until the transaction was accepted by Jito/RPC (excludes confirmation wait)
+    pub submit_latency: std::time::Duration,
+}
+
+impl Submission {
+    pub fn is_confirmed(&self) -> bool {
+        matches!(self.outcome, TxOutcome::Confirmed { .. })
+    }
+}
+
+/// Sends signed transactions and tracks them until they land (or can't anymore)
+#[async_trait]
+pub trait TxSubmitter: Send + Sync {
+    /// Blockhash for venues that need one right before signing
+    async fn latest_blockhash(&self) -> Result<Hash>;
+
+    /// Submit and wait for a final state
+    /// Err only if the transaction could not be submitted at all
+    async fn submit_and_confirm(&self, signed_tx: &VersionedTransaction) -> Result<Submission>;
+}
+
+/// Jito bundle (MEV protection) with RPC fallback, confirmed via RPC + Jito bundle status
+pub struct JitoSubmitter {
+    jito: JitoClient,
+    rpc_client: Arc<RpcClient>,
+    confirmer: TxConfirmer,
+}
+
+impl JitoSubmitter {
+    pub fn new(jito: JitoClient, rpc_client: Arc<RpcClient>) -> Self {
+        Self {
+            jito,
+            confirmer: TxConfirmer::new(rpc_client.clone()),
+            rpc_client,
+        }
+    }
+}
+
+#[async_trait]
+impl TxSubmitter for JitoSubmitter {
+    async fn latest_blockhash(&self) -> Result<Hash> {
+        Ok(self.rpc_client.get_latest_blockhash().await
+            .map_err(|e| SpectreError::http("RPC getLatestBlockhash", e))?)
+    }
+
+    /// Send via Jito bundle, falling back to RPC, then track it until it confirms,
+    /// fails on-chain, or its blockhash expires
+    async fn submit_and_confirm(&self, signed_tx: &VersionedTransaction) -> Result<Submission> {
+        let start = std::time::Instant::now();
+        let signature = transaction_signature(signed_tx);
+        let recent_blockhash = *signed_tx.message.recent_blockhash();
+
+        let bundle_id = match self.jito.send_bundle(signed_tx).await {
+            Ok(id) => Some(id),
+            Err(jito_err) => {
+                warn!("⚠️ Jito bundle failed, falling back to RPC: {}", jito_err);
+                self.rpc_client
+                    .send_transaction(signed_tx)
+                    .await
+                    .map_err(|rpc_err| {
+                        // Preflight errors say why the transaction can't land
+                        SpectreError::from_transaction_error(&rpc_err.to_string())
+                            .unwrap_or_else(|| SpectreError::SubmitRejected(format!("Jito={}, RPC={}", jito_err, rpc_err)))
+                    })?;
+                None
+            }
+        };
+
+        let submit_latency = start.elapsed();
+        info!(
+            "📤 TX {} submitted{} - waiting for confirmation",
+            signature,
+            bundle_id.as_deref().map(|id| format!(" (bundle {})", id)).unwrap_or_default()
+        );
+
+        let outcome = self.confirmer
+            .confirm(&signature, &recent_blockhash, bundle_id.as_deref(), &self.jito)
+            .await;
+
+        Ok(Submission {
+            signature: signature.to_string(),
+            bundle_id,
+            outcome,
+            submit_latency,
+        })
+    }
+}
@@ -2,28 +2,73 @@ use anyhow::{anyhow, Result};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
-    signature::{Keypair, Signer},
     transaction::VersionedTransaction,
 };
 use std::sync::Arc;
 use tracing::{info, warn, error};
 
 use crate::config::Config;
-use crate::jupiter::JupiterClient;
+use crate::error::SpectreError;
+use crate::jupiter::{JupiterClient, SOL_MINT};
 use crate::jito::JitoClient;
+use crate::journal::{ExitFill, OrderContext, TradeJournal};
+use crate::paper::{SimulatedFill, BASE_FEE_LAMPORTS};
+use crate::price::PriceSource;
 use crate::pumpfun_trade::PumpfunTrader;
 use crate::position::{Position, PositionManager, ExitReason};
 use crate::redis::{SpectreSignal, SpectrePreSignal, TradeResult};
+use crate::risk::{BuyReservation, Exposure, RiskManager, RiskRejection};
+use crate::sizing::{BuySize, SizingInput};
+use crate::store::PositionStore;
+use crate::submit::{JitoSubmitter, TxSubmitter};
+use crate::venue::{sign_transaction, BuyOrder, SellOrder, Side, SwapTx, SwapVenue};
+use crate::wallet::{WalletHolding, WalletReader};
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::RwLock;
 
+/// Positions younger than this are left alone by reconciliation
+/// (the buy may not be visible on-chain yet)
+const RECONCILE_GRACE_SECS: i64 = 60;
+
+/// Base/quote mints the wallet holds as currency - never adopted as positions
+const RECONCILE_NEVER_ADOPT: [&str; 3] = [
+    SOL_MINT,
+    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", // USDC
+    "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", // USDT
+];
+
+/// How long to wait for a buy to show up in the wallet before falling back to an estimate
+const FILL_TIMEOUT_SECS: u64 = 20;
+const FILL_POLL_INTERVAL_MS: u64 = 500;
+
+/// Skip a buy if the quote moved this much against the signal price
+const MAX_PRICE_CHANGE_PERCENT: f64 = 30.0;
+
+/// Outcome of a wallet reconciliation pass
+#[derive(Debug, Default)]
+pub struct ReconcileReport {
+    /// Untracked holdings adopted as new positions
+    pub adopted: Vec<Position>,
+    /// Untracked holdings we could not (or were told not to) adopt
+    pub flagged: Vec<String>,
+    /// Tracked positions with zero on-chain balance that were removed
+    pub dropped: Vec<String>,
+    /// Tracked positions whose token amount was corrected
+    pub resynced: Vec<String>,
+}
+
 /// Prepared transaction ready for immediate execution
 #[derive(Debug, Clone)]
 pub struct PreparedTx {
     pub token_mint: String,
     pub token_symbol: String,
-    pub tx_bytes: Vec<u8>,
+    /// Venue that built the transaction (only used when the buy routes there too)
+    pub venue: &'static str,
+    /// Buy size the transaction was built for (only used when the buy is sized the same)
+    pub amount_sol: f64,
+    pub transaction: VersionedTransaction,
     pub created_at: std::time::Instant,
     pub market_cap_usd: Option<f64>,
     pub entry_price_usd: Option<f64>,
@@ -78,31 +123,91 @@ impl PreparedTxCache {
     }
 }
 
-pub struct SpectreTrader {
-    config: Config,
-    rpc_client: Arc<RpcClient>,
-    jupiter: JupiterClient,
-    jito: JitoClient,
-    pumpfun: PumpfunTrader,
-    position_manager: PositionManager,
-    prepared_tx_cache: PreparedTxCache,
+/// Everything the trader talks to outside the process
+pub struct TraderBackends {
+    /// Bonding-curve venue (NINJA signals, pump.fun positions)
+    pub pumpfun: Arc<dyn SwapVenue>,
+    /// Aggregator venue (CONSENSUS signals, graduated tokens, adopted holdings)
+    pub jupiter: Arc<dyn SwapVenue>,
+    pub submitter: Arc<dyn TxSubmitter>,
+    pub wallet: Arc<dyn WalletReader>,
 }
 
-impl SpectreTrader {
-    pub fn new(config: Config) -> Self {
+impl TraderBackends {
+    /// PumpPortal, Jupiter, Jito and the configured RPC
+    pub fn live(config: &Config) -> Self {
         let rpc_client = Arc::new(RpcClient::new_with_commitment(
             config.rpc_url.clone(),
             CommitmentConfig::confirmed(),
         ));
 
         Self {
-            jupiter: JupiterClient::with_api_key(config.jupiter_api_key.clone()),
-            jito: JitoClient::new(&config.jito_block_engine_url),
-            pumpfun: PumpfunTrader::new(),
-            position_manager: PositionManager::new(),
+            pumpfun: Arc::new(PumpfunTrader::with_api_url(&config.pumpportal_api_url)),
+            jupiter: Arc::new(match config.jupiter_api_url {
+                Some(ref url) => JupiterClient::with_base_url(config.jupiter_api_key.clone(), url),
+                None => JupiterClient::with_api_key(config.jupiter_api_key.clone()),
+            }),
+            submitter: Arc::new(JitoSubmitter::new(
+                JitoClient::new(&config.jito_block_engine_url),
+                rpc_client.clone(),
+            )),
+            wallet: rpc_client,
+        }
+    }
+}
+
+pub struct SpectreTrader {
+    config: Config,
+    pumpfun: Arc<dyn SwapVenue>,
+    jupiter: Arc<dyn SwapVenue>,
+    submitter: Arc<dyn TxSubmitter>,
+    wallet: Arc<dyn WalletReader>,
+    position_manager: PositionManager,
+    /// Portfolio limits consulted before every buy
+    risk: RiskManager,
+    prepared_tx_cache: PreparedTxCache,
+    /// Local SQLite audit trail (signals, order attempts, fills, exits)
+    journal: TradeJournal,
+    /// SOL price in USD (for converting SOL spent into USD entry prices)
+    sol_price_usd: RwLock<f64>,
+    /// New entries halted by an operator command (exits keep running)
+    paused: AtomicBool,
+}
+
+impl SpectreTrader {
+    pub fn new(config: Config) -> Result<Self> {
+        let backends = TraderBackends::live(&config);
+        Self::with_backends(config, backends)
+    }
+
+    /// Trader on custom venues / submitter / wallet (e.g. in-process fakes)
+    pub fn with_backends(config: Config, backends: TraderBackends) -> Result<Self> {
+        let position_store = PositionStore::open(&config.positions_file)?;
+        let journal = TradeJournal::open(&config.journal_db)?;
+
+        Ok(Self {
+            pumpfun: backends.pumpfun,
+            jupiter: backends.jupiter,
+            submitter: backends.submitter,
+            wallet: backends.wallet,
+            position_manager: PositionManager::with_store(position_store),
+            risk: RiskManager::new(config.risk_limits()),
             prepared_tx_cache: PreparedTxCache::new(60), // 60 second expiry
+            journal,
+            sol_price_usd: RwLock::new(200.0), // Default until main sets the real price
+            paused: AtomicBool::new(false),
             config,
-            rpc_client,
+        })
+    }
+
+    /// Venue for a position or signal
+    /// - NINJA (micro-cap $5K-$20K) -> pump.fun bonding curve (more reliable)
+    /// - CONSENSUS ($20K+) -> Jupiter (token likely graduated to Raydium)
+    fn venue(&self, is_pumpfun: bool) -> &dyn SwapVenue {
+        if is_pumpfun {
+            self.pumpfun.as_ref()
+        } else {
+            self.jupiter.as_ref()
         }
     }
 
@@ -112,28 +217,35 @@ impl SpectreTrader {
         let token_mint = &pre_signal.token_mint;
         let token_symbol = &pre_signal.token_symbol;
 
+        // Nothing is ever sent in paper mode, nothing is bought while paused
+        if self.config.paper_mode || self.is_paused() {
+            return;
+        }
+
         // Check if we already have a position (shouldn't prepare if we do)
         if self.position_manager.has_position(token_mint).await {
             warn!("⚠️ Already have position in {}, skipping TX preparation", token_symbol);
             return;
         }
 
-        // Get buy transaction from PumpPortal
-        let slippage_percent = (self.config.slippage_bps / 100) as u16;
-        let priority_fee_sol = self.config.jito_tip_lamports as f64 / 1e9;
+        // Pre-signals come from the NINJA flow, which buys on the bonding curve
+        let size = self.config.sizing.size(&SizingInput::from(pre_signal));
+        let order = BuyOrder {
+            wallet: self.config.wallet_pubkey(),
+            token_mint: token_mint.clone(),
+            amount_sol: size.amount_sol,
+            slippage_bps: self.config.slippage_bps,
+            priority_fee_lamports: self.config.jito_tip_lamports,
+        };
 
-        match self.pumpfun.get_buy_transaction(
-            &self.config.wallet_pubkey().to_string(),
-            token_mint,
-            self.config.trade_amount_sol,
-            slippage_percent,
-            priority_fee_sol,
-        ).await {
-            Ok(tx_bytes) => {
+        match self.pumpfun.build_buy(&order).await {
+            Ok(swap) => {
                 let prepared = PreparedTx {
                     token_mint: token_mint.clone(),
                     token_symbol: token_symbol.clone(),
-                    tx_bytes,
+                    venue: self.pumpfun.name(),
+                    amount_sol: size.amount_sol,
+                    transaction: swap.transaction,
                     created_at: std::time::Instant::now(),
                     market_cap_usd: pre_signal.market_cap_usd,
                     entry_price_usd: pre_signal.entry_price_usd,
@@ -146,6 +258,15 @@ impl SpectreTrader {
         }
     }
 
+    /// Halt (true) or resume (false) new entries, returns the previous state
+    pub fn set_paused(&self, paused: bool) -> bool {
+        self.paused.swap(paused, Ordering::SeqCst)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
     /// Get prepared TX cache reference
     pub fn prepared_tx_cache(&self) -> &PreparedTxCache {
         &self.prepared_tx_cache
@@ -154,239 +275,368 @@ impl SpectreTrader {
     /// Execute buy order for a signal with retry logic
     /// Routes NINJA signals to pump.fun, CONSENSUS signals to Jupiter
     pub async fn execute_buy(&self, signal: &SpectreSignal) -> Result<TradeResult> {
+        let signal_age_ms = signal.age_ms();
+        let result = self.execute_buy_checked(signal, signal_age_ms).await?;
+        Ok(TradeResult { signal_age_ms, ..result })
+    }
+
+    /// Pre-trade checks (signal age, existing position, sizing, risk limits), then the buy
+    async fn execute_buy_checked(&self, signal: &SpectreSignal, signal_age_ms: Option<u64>) -> Result<TradeResult> {
         let token_mint = &signal.token_mint;
         let token_symbol = &signal.token_symbol;
 
+        self.journal.record_signal(signal);
+
+        if self.is_paused() {
+            let e = SpectreError::Paused("new entries are paused".to_string());
+            warn!("⏸️ BUY {} skipped: {}", token_symbol, e);
+            return Ok(TradeResult {
+                error_code: Some(e.code()),
+                ..self.create_error_result(signal, &e.to_string(), 1, None)
+            });
+        }
+
+        // A backed-up queue or a restart can hand us signals the market has long moved past
+        match (signal_age_ms, self.config.max_signal_age_secs(&signal.signal_type)) {
+            (Some(age_ms), Some(max_age_secs)) if age_ms > max_age_secs * 1000 => {
+                let e = SpectreError::StaleSignal(format!(
+                    "{:.1}s old (max {}s for {})",
+                    age_ms as f64 / 1000.0, max_age_secs, signal.signal_type
+                ));
+                warn!("⌛ BUY {} skipped: {}", token_symbol, e);
+                return Ok(TradeResult {
+                    error_code: Some(e.code()),
+                    ..self.create_error_result(signal, &e.to_string(), 1, None)
+                });
+            }
+            (None, _) => warn!("⚠️ Unparseable signal timestamp for {}: {}", token_symbol, signal.timestamp),
+            _ => {}
+        }
+
         // Check if we already have a position
         if self.position_manager.has_position(token_mint).await {
             warn!("⚠️ Already have position in {}, skipping", token_symbol);
             return Ok(self.create_error_result(signal, "Already have position", 1, None));
         }
 
-        // Route based on signal type:
-        // - NINJA (micro-cap $5K-$20K) -> pump.fun bonding curve (more reliable)
-        // - CONSENSUS ($20K+) -> Jupiter (token likely graduated to Raydium)
+        let size = self.buy_size(signal);
+        info!("📏 Buy size for {}: {} SOL ({})", token_symbol, size.amount_sol, size.basis);
+
+        // Portfolio limits - the reservation counts this buy until it's done, i.e. until
+        // after its position is in the position manager (both buy paths add it before returning)
+        let _reservation = match self.admit_buy(signal, size.amount_sol).await {
+            Ok(reservation) => reservation,
+            Err(rejection) => {
+                warn!("🛡️ BUY {} rejected: {}", token_symbol, rejection);
+                let e = SpectreError::RiskRejected(rejection.to_string());
+                return Ok(TradeResult {
+                    error_code: Some(e.code()),
+                    ..self.create_error_result(signal, &e.to_string(), 1, None)
+                });
+            }
+        };
+
         let is_ninja = signal.signal_type.to_lowercase() == "ninja";
 
         info!(
             "👻 Executing BUY via {}: {} ({}) - MCap: ${:.0}",
-            if is_ninja { "PUMP.FUN" } else { "JUPITER" },
+            self.venue(is_ninja).label(),
             token_symbol,
             token_mint,
             signal.market_cap_usd.unwrap_or(0.0)
         );
 
-        if is_ninja {
-            self.execute_buy_pumpfun(signal).await
+        if self.config.paper_mode {
+            self.execute_buy_paper(signal, is_ninja).await
         } else {
-            self.execute_buy_jupiter(signal).await
+            self.execute_buy_live(signal, is_ninja).await
         }
     }
 
-    /// Execute buy via pump.fun bonding curve (for NINJA signals)
-    /// Uses prepared TX from cache if available (Fast Confirm optimization)
-    async fn execute_buy_pumpfun(&self, signal: &SpectreSignal) -> Result<TradeResult> {
-        const MAX_ATTEMPTS: u32 = 2;
+    /// Buy size for a signal under the configured sizing policy
+    fn buy_size(&self, signal: &SpectreSignal) -> BuySize {
+        self.config.sizing.size(&SizingInput::from(signal))
+    }
+
+    /// Check a buy of `amount_sol` against the risk limits
+    async fn admit_buy(&self, signal: &SpectreSignal, amount_sol: f64) -> Result<BuyReservation<'_>, RiskRejection> {
+        let realized_today_sol = self.journal.realized_pnl_today().await.unwrap_or_else(|e| {
+            warn!("⚠️ Failed to read today's realized PnL: {}", e);
+            0.0
+        });
+
+        // Paper buys never spend the wallet; an unknown balance can't cover the reserve
+        let wallet_sol = match self.config.paper_mode {
+            true => None,
+            false => Some(self.get_balance().await.unwrap_or_else(|e| {
+                warn!("⚠️ Failed to get balance for the risk check: {}", e);
+                0.0
+            })),
+        };
 
+        // Reservations first: a buy finishing while positions are read is in one snapshot or the other
+        let in_flight = self.risk.in_flight();
+        let open_positions = self
+            .position_manager
+            .get_all_positions()
+            .await
+            .iter()
+            .map(|p| (p.token_mint.clone(), p.open_cost_sol()))
+            .collect();
+
+        let exposure = Exposure {
+            open_positions,
+            in_flight,
+            realized_today_sol,
+            wallet_sol,
+        };
+        self.risk.admit(&signal.token_mint, amount_sol, &exposure)
+    }
+
+    /// Paper buy: real quote/price, simulated fill - nothing is signed or sent
+    /// Venues without a quote API (pump.fun) fill at the signal price
+    async fn execute_buy_paper(&self, signal: &SpectreSignal, is_pumpfun: bool) -> Result<TradeResult> {
+        let start = std::time::Instant::now();
         let token_mint = &signal.token_mint;
         let token_symbol = &signal.token_symbol;
+        let venue = self.venue(is_pumpfun);
+        let order = OrderContext { side: "buy", venue: venue.name(), token_mint, token_symbol };
 
-        // Use dynamic priority fee from signal, or fall back to config default
+        let sol_usd = *self.sol_price_usd.read().await;
+        let trade_amount = self.buy_size(signal).amount_sol;
         let priority_fee = signal.priority_fee_lamports.unwrap_or(self.config.jito_tip_lamports);
-        info!(
-            "💰 Priority fee: {} lamports ({:.6} SOL) {}",
-            priority_fee,
-            priority_fee as f64 / 1e9,
-            if signal.priority_fee_lamports.is_some() { "(dynamic)" } else { "(config default)" }
-        );
+        let network_fee_sol = (BASE_FEE_LAMPORTS + priority_fee) as f64 / 1e9;
 
-        // ⚡ FAST CONFIRM: Check if we have a prepared TX from pre-signal
-        let prepared_tx = self.prepared_tx_cache.get(token_mint).await;
-        let used_prepared = prepared_tx.is_some();
+        let (fill, decimals) = match self.paper_buy_fill(venue, signal, trade_amount, network_fee_sol, sol_usd).await {
+            Ok(quoted) => quoted,
+            Err(e) => {
+                error!("❌ [Paper] Buy quote failed for {}: {}", token_symbol, e);
+                let error = format!("Paper quote failed: {}", e);
+                self.journal.record_attempt(&order, 1, start.elapsed().as_millis() as u64, None, Some(&error));
+                return Ok(TradeResult {
+                    error_code: SpectreError::code_of(&e),
+                    ..self.create_error_result(signal, &error, 1, None)
+                });
+            }
+        };
 
-        if used_prepared {
-            info!("⚡ Using PREPARED TX for {} (Fast Confirm)", token_symbol);
+        let mut position = Position::new_with_signal_type(
+            token_mint.clone(),
+            token_symbol.clone(),
+            fill.price_usd,
+            fill.amount_tokens,
+            trade_amount,
+            signal.stop_loss_percent,
+            signal.take_profit_percent,
+            "paper".to_string(),
+            is_pumpfun,
+            signal.signal_type.clone(),
+        )
+        .with_trigger_wallets(signal.wallets.iter().map(|w| w.address.clone()).collect());
+        if let Some(strategy) = self.config.exit_strategies.resolve(&signal.signal_type, &signal.strength) {
+            position = position.with_exit_strategy(strategy);
         }
+        position.apply_fill(fill.amount_tokens, decimals, fill.price_usd);
 
-        for attempt in 1..=MAX_ATTEMPTS {
-            let start = std::time::Instant::now();
+        self.journal.record_attempt(&order, 1, start.elapsed().as_millis() as u64, None, None);
+        self.journal.record_entry(&position, fill.amount_sol);
+        self.position_manager.add_position(position).await;
 
-            // 1. Get transaction - from cache or PumpPortal
-            // Note: Prepared TX uses config default fee; fresh TX uses dynamic fee
-            let tx_bytes = if let Some(ref prepared) = prepared_tx {
-                if attempt == 1 {
-                    // Use prepared TX on first attempt (may have old priority fee, but faster)
-                    prepared.tx_bytes.clone()
-                } else {
-                    // Get fresh TX on retry with dynamic priority fee
-                    self.get_fresh_pumpfun_tx_with_fee(token_mint, priority_fee).await?
-                }
-            } else {
-                // No prepared TX, get fresh one with dynamic priority fee
-                match self.get_fresh_pumpfun_tx_with_fee(token_mint, priority_fee).await {
-                    Ok(bytes) => bytes,
-                    Err(e) => {
-                        error!("❌ [Attempt {}/{}] PumpPortal buy failed: {}", attempt, MAX_ATTEMPTS, e);
-                        if attempt < MAX_ATTEMPTS {
-                            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                            continue;
-                        }
-                        return Ok(self.create_error_result(signal, &format!("PumpPortal failed: {}", e), attempt, None));
-                    }
-                }
-            };
+        info!(
+            "📝 PAPER BUY ({}): {} tokens of {} for {:.4} SOL @ ${:.10}",
+            venue.name(), fill.amount_tokens, token_symbol, fill.amount_sol, fill.price_usd
+        );
 
-            // Remove from cache after use (regardless of success)
-            if used_prepared {
-                self.prepared_tx_cache.remove(token_mint).await;
-            }
+        Ok(TradeResult {
+            success: true,
+            amount_sol: fill.amount_sol,
+            amount_tokens: Some(fill.amount_tokens as f64),
+            price_per_token: Some(fill.price_usd),
+            error: None,
+            latency_ms: start.elapsed().as_millis() as u64,
+            price_at_trade: Some(fill.price_usd),
+            signal_to_submit_ms: signal.age_ms(), // simulated fill stands in for the submission
+            ..self.create_error_result(signal, "", 1, None) // signal context
+        })
+    }
 
-            // 2. Sign transaction
-            let signed_tx = match self.pumpfun.sign_transaction(&tx_bytes, &self.config.wallet) {
-                Ok(tx) => tx,
-                Err(e) => {
-                    error!("❌ [Attempt {}/{}] Failed to sign TX: {}", attempt, MAX_ATTEMPTS, e);
-                    if attempt < MAX_ATTEMPTS {
-                        continue;
-                    }
-                    return Ok(self.create_error_result(signal, &format!("Sign failed: {}", e), attempt, None));
-                }
-            };
+    /// Simulated buy from the venue's quote, or the signal price if it has none
+    async fn paper_buy_fill(&self, venue: &dyn SwapVenue, signal: &SpectreSignal, trade_amount: f64, network_fee_sol: f64, sol_usd: f64) -> Result<(SimulatedFill, u8)> {
+        let amount_lamports = (trade_amount * 1e9) as u64;
+        let model = self.config.fill_model();
 
-            // 3. Send via Jito bundle for MEV protection
-            let tx_sig = match self.jito.send_bundle(&signed_tx).await {
-                Ok(id) => id,
-                Err(e) => {
-                    warn!("⚠️ [Attempt {}/{}] Jito bundle failed, falling back to RPC: {}", attempt, MAX_ATTEMPTS, e);
-                    match self.rpc_client.send_and_confirm_transaction(&signed_tx).await {
-                        Ok(sig) => sig.to_string(),
-                        Err(rpc_e) => {
-                            error!("❌ [Attempt {}/{}] RPC also failed: {}", attempt, MAX_ATTEMPTS, rpc_e);
-                            if attempt < MAX_ATTEMPTS {
-                                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                                continue;
-                            }
-                            return Ok(self.create_error_result(signal, &format!("TX failed: {}", rpc_e), attempt, None));
-                        }
-                    }
-                }
-            };
+        match venue.quote_buy(&signal.token_mint, amount_lamports, self.config.slippage_bps).await? {
+            Some(out_amount) => {
+                let decimals = self.token_decimals(venue, &signal.token_mint).await?;
+                Ok((model.buy_from_quote(trade_amount, network_fee_sol, out_amount, sol_usd, decimals), decimals))
+            }
+            None => {
+                let price = signal.entry_price_usd
+                    .filter(|p| *p > 0.0)
+                    .ok_or_else(|| anyhow!("no signal price to fill at"))?;
+                let decimals = self.token_decimals(venue, &signal.token_mint).await?;
+                Ok((model.buy_at_price(trade_amount, network_fee_sol, price, sol_usd, decimals), decimals))
+            }
+        }
+    }
 
-            let elapsed = start.elapsed();
+    /// Paper sell: venue quote or last observed price, simulated fill
+    async fn execute_sell_paper(&self, position: &Position, reason: ExitReason) -> Result<TradeResult> {
+        let start = std::time::Instant::now();
+        let venue = self.venue(position.is_pumpfun);
+        let order = OrderContext { side: "sell", venue: venue.name(), token_mint: &position.token_mint, token_symbol: &position.token_symbol };
 
-            // Use signal price as entry (we don't have exact quote from pump.fun)
-            let entry_price = signal.entry_price_usd.unwrap_or(0.0);
+        let sol_usd = *self.sol_price_usd.read().await;
+        let network_fee_sol = (BASE_FEE_LAMPORTS + self.config.jito_tip_sell_lamports) as f64 / 1e9;
 
-            // Estimate tokens received (we'll update from on-chain later if needed)
-            // For now, estimate from market cap and SOL invested
-            let estimated_tokens = if entry_price > 0.0 {
-                ((self.config.trade_amount_sol * 200.0) / entry_price) as u64 // Rough SOL price estimate
-            } else {
-                0
-            };
+        let fill = match self.paper_sell_fill(venue, position, network_fee_sol, sol_usd).await {
+            Ok(fill) => fill,
+            Err(e) => {
+                error!("❌ [Paper] Sell quote failed for {}: {}", position.token_symbol, e);
+                let error = format!("Paper quote failed: {}", e);
+                self.journal.record_attempt(&order, 1, start.elapsed().as_millis() as u64, None, Some(&error));
+                return Ok(TradeResult {
+                    error: Some(error),
+                    error_code: SpectreError::code_of(&e),
+                    ..self.create_sell_result(position, 1, start)
+                });
+            }
+        };
 
-            // Create position for SL/TP monitoring (mark as pump.fun position)
-            // Pass signal_type for NINJA scaled exits
-            let position = Position::new_with_signal_type(
-                token_mint.clone(),
-                token_symbol.clone(),
-                entry_price,
-                estimated_tokens,
-                self.config.trade_amount_sol,
-                signal.stop_loss_percent,
-                signal.take_profit_percent,
-                tx_sig.clone(),
-                true, // is_pumpfun = true
-                signal.signal_type.clone(),
-            );
-            self.position_manager.add_position(position).await;
+        self.journal.record_attempt(&order, 1, start.elapsed().as_millis() as u64, None, None);
 
-            info!(
-                "✅ PUMP.FUN BUY executed (attempt {}): ~{} tokens for {} SOL (took: {:?})",
-                attempt,
-                estimated_tokens,
-                self.config.trade_amount_sol,
-                elapsed
-            );
+        info!(
+            "📝 PAPER SELL ({}) ({}): {} tokens of {} for {:.4} SOL @ ${:.10}",
+            venue.name(), reason, fill.amount_tokens, position.token_symbol, fill.amount_sol, fill.price_usd
+        );
 
-            return Ok(TradeResult {
-                success: true,
-                token_mint: token_mint.clone(),
-                token_symbol: token_symbol.clone(),
-                action: "buy".to_string(),
-                amount_sol: self.config.trade_amount_sol,
-                amount_tokens: Some(estimated_tokens as f64),
-                price_per_token: Some(entry_price),
-                tx_signature: Some(tx_sig),
-                error: None,
-                latency_ms: elapsed.as_millis() as u64,
-                timestamp: chrono::Utc::now().to_rfc3339(),
-                signal_type: Some(signal.signal_type.clone()),
-                signal_strength: Some(signal.strength.clone()),
-                market_cap_usd: signal.market_cap_usd,
-                liquidity_usd: signal.liquidity_usd,
-                entry_price_usd: signal.entry_price_usd,
-                stop_loss_percent: Some(signal.stop_loss_percent),
-                take_profit_percent: Some(signal.take_profit_percent),
-                trigger_wallets: Some(signal.wallets.clone()),
-                attempt_number: attempt,
-                price_at_signal: signal.entry_price_usd,
-                price_at_trade: signal.entry_price_usd,
-                price_change_percent: Some(0.0),
-                signal_timestamp: Some(signal.timestamp.clone()),
-            });
-        }
+        Ok(TradeResult {
+            success: true,
+            amount_sol: fill.amount_sol,
+            price_per_token: Some(fill.price_usd),
+            ..self.create_sell_result(position, 1, start)
+        })
+    }
 
-        Ok(self.create_error_result(signal, "Max attempts exhausted", MAX_ATTEMPTS, None))
+    /// Simulated sell from the venue's quote, or the last observed price if it has none
+    async fn paper_sell_fill(&self, venue: &dyn SwapVenue, position: &Position, network_fee_sol: f64, sol_usd: f64) -> Result<SimulatedFill> {
+        let decimals = match position.token_decimals {
+            Some(decimals) => decimals,
+            None => self.token_decimals(venue, &position.token_mint).await?,
+        };
+        let model = self.config.fill_model();
+
+        match venue.quote_sell(&position.token_mint, position.amount_tokens, self.config.slippage_bps).await? {
+            Some(out_lamports) => Ok(model.sell_from_quote(position.amount_tokens, decimals, out_lamports, sol_usd, network_fee_sol)),
+            None => match position.last_price {
+                price if price > 0.0 => Ok(model.sell_at_price(position.amount_tokens, decimals, price, sol_usd, network_fee_sol)),
+                _ => Err(anyhow!("no observed price to fill at")),
+            },
+        }
     }
 
-    /// Execute buy via Jupiter (for CONSENSUS signals - graduated tokens)
-    async fn execute_buy_jupiter(&self, signal: &SpectreSignal) -> Result<TradeResult> {
-        const MAX_ATTEMPTS: u32 = 2;
-        const MAX_PRICE_CHANGE_PERCENT: f64 = 30.0;
+    /// Execute a buy on the signal's venue
+    /// Uses prepared TX from cache if available (Fast Confirm optimization)
+    async fn execute_buy_live(&self, signal: &SpectreSignal, is_pumpfun: bool) -> Result<TradeResult> {
+        let venue = self.venue(is_pumpfun);
+        let policy = venue.retry_policy(Side::Buy);
+        let retry_delay = tokio::time::Duration::from_millis(policy.retry_delay_ms);
 
         let token_mint = &signal.token_mint;
         let token_symbol = &signal.token_symbol;
         let signal_price = signal.entry_price_usd;
+        let trade_amount = self.buy_size(signal).amount_sol;
+        let order = OrderContext { side: "buy", venue: venue.name(), token_mint, token_symbol };
+
+        // Use dynamic priority fee from signal, or fall back to config default
+        let priority_fee = signal.priority_fee_lamports.unwrap_or(self.config.jito_tip_lamports);
+        info!(
+            "💰 Priority fee: {} lamports ({:.6} SOL) {}",
+            priority_fee,
+            priority_fee as f64 / 1e9,
+            if signal.priority_fee_lamports.is_some() { "(dynamic)" } else { "(config default)" }
+        );
+
+        let buy_order = BuyOrder {
+            wallet: self.config.wallet_pubkey(),
+            token_mint: token_mint.clone(),
+            amount_sol: trade_amount,
+            slippage_bps: self.config.slippage_bps,
+            priority_fee_lamports: priority_fee,
+        };
 
-        // Try up to MAX_ATTEMPTS times
-        for attempt in 1..=MAX_ATTEMPTS {
+        // ⚡ FAST CONFIRM: Check if we have a prepared TX from pre-signal (for this venue and size)
+        // Removed from cache right away - it is only tried once, regardless of success
+        let mut prepared_tx = self.prepared_tx_cache
+            .get(token_mint)
+            .await
+            .filter(|p| p.venue == venue.name());
+        match prepared_tx {
+            Some(ref prepared) if prepared.amount_sol != trade_amount => {
+                // The signal sizes differently than the pre-signal did (more wallets, strength unknown back then)
+                info!(
+                    "⚡ Prepared TX for {} was sized {} SOL, signal sizes {} SOL - building a new one",
+                    token_symbol, prepared.amount_sol, trade_amount
+                );
+                prepared_tx = None;
+                self.prepared_tx_cache.remove(token_mint).await;
+            }
+            Some(_) => {
+                info!("⚡ Using PREPARED TX for {} (Fast Confirm)", token_symbol);
+                self.prepared_tx_cache.remove(token_mint).await;
+            }
+            None => {}
+        }
+
+        // Tokens of this mint already in the wallet (e.g. dust reconciliation flagged) aren't part of the fill
+        let mut balance_before: Option<u64> = None;
+
+        for attempt in 1..=policy.max_attempts {
             let start = std::time::Instant::now();
 
-            // Convert SOL to lamports
-            let amount_lamports = (self.config.trade_amount_sol * 1e9) as u64;
+            // 1. Get transaction (prepared on first attempt, may have old priority fee but faster)
+            //    and a fresh blockhash in parallel for lower latency (the pre-buy balance too, once)
+            let build = async {
+                match prepared_tx.take() {
+                    Some(prepared) => Ok(SwapTx { transaction: prepared.transaction, quoted_out: None }),
+                    None => venue.build_buy(&buy_order).await,
+                }
+            };
+            let read_balance = async {
+                match balance_before {
+                    Some(balance) => balance,
+                    None => self.get_token_holding(token_mint).await.ok().flatten().map_or(0, |h| h.amount),
+                }
+            };
+            let (built, blockhash, balance) = tokio::join!(build, self.fresh_blockhash(venue), read_balance);
+            balance_before = Some(balance);
 
-            // 1. Get quote from Jupiter
-            let quote = match self.jupiter.get_quote(
-                token_mint,
-                amount_lamports,
-                self.config.slippage_bps,
-            ).await {
-                Ok(q) => q,
+            let swap = match built {
+                Ok(swap) => swap,
                 Err(e) => {
-                    error!("❌ [Attempt {}/{}] Failed to get Jupiter quote: {}", attempt, MAX_ATTEMPTS, e);
-                    if attempt < MAX_ATTEMPTS {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                    error!("❌ [Attempt {}/{}] {} buy failed: {}", attempt, policy.max_attempts, venue.label(), e);
+                    self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, None, Some(&e.to_string()));
+                    if attempt < policy.max_attempts && SpectreError::is_retryable(&e) {
+                        tokio::time::sleep(retry_delay).await;
                         continue;
                     }
-                    return Ok(self.create_error_result(signal, &format!("Quote failed: {}", e), attempt, None));
+                    return Ok(TradeResult {
+                        error_code: SpectreError::code_of(&e),
+                        ..self.create_error_result(signal, &format!("Swap TX failed: {}", e), attempt, None)
+                    });
                 }
             };
 
-            let out_amount: u64 = quote.out_amount.parse().unwrap_or(0);
-
             // Calculate current price from quote (SOL per token)
-            let current_price = if out_amount > 0 {
-                Some(self.config.trade_amount_sol / (out_amount as f64))
-            } else {
-                None
-            };
+            let quoted_out = swap.quoted_out;
+            let current_price = quoted_out
+                .filter(|out| *out > 0)
+                .map(|out| trade_amount / (out as f64));
 
             // Check price change from signal (if we have both prices)
             let price_change_percent = match (signal_price, current_price) {
                 (Some(signal_p), Some(current_p)) if signal_p > 0.0 => {
-                    let change = ((current_p - signal_p) / signal_p) * 100.0;
-                    Some(change)
+                    Some(((current_p - signal_p) / signal_p) * 100.0)
                 }
                 _ => None
             };
@@ -399,177 +649,220 @@ impl SpectreTrader {
                         change, MAX_PRICE_CHANGE_PERCENT, token_symbol
                     );
                     return Ok(TradeResult {
-                        success: false,
-                        token_mint: token_mint.clone(),
-                        token_symbol: token_symbol.clone(),
-                        action: "buy".to_string(),
-                        amount_sol: self.config.trade_amount_sol,
-                        amount_tokens: None,
                         price_per_token: current_price,
-                        tx_signature: None,
-                        error: Some(format!("Price jumped {:.1}% > {}% max", change, MAX_PRICE_CHANGE_PERCENT)),
                         latency_ms: start.elapsed().as_millis() as u64,
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                        signal_type: Some(signal.signal_type.clone()),
-                        signal_strength: Some(signal.strength.clone()),
-                        market_cap_usd: signal.market_cap_usd,
-                        liquidity_usd: signal.liquidity_usd,
-                        entry_price_usd: signal.entry_price_usd,
-                        stop_loss_percent: Some(signal.stop_loss_percent),
-                        take_profit_percent: Some(signal.take_profit_percent),
-                        trigger_wallets: Some(signal.wallets.clone()),
-                        attempt_number: attempt,
-                        price_at_signal: signal_price,
-                        price_at_trade: current_price,
                         price_change_percent,
-                        signal_timestamp: Some(signal.timestamp.clone()),
+                        ..self.create_error_result(
+                            signal,
+                            &format!("Price jumped {:.1}% > {}% max", change, MAX_PRICE_CHANGE_PERCENT),
+                            attempt,
+                            current_price,
+                        )
                     });
                 }
             }
 
-            // 2. Get swap transaction AND blockhash in parallel for lower latency
-            let rpc_client = self.rpc_client.clone();
-            let wallet_pubkey = self.config.wallet_pubkey();
-            let jito_tip = self.config.jito_tip_lamports;
-
-            let blockhash_future = rpc_client.get_latest_blockhash();
-            let swap_tx_future = self.jupiter.get_swap_transaction(
-                quote,
-                &wallet_pubkey,
-                jito_tip,
-            );
-
-            let (blockhash_result, swap_tx_result) = tokio::join!(blockhash_future, swap_tx_future);
-
-            let recent_blockhash = match blockhash_result {
+            let recent_blockhash = match blockhash {
                 Ok(bh) => bh,
                 Err(e) => {
-                    error!("❌ [Attempt {}/{}] Failed to get blockhash: {}", attempt, MAX_ATTEMPTS, e);
-                    if attempt < MAX_ATTEMPTS {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                    error!("❌ [Attempt {}/{}] Failed to get blockhash: {}", attempt, policy.max_attempts, e);
+                    self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, None, Some(&e.to_string()));
+                    if attempt < policy.max_attempts {
+                        tokio::time::sleep(retry_delay).await;
                         continue;
                     }
-                    return Ok(self.create_error_result(signal, &format!("Blockhash failed: {}", e), attempt, current_price));
+                    return Ok(TradeResult {
+                        error_code: SpectreError::code_of(&e),
+                        ..self.create_error_result(signal, &format!("Blockhash failed: {}", e), attempt, current_price)
+                    });
                 }
             };
 
-            let (transaction, _last_valid_block) = match swap_tx_result {
+            // 2. Sign
+            let signed_tx = match sign_transaction(swap.transaction, &self.config.wallet, recent_blockhash) {
                 Ok(tx) => tx,
                 Err(e) => {
-                    error!("❌ [Attempt {}/{}] Failed to get swap transaction: {}", attempt, MAX_ATTEMPTS, e);
-                    if attempt < MAX_ATTEMPTS {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                    error!("❌ [Attempt {}/{}] {} buy TX rejected: {}", attempt, policy.max_attempts, venue.label(), e);
+                    self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, None, Some(&e.to_string()));
+                    return Ok(TradeResult {
+                        error_code: Some(e.code()),
+                        ..self.create_error_result(signal, &e.to_string(), attempt, current_price)
+                    });
+                }
+            };
+
+            // 3. Send (Jito bundle for MEV protection) and wait until it lands
+            let signal_to_submit_ms = signal.age_ms();
+            if let Some(ms) = signal_to_submit_ms {
+                info!("⏱️ Signal -> submit: {}ms ({})", ms, token_symbol);
+            }
+            let submission = match self.submitter.submit_and_confirm(&signed_tx).await {
+                Ok(submission) => submission,
+                Err(e) => {
+                    error!("❌ [Attempt {}/{}] TX submission failed: {}", attempt, policy.max_attempts, e);
+                    self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, None, Some(&e.to_string()));
+                    if attempt < policy.max_attempts && SpectreError::is_retryable(&e) {
+                        tokio::time::sleep(retry_delay).await;
                         continue;
                     }
-                    return Ok(self.create_error_result(signal, &format!("Swap tx failed: {}", e), attempt, current_price));
+                    return Ok(TradeResult {
+                        error_code: SpectreError::code_of(&e),
+                        signal_to_submit_ms,
+                        ..self.create_error_result(signal, &format!("TX failed: {}", e), attempt, current_price)
+                    });
                 }
             };
 
-            let signed_tx = self.sign_versioned_transaction(transaction, recent_blockhash)?;
+            // Never create a position for a buy that didn't land
+            if let Some(tx_error) = submission.outcome.error() {
+                error!("❌ [Attempt {}/{}] Buy TX {} not confirmed: {}", attempt, policy.max_attempts, submission.signature, submission.outcome);
+                self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, Some(&submission.signature), Some(&submission.outcome.to_string()));
+                if attempt < policy.max_attempts && tx_error.code().is_retryable() {
+                    tokio::time::sleep(retry_delay).await;
+                    continue;
+                }
+                return Ok(TradeResult {
+                    tx_signature: Some(submission.signature.clone()),
+                    bundle_id: submission.bundle_id.clone(),
+                    error_code: Some(tx_error.code()),
+                    signal_to_submit_ms,
+                    ..self.create_error_result(signal, &format!("TX {}", submission.outcome), attempt, current_price)
+                });
+            }
 
-            // 4. Send via Jito bundle for MEV protection
-            let bundle_id = match self.jito.send_bundle(&signed_tx).await {
-                Ok(id) => id,
-                Err(e) => {
-                    warn!("⚠️ [Attempt {}/{}] Jito bundle failed, falling back to RPC: {}", attempt, MAX_ATTEMPTS, e);
-                    // Fallback to direct RPC submission
-                    match self.rpc_client.send_and_confirm_transaction(&signed_tx).await {
-                        Ok(sig) => sig.to_string(),
+            let tx_sig = submission.signature.clone();
+            let elapsed = submission.submit_latency;
+            self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, Some(&submission.signature), None);
+
+            // Read what we actually received (a quote is only an upper bound, pump.fun gives none)
+            let fill = self.wait_for_token_fill(token_mint, balance).await;
+
+            let (entry_price, tokens_received) = match fill {
+                Some(ref holding) => (
+                    self.effective_entry_price(trade_amount, holding).await,
+                    holding.amount,
+                ),
+                None => {
+                    // Fill not visible yet - entry at the signal price, else the quote's (SOL per raw unit
+                    // converted to USD per whole token); amount from the quote (reconciliation fixes it later)
+                    let decimals = match self.token_decimals(venue, token_mint).await {
+                        Ok(decimals) => Some(decimals),
                         Err(e) => {
-                            error!("❌ [Attempt {}/{}] Transaction failed: {}", attempt, MAX_ATTEMPTS, e);
-                            if attempt < MAX_ATTEMPTS {
-                                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                                continue;
-                            }
-                            return Ok(self.create_error_result(signal, &format!("TX failed: {}", e), attempt, current_price));
+                            warn!("⚠️ Failed to read decimals of {}: {}", token_symbol, e);
+                            None
                         }
-                    }
+                    };
+                    let entry_price = match signal_price.filter(|p| *p > 0.0) {
+                        Some(price) => Some(price),
+                        None => self.quote_price_usd(current_price, decimals).await,
+                    };
+                    let estimated_tokens = match quoted_out {
+                        Some(out) => out,
+                        None => self.estimate_tokens(trade_amount, signal_price, decimals).await,
+                    };
+                    // No price at all - price exits wait for the first observed price (see needs_price_sync)
+                    let entry_price = entry_price.filter(|p| *p > 0.0).unwrap_or_else(|| {
+                        warn!("⚠️ No entry price for {} - SL/TP armed on the first price update", token_symbol);
+                        0.0
+                    });
+                    (entry_price, estimated_tokens)
                 }
             };
 
-            let elapsed = start.elapsed();
-
-            // Use actual trade price (from quote), not signal price
-            // current_price = SOL amount / tokens received
-            let actual_entry_price = current_price.unwrap_or_else(|| {
-                // Fallback to signal price if we couldn't calculate
-                signal.entry_price_usd.unwrap_or(0.0)
-            });
-
-            // 5. Create position for SL/TP monitoring (Jupiter = not pump.fun)
-            // Pass signal_type for NINJA scaled exits
-            let position = Position::new_with_signal_type(
+            // Create position for SL/TP monitoring
+            // Exit strategy (scaled TP ladder) is resolved from signal type + strength
+            let mut position = Position::new_with_signal_type(
                 token_mint.clone(),
                 token_symbol.clone(),
-                actual_entry_price,  // Use actual trade price!
-                out_amount,
-                self.config.trade_amount_sol,
+                entry_price,
+                tokens_received,
+                trade_amount,
                 signal.stop_loss_percent,
                 signal.take_profit_percent,
-                bundle_id.clone(),
-                false, // is_pumpfun = false (Jupiter)
+                tx_sig.clone(),
+                is_pumpfun,
                 signal.signal_type.clone(),
-            );
+            )
+            .with_trigger_wallets(signal.wallets.iter().map(|w| w.address.clone()).collect());
+            if let Some(strategy) = self.config.exit_strategies.resolve(&signal.signal_type, &signal.strength) {
+                position = position.with_exit_strategy(strategy);
+            }
+            if let Some(ref holding) = fill {
+                position.apply_fill(holding.amount, holding.decimals, entry_price);
+            }
+            let sol_spent = self.wallet_sol_change(&tx_sig).await
+                .map(|change| -change)
+                .unwrap_or(trade_amount);
+            self.journal.record_entry(&position, sol_spent);
             self.position_manager.add_position(position).await;
 
             info!(
-                "✅ BUY executed (attempt {}): {} tokens for {} SOL (took: {:?})",
+                "✅ {} BUY executed (attempt {}): {}{} tokens for {} SOL @ ${:.10} (took: {:?})",
+                venue.label(),
                 attempt,
-                out_amount,
-                self.config.trade_amount_sol,
+                if fill.is_some() { "" } else { "~" },
+                tokens_received,
+                trade_amount,
+                entry_price,
                 elapsed
             );
 
+            // Venues without a quote trade at the signal price as far as we know
+            let (price_at_trade, price_change_percent) = match current_price {
+                Some(_) => (current_price, price_change_percent),
+                None => (signal_price, Some(0.0)),
+            };
+
             return Ok(TradeResult {
                 success: true,
-                token_mint: token_mint.clone(),
-                token_symbol: token_symbol.clone(),
-                action: "buy".to_string(),
-                amount_sol: self.config.trade_amount_sol,
-                amount_tokens: Some(out_amount as f64),
-                price_per_token: Some(actual_entry_price),
-                tx_signature: Some(bundle_id),
+                amount_tokens: Some(tokens_received as f64),
+                price_per_token: Some(entry_price).filter(|p| *p > 0.0),
+                tx_signature: Some(tx_sig),
+                bundle_id: submission.bundle_id,
                 error: None,
                 latency_ms: elapsed.as_millis() as u64,
-                timestamp: chrono::Utc::now().to_rfc3339(),
-                signal_type: Some(signal.signal_type.clone()),
-                signal_strength: Some(signal.strength.clone()),
-                market_cap_usd: signal.market_cap_usd,
-                liquidity_usd: signal.liquidity_usd,
-                entry_price_usd: signal.entry_price_usd,
-                stop_loss_percent: Some(signal.stop_loss_percent),
-                take_profit_percent: Some(signal.take_profit_percent),
-                trigger_wallets: Some(signal.wallets.clone()),
-                attempt_number: attempt,
-                price_at_signal: signal_price,
-                price_at_trade: current_price,
+                price_at_trade,
                 price_change_percent,
-                signal_timestamp: Some(signal.timestamp.clone()),
+                signal_to_submit_ms,
+                ..self.create_error_result(signal, "", attempt, None) // signal context
             });
         }
 
-        // Should never reach here, but just in case
-        Ok(self.create_error_result(signal, "Max attempts exhausted", MAX_ATTEMPTS, None))
+        Ok(self.create_error_result(signal, "Max attempts exhausted", policy.max_attempts, None))
     }
 
-    /// Helper to get fresh TX from PumpPortal (used when no prepared TX or on retry)
-    /// Uses config default priority fee - for dynamic fee, use get_fresh_pumpfun_tx_with_fee
-    async fn get_fresh_pumpfun_tx(&self, token_mint: &str) -> Result<Vec<u8>> {
-        self.get_fresh_pumpfun_tx_with_fee(token_mint, self.config.jito_tip_lamports).await
+    /// Blockhash to sign with, for venues that want a fresh one
+    async fn fresh_blockhash(&self, venue: &dyn SwapVenue) -> Result<Option<solana_sdk::hash::Hash>> {
+        if !venue.needs_fresh_blockhash() {
+            return Ok(None);
+        }
+        self.submitter.latest_blockhash().await.map(Some)
     }
 
-    /// Helper to get fresh TX from PumpPortal with custom priority fee
-    async fn get_fresh_pumpfun_tx_with_fee(&self, token_mint: &str, priority_fee_lamports: u64) -> Result<Vec<u8>> {
-        let slippage_percent = (self.config.slippage_bps / 100) as u16;
-        let priority_fee_sol = priority_fee_lamports as f64 / 1e9;
+    /// Decimals of a mint: fixed by the venue, or read on-chain
+    async fn token_decimals(&self, venue: &dyn SwapVenue, token_mint: &str) -> Result<u8> {
+        match venue.token_decimals() {
+            Some(decimals) => Ok(decimals),
+            None => self.wallet.mint_decimals(token_mint).await,
+        }
+    }
 
-        self.pumpfun.get_buy_transaction(
-            &self.config.wallet_pubkey().to_string(),
-            token_mint,
-            self.config.trade_amount_sol,
-            slippage_percent,
-            priority_fee_sol,
-        ).await
+    /// Raw tokens a buy of `trade_amount` SOL gets at the signal price (0 if unknown)
+    async fn estimate_tokens(&self, trade_amount: f64, signal_price: Option<f64>, decimals: Option<u8>) -> u64 {
+        match (signal_price.filter(|p| *p > 0.0), decimals) {
+            (Some(price), Some(decimals)) => {
+                let sol_usd = *self.sol_price_usd.read().await;
+                ((trade_amount * sol_usd) / price * 10f64.powi(decimals as i32)) as u64
+            }
+            _ => 0,
+        }
+    }
+
+    /// Quote price (SOL per raw token unit) as USD per whole token, when the venue knows the decimals
+    async fn quote_price_usd(&self, sol_per_unit: Option<f64>, decimals: Option<u8>) -> Option<f64> {
+        let (price, decimals) = (sol_per_unit?, decimals?);
+        let sol_usd = *self.sol_price_usd.read().await;
+        Some(price * 10f64.powi(decimals as i32) * sol_usd)
     }
 
     /// Helper to create error TradeResult with all signal context
@@ -579,11 +872,13 @@ impl SpectreTrader {
             token_mint: signal.token_mint.clone(),
             token_symbol: signal.token_symbol.clone(),
             action: "buy".to_string(),
-            amount_sol: self.config.trade_amount_sol,
+            amount_sol: self.buy_size(signal).amount_sol,
             amount_tokens: None,
             price_per_token: None,
             tx_signature: None,
+            bundle_id: None,
             error: Some(error.to_string()),
+            error_code: None,
             latency_ms: 0,
             timestamp: chrono::Utc::now().to_rfc3339(),
             signal_type: Some(signal.signal_type.clone()),
@@ -599,12 +894,17 @@ impl SpectreTrader {
             price_at_trade: current_price,
             price_change_percent: None,
             signal_timestamp: Some(signal.timestamp.clone()),
+            signal_age_ms: None,
+            signal_to_submit_ms: None,
+            exit_reason: None,
+            paper: self.config.paper_mode,
         }
     }
 
     /// Execute sell order (SL/TP triggered) with retry logic
     /// Routes to pump.fun or Jupiter based on how position was opened
     /// For ScaledTakeProfit, only sells partial position
+    /// The position is only advanced/removed once the sell is confirmed on-chain
     pub async fn execute_sell(&self, token_mint: &str, reason: ExitReason) -> Result<TradeResult> {
         let position = match self.position_manager.get_position(token_mint).await {
             Some(p) => p,
@@ -613,25 +913,18 @@ impl SpectreTrader {
             }
         };
 
-        // For scaled exits, calculate tokens to sell and update position
-        let (tokens_to_sell, should_remove_position) = match &reason {
-            ExitReason::ScaledTakeProfit { stage, sell_percent, .. } => {
-                // Advance position to next stage and get tokens to sell
-                match self.position_manager.advance_scaled_exit(token_mint, *stage, *sell_percent).await {
-                    Some((tokens, fully_closed)) => (tokens, fully_closed),
-                    None => return Err(anyhow!("Failed to advance scaled exit for {}", token_mint)),
-                }
-            }
-            _ => {
-                // Full exit - sell all tokens
-                (position.amount_tokens, true)
-            }
+        // For partial exits, calculate tokens to sell (position is updated after confirmation)
+        let (tokens_to_sell, should_remove_position) = if reason.is_partial() {
+            position.scaled_exit_amount(reason.sell_percent())
+        } else {
+            // Full exit - sell all tokens
+            (position.amount_tokens, true)
         };
 
         info!(
             "🔴 Executing {} SELL via {} ({}): {} - {} of {} tokens",
             if reason.is_partial() { "PARTIAL" } else { "FULL" },
-            if position.is_pumpfun { "PUMP.FUN" } else { "JUPITER" },
+            self.venue(position.is_pumpfun).label(),
             reason,
             position.token_symbol,
             tokens_to_sell,
@@ -644,497 +937,257 @@ impl SpectreTrader {
             ..position.clone()
         };
 
-        let result = if position.is_pumpfun {
-            self.execute_sell_pumpfun(token_mint, &sell_position, reason.clone(), should_remove_position).await
+        let result = if self.config.paper_mode {
+            self.execute_sell_paper(&sell_position, reason).await?
         } else {
-            self.execute_sell_jupiter(token_mint, &sell_position, reason.clone(), should_remove_position).await
+            self.execute_sell_live(&sell_position, reason, should_remove_position).await?
         };
 
-        result
-    }
-
-    /// Execute sell via pump.fun bonding curve
-    async fn execute_sell_pumpfun(&self, token_mint: &str, position: &Position, reason: ExitReason, should_remove_position: bool) -> Result<TradeResult> {
-        const MAX_SELL_ATTEMPTS: u32 = 3;
-        const RETRY_DELAY_MS: u64 = 500;
-
-        for attempt in 1..=MAX_SELL_ATTEMPTS {
-            let start = std::time::Instant::now();
+        let mut result = TradeResult {
+            exit_reason: Some(reason.code().to_string()),
+            ..result
+        };
 
-            // Increase slippage on retries
-            let slippage_percent = (self.config.slippage_bps / 100) as u16 + ((attempt - 1) * 5) as u16;
-            let priority_fee_sol = self.config.jito_tip_sell_lamports as f64 / 1e9;
+        if !result.success {
+            return Ok(result);
+        }
 
-            // 1. Get sell transaction from PumpPortal
-            let tx_bytes = match self.pumpfun.get_sell_transaction(
-                &self.config.wallet_pubkey().to_string(),
-                token_mint,
-                position.amount_tokens,
-                slippage_percent,
-                priority_fee_sol,
-            ).await {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    error!("❌ [Sell Attempt {}/{}] PumpPortal sell failed: {}", attempt, MAX_SELL_ATTEMPTS, e);
-                    if attempt < MAX_SELL_ATTEMPTS {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(RETRY_DELAY_MS)).await;
-                        continue;
-                    }
-                    return Ok(TradeResult {
-                        success: false,
-                        token_mint: token_mint.to_string(),
-                        token_symbol: position.token_symbol.clone(),
-                        action: "sell".to_string(),
-                        amount_sol: 0.0,
-                        amount_tokens: Some(position.amount_tokens as f64),
-                        price_per_token: None,
-                        tx_signature: None,
-                        error: Some(format!("PumpPortal sell failed: {}", e)),
-                        latency_ms: start.elapsed().as_millis() as u64,
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                        signal_type: None,
-                        signal_strength: None,
-                        market_cap_usd: None,
-                        liquidity_usd: None,
-                        entry_price_usd: Some(position.entry_price),
-                        stop_loss_percent: Some(position.stop_loss_percent),
-                        take_profit_percent: Some(position.take_profit_percent),
-                        trigger_wallets: None,
-                        attempt_number: attempt,
-                        price_at_signal: None,
-                        price_at_trade: None,
-                        price_change_percent: None,
-                        signal_timestamp: None,
-                    });
-                }
-            };
+        // SOL actually received (net of fees), instead of the quote/estimate
+        let tx_signature = result.tx_signature.clone().unwrap_or_default();
+        if let Some(sol_received) = self.wallet_sol_change(&tx_signature).await {
+            result.amount_sol = sol_received;
+        }
 
-            // 2. Sign transaction
-            let signed_tx = match self.pumpfun.sign_transaction(&tx_bytes, &self.config.wallet) {
-                Ok(tx) => tx,
-                Err(e) => {
-                    error!("❌ [Sell Attempt {}/{}] Failed to sign TX: {}", attempt, MAX_SELL_ATTEMPTS, e);
-                    if attempt < MAX_SELL_ATTEMPTS {
-                        continue;
+        // Commit the exit only after the sell landed
+        let closes_position = match reason {
+            ExitReason::ScaledTakeProfit { stage, sell_percent, .. } => {
+                match self.position_manager.advance_scaled_exit(token_mint, stage, sell_percent).await {
+                    Some((_, true)) => {
+                        self.position_manager.remove_position(token_mint).await;
+                        true
                     }
-                    return Ok(TradeResult {
-                        success: false,
-                        token_mint: token_mint.to_string(),
-                        token_symbol: position.token_symbol.clone(),
-                        action: "sell".to_string(),
-                        amount_sol: 0.0,
-                        amount_tokens: Some(position.amount_tokens as f64),
-                        price_per_token: None,
-                        tx_signature: None,
-                        error: Some(format!("Sign failed: {}", e)),
-                        latency_ms: start.elapsed().as_millis() as u64,
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                        signal_type: None,
-                        signal_strength: None,
-                        market_cap_usd: None,
-                        liquidity_usd: None,
-                        entry_price_usd: Some(position.entry_price),
-                        stop_loss_percent: Some(position.stop_loss_percent),
-                        take_profit_percent: Some(position.take_profit_percent),
-                        trigger_wallets: None,
-                        attempt_number: attempt,
-                        price_at_signal: None,
-                        price_at_trade: None,
-                        price_change_percent: None,
-                        signal_timestamp: None,
-                    });
-                }
-            };
-
-            // 3. Send via Jito bundle
-            let tx_sig = match self.jito.send_bundle(&signed_tx).await {
-                Ok(id) => id,
-                Err(e) => {
-                    warn!("⚠️ [Sell Attempt {}/{}] Jito failed, trying RPC: {}", attempt, MAX_SELL_ATTEMPTS, e);
-                    match self.rpc_client.send_and_confirm_transaction(&signed_tx).await {
-                        Ok(sig) => sig.to_string(),
-                        Err(rpc_e) => {
-                            error!("❌ [Sell Attempt {}/{}] RPC also failed: {}", attempt, MAX_SELL_ATTEMPTS, rpc_e);
-                            if attempt < MAX_SELL_ATTEMPTS {
-                                tokio::time::sleep(tokio::time::Duration::from_millis(RETRY_DELAY_MS)).await;
-                                continue;
-                            }
-                            return Ok(TradeResult {
-                                success: false,
-                                token_mint: token_mint.to_string(),
-                                token_symbol: position.token_symbol.clone(),
-                                action: "sell".to_string(),
-                                amount_sol: 0.0,
-                                amount_tokens: Some(position.amount_tokens as f64),
-                                price_per_token: None,
-                                tx_signature: None,
-                                error: Some(format!("TX failed: {}", rpc_e)),
-                                latency_ms: start.elapsed().as_millis() as u64,
-                                timestamp: chrono::Utc::now().to_rfc3339(),
-                                signal_type: None,
-                                signal_strength: None,
-                                market_cap_usd: None,
-                                liquidity_usd: None,
-                                entry_price_usd: Some(position.entry_price),
-                                stop_loss_percent: Some(position.stop_loss_percent),
-                                take_profit_percent: Some(position.take_profit_percent),
-                                trigger_wallets: None,
-                                attempt_number: attempt,
-                                price_at_signal: None,
-                                price_at_trade: None,
-                                price_change_percent: None,
-                                signal_timestamp: None,
-                            });
-                        }
+                    Some((_, false)) => {
+                        self.resync_remainder(token_mint).await;
+                        false
                     }
+                    None => false,
                 }
-            };
-
-            // SUCCESS! Only remove position if it's a full exit
-            if should_remove_position {
+            }
+            // Partial exit outside the ladder (manual sell of a percentage)
+            _ if !should_remove_position => {
+                self.position_manager.update_tokens_after_sell(token_mint, tokens_to_sell).await;
+                self.resync_remainder(token_mint).await;
+                false
+            }
+            _ => {
                 self.position_manager.remove_position(token_mint).await;
+                true
             }
+        };
 
-            let elapsed = start.elapsed();
-            info!(
-                "✅ PUMP.FUN {} SELL executed (attempt {}) ({}): {} tokens sold (took: {:?})",
-                if should_remove_position { "FULL" } else { "PARTIAL" },
-                attempt,
-                reason,
-                position.amount_tokens,
-                elapsed
-            );
+        self.journal.record_exit(&position, &ExitFill {
+            reason: &reason,
+            tokens_sold: tokens_to_sell,
+            sol_received: result.amount_sol,
+            price_usd: result.price_per_token,
+            closes_position,
+            tx_signature: &tx_signature,
+        });
 
-            return Ok(TradeResult {
-                success: true,
-                token_mint: token_mint.to_string(),
-                token_symbol: position.token_symbol.clone(),
-                action: "sell".to_string(),
-                amount_sol: position.amount_sol_invested, // Approximate, we don't know exact return
-                amount_tokens: Some(position.amount_tokens as f64),
-                price_per_token: None,
-                tx_signature: Some(tx_sig),
-                error: None,
-                latency_ms: elapsed.as_millis() as u64,
-                timestamp: chrono::Utc::now().to_rfc3339(),
-                signal_type: None,
-                signal_strength: None,
-                market_cap_usd: None,
-                liquidity_usd: None,
-                entry_price_usd: Some(position.entry_price),
-                stop_loss_percent: Some(position.stop_loss_percent),
-                take_profit_percent: Some(position.take_profit_percent),
-                trigger_wallets: None,
-                attempt_number: attempt,
-                price_at_signal: None,
-                price_at_trade: None,
-                price_change_percent: None,
-                signal_timestamp: None,
-            });
-        }
-
-        Err(anyhow!("Pump.fun sell failed after max attempts"))
+        Ok(result)
     }
 
-    /// Execute sell via Jupiter (for graduated tokens)
-    async fn execute_sell_jupiter(&self, token_mint: &str, position: &Position, reason: ExitReason, should_remove_position: bool) -> Result<TradeResult> {
-        const MAX_SELL_ATTEMPTS: u32 = 5;
-        const RETRY_DELAY_MS: u64 = 1000;
+    /// Keep the remainder of a partially sold position in sync with what the wallet really holds
+    async fn resync_remainder(&self, token_mint: &str) {
+        if let Ok(Some(holding)) = self.get_token_holding(token_mint).await {
+            let tracked = self.position_manager.get_position(token_mint).await.map(|p| p.amount_tokens);
+            if tracked != Some(holding.amount) {
+                self.position_manager.set_amount_tokens(token_mint, holding.amount).await;
+            }
+        }
+    }
 
-        let mut last_error: Option<String> = None;
+    /// Execute a sell on the venue the position was opened on
+    async fn execute_sell_live(&self, position: &Position, reason: ExitReason, should_remove_position: bool) -> Result<TradeResult> {
+        let venue = self.venue(position.is_pumpfun);
+        let policy = venue.retry_policy(Side::Sell);
+        let retry_delay = tokio::time::Duration::from_millis(policy.retry_delay_ms);
+        let order = OrderContext { side: "sell", venue: venue.name(), token_mint: &position.token_mint, token_symbol: &position.token_symbol };
 
-        for attempt in 1..=MAX_SELL_ATTEMPTS {
+        for attempt in 1..=policy.max_attempts {
             let start = std::time::Instant::now();
 
-            // 1. Get sell quote with increasing slippage on retries
-            let extra_slippage = 500 + (attempt - 1) * 200; // Start at 5%, add 2% per retry
-            let quote = match self.jupiter.get_sell_quote(
-                token_mint,
-                position.amount_tokens,
-                self.config.slippage_bps + extra_slippage as u16,
-            ).await {
-                Ok(q) => q,
+            // A stop-loss (or other full exit) queued behind this partial one takes over
+            if attempt > 1 && reason.is_partial() && self.position_manager.is_exit_superseded(&position.token_mint).await {
+                let e = SpectreError::Superseded("a queued full exit".to_string());
+                warn!("⏫ {} {} abandoned before attempt {}: {}", position.token_symbol, reason, attempt, e);
+                return Ok(TradeResult {
+                    error: Some(e.to_string()),
+                    error_code: Some(e.code()),
+                    ..self.create_sell_result(position, attempt, start)
+                });
+            }
+
+            // 1. Get sell transaction (venues widen slippage on retries) and blockhash in parallel
+            let sell_order = SellOrder {
+                wallet: self.config.wallet_pubkey(),
+                token_mint: position.token_mint.clone(),
+                amount_tokens: position.amount_tokens,
+                decimals: position.token_decimals,
+                full_exit: should_remove_position,
+                sell_percent: reason.sell_percent(),
+                slippage_bps: self.config.slippage_bps,
+                // Lower priority fee for sells
+                priority_fee_lamports: self.config.jito_tip_sell_lamports,
+                attempt,
+            };
+            let (built, blockhash) = tokio::join!(venue.build_sell(&sell_order), self.fresh_blockhash(venue));
+
+            let swap = match built {
+                Ok(swap) => swap,
                 Err(e) => {
-                    error!("❌ [Sell Attempt {}/{}] Failed to get quote: {}", attempt, MAX_SELL_ATTEMPTS, e);
-                    last_error = Some(format!("Sell quote failed: {}", e));
-                    if attempt < MAX_SELL_ATTEMPTS {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(RETRY_DELAY_MS)).await;
+                    error!("❌ [Sell Attempt {}/{}] {} sell failed: {}", attempt, policy.max_attempts, venue.label(), e);
+                    self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, None, Some(&e.to_string()));
+                    if attempt < policy.max_attempts && SpectreError::is_retryable(&e) {
+                        tokio::time::sleep(retry_delay).await;
                         continue;
                     }
-                    // Final attempt failed
                     return Ok(TradeResult {
-                        success: false,
-                        token_mint: token_mint.to_string(),
-                        token_symbol: position.token_symbol.clone(),
-                        action: "sell".to_string(),
-                        amount_sol: 0.0,
-                        amount_tokens: Some(position.amount_tokens as f64),
-                        price_per_token: None,
-                        tx_signature: None,
-                        error: last_error,
-                        latency_ms: start.elapsed().as_millis() as u64,
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                        signal_type: None,
-                        signal_strength: None,
-                        market_cap_usd: None,
-                        liquidity_usd: None,
-                        entry_price_usd: Some(position.entry_price),
-                        stop_loss_percent: Some(position.stop_loss_percent),
-                        take_profit_percent: Some(position.take_profit_percent),
-                        trigger_wallets: None,
-                        attempt_number: attempt,
-                        price_at_signal: None,
-                        price_at_trade: None,
-                        price_change_percent: None,
-                        signal_timestamp: None,
+                        error: Some(format!("Swap TX failed: {}", e)),
+                        error_code: SpectreError::code_of(&e),
+                        ..self.create_sell_result(position, attempt, start)
                     });
                 }
             };
 
-            let out_lamports: u64 = quote.out_amount.parse().unwrap_or(0);
-            let out_sol = out_lamports as f64 / 1e9;
-
-            // 2. Get swap transaction (use lower tip for sells)
-            let (transaction, _) = match self.jupiter.get_swap_transaction(
-                quote,
-                &self.config.wallet_pubkey(),
-                self.config.jito_tip_sell_lamports,
-            ).await {
-                Ok(tx) => tx,
+            let recent_blockhash = match blockhash {
+                Ok(bh) => bh,
                 Err(e) => {
-                    error!("❌ [Sell Attempt {}/{}] Failed to get swap TX: {}", attempt, MAX_SELL_ATTEMPTS, e);
-                    last_error = Some(format!("Swap TX failed: {}", e));
-                    if attempt < MAX_SELL_ATTEMPTS {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(RETRY_DELAY_MS)).await;
+                    error!("❌ [Sell Attempt {}/{}] Failed to get blockhash: {}", attempt, policy.max_attempts, e);
+                    self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, None, Some(&e.to_string()));
+                    if attempt < policy.max_attempts {
+                        tokio::time::sleep(retry_delay).await;
                         continue;
                     }
                     return Ok(TradeResult {
-                        success: false,
-                        token_mint: token_mint.to_string(),
-                        token_symbol: position.token_symbol.clone(),
-                        action: "sell".to_string(),
-                        amount_sol: 0.0,
-                        amount_tokens: Some(position.amount_tokens as f64),
-                        price_per_token: None,
-                        tx_signature: None,
-                        error: last_error,
-                        latency_ms: start.elapsed().as_millis() as u64,
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                        signal_type: None,
-                        signal_strength: None,
-                        market_cap_usd: None,
-                        liquidity_usd: None,
-                        entry_price_usd: Some(position.entry_price),
-                        stop_loss_percent: Some(position.stop_loss_percent),
-                        take_profit_percent: Some(position.take_profit_percent),
-                        trigger_wallets: None,
-                        attempt_number: attempt,
-                        price_at_signal: None,
-                        price_at_trade: None,
-                        price_change_percent: None,
-                        signal_timestamp: None,
+                        error: Some(format!("Blockhash failed: {}", e)),
+                        error_code: SpectreError::code_of(&e),
+                        ..self.create_sell_result(position, attempt, start)
                     });
                 }
             };
 
-            // 3. Sign and send
-            let recent_blockhash = match self.rpc_client.get_latest_blockhash().await {
-                Ok(bh) => bh,
+            let quoted_sol = swap.quoted_out.map(|lamports| lamports as f64 / 1e9);
+
+            // 2. Sign, send and wait until it lands
+            let signed_tx = match sign_transaction(swap.transaction, &self.config.wallet, recent_blockhash) {
+                Ok(tx) => tx,
                 Err(e) => {
-                    error!("❌ [Sell Attempt {}/{}] Failed to get blockhash: {}", attempt, MAX_SELL_ATTEMPTS, e);
-                    last_error = Some(format!("Blockhash failed: {}", e));
-                    if attempt < MAX_SELL_ATTEMPTS {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(RETRY_DELAY_MS)).await;
-                        continue;
-                    }
+                    error!("❌ [Sell Attempt {}/{}] {} sell TX rejected: {}", attempt, policy.max_attempts, venue.label(), e);
+                    self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, None, Some(&e.to_string()));
                     return Ok(TradeResult {
-                        success: false,
-                        token_mint: token_mint.to_string(),
-                        token_symbol: position.token_symbol.clone(),
-                        action: "sell".to_string(),
-                        amount_sol: 0.0,
-                        amount_tokens: Some(position.amount_tokens as f64),
-                        price_per_token: None,
-                        tx_signature: None,
-                        error: last_error,
-                        latency_ms: start.elapsed().as_millis() as u64,
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                        signal_type: None,
-                        signal_strength: None,
-                        market_cap_usd: None,
-                        liquidity_usd: None,
-                        entry_price_usd: Some(position.entry_price),
-                        stop_loss_percent: Some(position.stop_loss_percent),
-                        take_profit_percent: Some(position.take_profit_percent),
-                        trigger_wallets: None,
-                        attempt_number: attempt,
-                        price_at_signal: None,
-                        price_at_trade: None,
-                        price_change_percent: None,
-                        signal_timestamp: None,
+                        error: Some(e.to_string()),
+                        error_code: Some(e.code()),
+                        ..self.create_sell_result(position, attempt, start)
                     });
                 }
             };
 
-            let signed_tx = match self.sign_versioned_transaction(transaction, recent_blockhash) {
-                Ok(tx) => tx,
+            let submission = match self.submitter.submit_and_confirm(&signed_tx).await {
+                Ok(submission) => submission,
                 Err(e) => {
-                    error!("❌ [Sell Attempt {}/{}] Failed to sign TX: {}", attempt, MAX_SELL_ATTEMPTS, e);
-                    last_error = Some(format!("Sign failed: {}", e));
-                    if attempt < MAX_SELL_ATTEMPTS {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(RETRY_DELAY_MS)).await;
+                    error!("❌ [Sell Attempt {}/{}] TX submission failed: {}", attempt, policy.max_attempts, e);
+                    self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, None, Some(&e.to_string()));
+                    if attempt < policy.max_attempts && SpectreError::is_retryable(&e) {
+                        tokio::time::sleep(retry_delay).await;
                         continue;
                     }
                     return Ok(TradeResult {
-                        success: false,
-                        token_mint: token_mint.to_string(),
-                        token_symbol: position.token_symbol.clone(),
-                        action: "sell".to_string(),
-                        amount_sol: 0.0,
-                        amount_tokens: Some(position.amount_tokens as f64),
-                        price_per_token: None,
-                        tx_signature: None,
-                        error: last_error,
-                        latency_ms: start.elapsed().as_millis() as u64,
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                        signal_type: None,
-                        signal_strength: None,
-                        market_cap_usd: None,
-                        liquidity_usd: None,
-                        entry_price_usd: Some(position.entry_price),
-                        stop_loss_percent: Some(position.stop_loss_percent),
-                        take_profit_percent: Some(position.take_profit_percent),
-                        trigger_wallets: None,
-                        attempt_number: attempt,
-                        price_at_signal: None,
-                        price_at_trade: None,
-                        price_change_percent: None,
-                        signal_timestamp: None,
+                        error: Some(format!("TX failed: {}", e)),
+                        error_code: SpectreError::code_of(&e),
+                        ..self.create_sell_result(position, attempt, start)
                     });
                 }
             };
 
-            // Try Jito first, then fallback to RPC
-            let tx_sig = match self.jito.send_bundle(&signed_tx).await {
-                Ok(id) => id,
-                Err(jito_err) => {
-                    warn!("⚠️ [Sell Attempt {}/{}] Jito failed, trying RPC: {}", attempt, MAX_SELL_ATTEMPTS, jito_err);
-                    match self.rpc_client.send_and_confirm_transaction(&signed_tx).await {
-                        Ok(sig) => sig.to_string(),
-                        Err(rpc_err) => {
-                            error!("❌ [Sell Attempt {}/{}] RPC also failed: {}", attempt, MAX_SELL_ATTEMPTS, rpc_err);
-                            last_error = Some(format!("TX failed: Jito={}, RPC={}", jito_err, rpc_err));
-                            if attempt < MAX_SELL_ATTEMPTS {
-                                tokio::time::sleep(tokio::time::Duration::from_millis(RETRY_DELAY_MS)).await;
-                                continue;
-                            }
-                            return Ok(TradeResult {
-                                success: false,
-                                token_mint: token_mint.to_string(),
-                                token_symbol: position.token_symbol.clone(),
-                                action: "sell".to_string(),
-                                amount_sol: 0.0,
-                                amount_tokens: Some(position.amount_tokens as f64),
-                                price_per_token: None,
-                                tx_signature: None,
-                                error: last_error,
-                                latency_ms: start.elapsed().as_millis() as u64,
-                                timestamp: chrono::Utc::now().to_rfc3339(),
-                                signal_type: None,
-                                signal_strength: None,
-                                market_cap_usd: None,
-                                liquidity_usd: None,
-                                entry_price_usd: Some(position.entry_price),
-                                stop_loss_percent: Some(position.stop_loss_percent),
-                                take_profit_percent: Some(position.take_profit_percent),
-                                trigger_wallets: None,
-                                attempt_number: attempt,
-                                price_at_signal: None,
-                                price_at_trade: None,
-                                price_change_percent: None,
-                                signal_timestamp: None,
-                            });
-                        }
-                    }
+            if let Some(tx_error) = submission.outcome.error() {
+                error!("❌ [Sell Attempt {}/{}] Sell TX {} not confirmed: {}", attempt, policy.max_attempts, submission.signature, submission.outcome);
+                self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, Some(&submission.signature), Some(&submission.outcome.to_string()));
+                if attempt < policy.max_attempts && tx_error.code().is_retryable() {
+                    tokio::time::sleep(retry_delay).await;
+                    continue;
                 }
-            };
-
-            // SUCCESS! Only remove position if it's a full exit
-            if should_remove_position {
-                self.position_manager.remove_position(token_mint).await;
+                return Ok(TradeResult {
+                    tx_signature: Some(submission.signature),
+                    bundle_id: submission.bundle_id,
+                    error: Some(format!("TX {}", submission.outcome)),
+                    error_code: Some(tx_error.code()),
+                    ..self.create_sell_result(position, attempt, start)
+                });
             }
 
-            let elapsed = start.elapsed();
-            let pnl_sol = out_sol - position.amount_sol_invested;
-            let pnl_percent = (out_sol / position.amount_sol_invested - 1.0) * 100.0;
+            let elapsed = submission.submit_latency;
+            self.journal.record_attempt(&order, attempt, start.elapsed().as_millis() as u64, Some(&submission.signature), None);
 
             info!(
-                "✅ {} SELL executed (attempt {}) ({}): {} SOL received | PnL: {:.4} SOL ({:.1}%) | took: {:?}",
+                "✅ {} {} SELL confirmed (attempt {}) ({}): {} tokens sold{} (took: {:?})",
+                venue.label(),
                 if should_remove_position { "FULL" } else { "PARTIAL" },
                 attempt,
                 reason,
-                out_sol,
-                pnl_sol,
-                pnl_percent,
+                position.amount_tokens,
+                quoted_sol.map(|sol| format!(" for ~{:.4} SOL", sol)).unwrap_or_default(),
                 elapsed
             );
 
             return Ok(TradeResult {
                 success: true,
-                token_mint: token_mint.to_string(),
-                token_symbol: position.token_symbol.clone(),
-                action: "sell".to_string(),
-                amount_sol: out_sol,
-                amount_tokens: Some(position.amount_tokens as f64),
-                price_per_token: None,
-                tx_signature: Some(tx_sig),
-                error: None,
+                // Quote, or invested amount when the venue gives none (replaced by the on-chain change)
+                amount_sol: quoted_sol.unwrap_or(position.amount_sol_invested),
+                tx_signature: Some(submission.signature),
+                bundle_id: submission.bundle_id,
                 latency_ms: elapsed.as_millis() as u64,
-                timestamp: chrono::Utc::now().to_rfc3339(),
-                signal_type: None,
-                signal_strength: None,
-                market_cap_usd: None,
-                liquidity_usd: None,
-                entry_price_usd: Some(position.entry_price),
-                stop_loss_percent: Some(position.stop_loss_percent),
-                take_profit_percent: Some(position.take_profit_percent),
-                trigger_wallets: None,
-                attempt_number: attempt,
-                price_at_signal: None,
-                price_at_trade: None,
-                price_change_percent: None,
-                signal_timestamp: None,
+                ..self.create_sell_result(position, attempt, start)
             });
         }
 
-        // Should never reach here
-        Err(anyhow!("Sell failed after {} attempts", MAX_SELL_ATTEMPTS))
+        Err(anyhow!("Sell failed after {} attempts", policy.max_attempts))
     }
 
-    /// Sign a versioned transaction
-    fn sign_versioned_transaction(
-        &self,
-        mut transaction: VersionedTransaction,
-        recent_blockhash: solana_sdk::hash::Hash,
-    ) -> Result<VersionedTransaction> {
-        // Update blockhash in the message
-        match &mut transaction.message {
-            solana_sdk::message::VersionedMessage::Legacy(msg) => {
-                msg.recent_blockhash = recent_blockhash;
-            }
-            solana_sdk::message::VersionedMessage::V0(msg) => {
-                msg.recent_blockhash = recent_blockhash;
-            }
+    /// Helper to create a sell TradeResult with position context (failed until filled in)
+    fn create_sell_result(&self, position: &Position, attempt: u32, start: std::time::Instant) -> TradeResult {
+        TradeResult {
+            success: false,
+            token_mint: position.token_mint.clone(),
+            token_symbol: position.token_symbol.clone(),
+            action: "sell".to_string(),
+            amount_sol: 0.0,
+            amount_tokens: Some(position.amount_tokens as f64),
+            price_per_token: None,
+            tx_signature: None,
+            bundle_id: None,
+            error: None,
+            error_code: None,
+            latency_ms: start.elapsed().as_millis() as u64,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            signal_type: None,
+            signal_strength: None,
+            market_cap_usd: None,
+            liquidity_usd: None,
+            entry_price_usd: Some(position.entry_price),
+            stop_loss_percent: Some(position.stop_loss_percent),
+            take_profit_percent: Some(position.take_profit_percent),
+            trigger_wallets: None,
+            attempt_number: attempt,
+            price_at_signal: None,
+            price_at_trade: None,
+            price_change_percent: None,
+            signal_timestamp: None,
+            signal_age_ms: None,
+            signal_to_submit_ms: None,
+            exit_reason: None,
+            paper: self.config.paper_mode,
         }
-
-        // Sign with wallet
-        let message_data = transaction.message.serialize();
-        let signature = self.config.wallet.sign_message(&message_data);
-
-        // Find the signer index and update signature
-        transaction.signatures[0] = signature;
-
-        Ok(transaction)
     }
 
     /// Get position manager reference
@@ -1142,9 +1195,214 @@ impl SpectreTrader {
         &self.position_manager
     }
 
+    /// Get trade journal reference
+    pub fn journal(&self) -> &TradeJournal {
+        &self.journal
+    }
+
     /// Check wallet balance
     pub async fn get_balance(&self) -> Result<f64> {
-        let balance = self.rpc_client.get_balance(&self.config.wallet_pubkey()).await?;
-        Ok(balance as f64 / 1e9)
+        self.wallet.sol_balance(&self.config.wallet_pubkey()).await
+    }
+
+    /// Update SOL/USD price used for entry price conversion
+    pub async fn set_sol_price(&self, price: f64) {
+        *self.sol_price_usd.write().await = price;
+    }
+
+    /// Get the wallet's balance of a single mint (None if no token account / zero)
+    pub async fn get_token_holding(&self, token_mint: &str) -> Result<Option<WalletHolding>> {
+        self.wallet.token_holding(&self.config.wallet_pubkey(), token_mint).await
+    }
+
+    /// Net SOL change of our wallet in a confirmed transaction (negative for buys)
+    async fn wallet_sol_change(&self, signature: &str) -> Option<f64> {
+        self.wallet.sol_change(signature).await.ok().flatten()
+    }
+
+    /// Poll the wallet until the bought tokens show up (or timeout)
+    /// The fill is what the balance grew by over `balance_before` (tokens held before the buy)
+    async fn wait_for_token_fill(&self, token_mint: &str, balance_before: u64) -> Option<WalletHolding> {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(FILL_TIMEOUT_SECS);
+
+        loop {
+            match self.get_token_holding(token_mint).await {
+                Ok(Some(holding)) if holding.amount > balance_before => {
+                    let fill = WalletHolding { amount: holding.amount - balance_before, ..holding };
+                    info!(
+                        "📥 Fill confirmed on-chain: {} tokens ({} decimals, {} held before)",
+                        fill.amount, fill.decimals, balance_before
+                    );
+                    return Some(fill);
+                }
+                Ok(_) => {}
+                Err(e) => warn!("⚠️ Failed to read token balance for {}: {}", token_mint, e),
+            }
+
+            if std::time::Instant::now() >= deadline {
+                warn!(
+                    "⚠️ No on-chain balance for {} after {}s - using estimated amount",
+                    &token_mint[..16.min(token_mint.len())],
+                    FILL_TIMEOUT_SECS
+                );
+                return None;
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(FILL_POLL_INTERVAL_MS)).await;
+        }
+    }
+
+    /// Effective USD entry price per whole token (what we paid incl. fees/slippage)
+    async fn effective_entry_price(&self, sol_spent: f64, holding: &WalletHolding) -> f64 {
+        let ui_amount = holding.ui_amount();
+        if ui_amount <= 0.0 {
+            return 0.0;
+        }
+        let sol_usd = *self.sol_price_usd.read().await;
+        sol_spent * sol_usd / ui_amount
+    }
+
+    /// List all non-zero SPL token balances in the wallet (classic + Token-2022)
+    pub async fn get_wallet_holdings(&self) -> Result<Vec<WalletHolding>> {
+        self.wallet.token_holdings(&self.config.wallet_pubkey()).await
+    }
+
+    /// Compare on-chain wallet holdings with tracked positions and fix drift:
+    /// - positions with zero balance are dropped (sold, but we never saw the confirmation)
+    /// - positions with a different balance get their token amount corrected
+    /// - untracked holdings are adopted (entry price from `prices`) or flagged
+    pub async fn reconcile_positions(&self, prices: &dyn PriceSource) -> Result<ReconcileReport> {
+        let holdings = self.get_wallet_holdings().await?;
+        let holdings: HashMap<String, WalletHolding> = holdings
+            .into_iter()
+            .map(|h| (h.token_mint.clone(), h))
+            .collect();
+
+        let mut report = ReconcileReport::default();
+        let now = chrono::Utc::now();
+
+        // 1. Tracked positions vs wallet
+        for position in self.position_manager.get_all_positions().await {
+            if (now - position.entry_time).num_seconds() < RECONCILE_GRACE_SECS {
+                continue;
+            }
+
+            // A sell in flight moves the balance - the exit path owns the position until it finishes
+            if self.position_manager.pending_exit(&position.token_mint).await.is_some() {
+                continue;
+            }
+
+            match holdings.get(&position.token_mint) {
+                None => {
+                    // Closed by a sell since the snapshot - already journaled by the exit
+                    if self.position_manager.remove_position(&position.token_mint).await.is_none() {
+                        continue;
+                    }
+                    warn!(
+                        "🧹 {} has zero on-chain balance - dropping position",
+                        position.token_symbol
+                    );
+                    // Tokens are gone: close it in the journal with nothing received
+                    self.journal.record_exit(&position, &ExitFill {
+                        reason: &ExitReason::ReconciledZeroBalance,
+                        tokens_sold: 0,
+                        sol_received: 0.0,
+                        price_usd: None,
+                        closes_position: true,
+                        tx_signature: "",
+                    });
+                    report.dropped.push(position.token_mint.clone());
+                }
+                Some(holding) if holding.amount != position.amount_tokens => {
+                    self.position_manager.set_amount_tokens(&position.token_mint, holding.amount).await;
+                    report.resynced.push(position.token_mint.clone());
+                }
+                Some(_) => {}
+            }
+        }
+
+        // 2. Wallet holdings we don't track
+        let mut sol_price: Option<f64> = None;
+
+        for holding in holdings.values() {
+            if RECONCILE_NEVER_ADOPT.contains(&holding.token_mint.as_str())
+                || self.position_manager.has_position(&holding.token_mint).await
+                || self.position_manager.pending_exit(&holding.token_mint).await.is_some()
+            {
+                continue;
+            }
+
+            let short_mint = &holding.token_mint[..16.min(holding.token_mint.len())];
+
+            let price = match prices.get_price(&holding.token_mint).await {
+                Ok(price) if price > 0.0 => price,
+                Ok(_) | Err(_) => {
+                    warn!("🚩 Untracked holding {} ({} tokens) - no price, cannot adopt", short_mint, holding.ui_amount());
+                    report.flagged.push(holding.token_mint.clone());
+                    continue;
+                }
+            };
+
+            let value_usd = holding.ui_amount() * price;
+            if value_usd < self.config.reconcile_min_value_usd {
+                // Dust left over from earlier trades
+                continue;
+            }
+
+            if !self.config.reconcile_adopt_orphans {
+                warn!("🚩 Untracked holding {} worth ${:.2} (adoption disabled)", short_mint, value_usd);
+                report.flagged.push(holding.token_mint.clone());
+                continue;
+            }
+
+            let sol_usd = match sol_price {
+                Some(p) => p,
+                None => {
+                    let p = prices.get_price(SOL_MINT).await.unwrap_or(200.0);
+                    sol_price = Some(p);
+                    p
+                }
+            };
+
+            // Jupiter routes both bonding-curve and graduated tokens, so it's the safe exit venue
+            let mut position = Position::new(
+                holding.token_mint.clone(),
+                short_mint.to_string(),
+                price,
+                holding.amount,
+                value_usd / sol_usd,
+                self.config.stop_loss_percent,
+                self.config.take_profit_percent,
+                "reconstructed".to_string(),
+                false,
+            );
+            position.reconstructed = true;
+            position.price_synced = true;
+            if let Some(strategy) = self.config.exit_strategies.resolve("", "") {
+                position = position.with_exit_strategy(strategy);
+            }
+
+            warn!(
+                "🧩 Adopted untracked holding {} | {} tokens @ ${:.10} (~${:.2})",
+                short_mint,
+                holding.ui_amount(),
+                price,
+                value_usd
+            );
+
+            self.journal.record_adopted(&position);
+            self.position_manager.add_position(position.clone()).await;
+            report.adopted.push(position);
+        }
+
+        info!(
+            "🔁 Reconciliation done: {} adopted, {} flagged, {} dropped, {} resynced",
+            report.adopted.len(),
+            report.flagged.len(),
+            report.dropped.len(),
+            report.resynced.len()
+        );
+
+        Ok(report)
     }
 }
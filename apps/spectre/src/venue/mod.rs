@@ -0,0 +1,122 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use solana_sdk::{
+    hash::Hash,
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::VersionedTransaction,
+};
+
+use crate::error::SpectreError;
+
+/// Side of an order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// How often an order is retried on a venue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub retry_delay_ms: u64,
+}
+
+/// Buy `amount_sol` worth of a token
+#[derive(Debug, Clone)]
+pub struct BuyOrder {
+    pub wallet: Pubkey,
+    pub token_mint: String,
+    pub amount_sol: f64,
+    pub slippage_bps: u16,
+    pub priority_fee_lamports: u64,
+}
+
+/// Sell part or all of a position
+#[derive(Debug, Clone)]
+pub struct SellOrder {
+    pub wallet: Pubkey,
+    pub token_mint: String,
+    /// Raw token units to sell
+    pub amount_tokens: u64,
+    /// Mint decimals when the fill was read on-chain (None = amount is an estimate)
+    pub decimals: Option<u8>,
+    /// Sell everything the wallet holds (no dust left behind)
+    pub full_exit: bool,
+    /// % of the remaining position being sold (scaled exits)
+    pub sell_percent: f64,
+    pub slippage_bps: u16,
+    pub priority_fee_lamports: u64,
+    /// 1-based attempt number (venues widen slippage on retries)
+    pub attempt: u32,
+}
+
+/// Unsigned swap transaction built by a venue
+pub struct SwapTx {
+    pub transaction: VersionedTransaction,
+    /// Quoted output (raw tokens for buys, lamports for sells) - None if the venue gives no quote
+    pub quoted_out: Option<u64>,
+}
+
+/// Where swaps are routed (pump.fun bonding curve, Jupiter aggregator, ...)
+#[async_trait]
+pub trait SwapVenue: Send + Sync {
+    /// Short name for the journal and paper fills ("pumpfun", "jupiter")
+    fn name(&self) -> &'static str;
+
+    /// Name in logs ("PUMP.FUN", "JUPITER")
+    fn label(&self) -> &'static str;
+
+    /// Attempts and delay between them for buys or sells
+    fn retry_policy(&self, side: Side) -> RetryPolicy;
+
+    /// Transactions get a fresh blockhash right before signing
+    fn needs_fresh_blockhash(&self) -> bool {
+        false
+    }
+
+    /// Decimals of every mint traded here, if fixed (pump.fun = 6)
+    fn token_decimals(&self) -> Option<u8> {
+        None
+    }
+
+    /// Raw tokens out for `amount_lamports` in (None = venue has no quote API)
+    async fn quote_buy(&self, token_mint: &str, amount_lamports: u64, slippage_bps: u16) -> Result<Option<u64>>;
+
+    /// Lamports out for `amount_tokens` in (None = venue has no quote API)
+    async fn quote_sell(&self, token_mint: &str, amount_tokens: u64, slippage_bps: u16) -> Result<Option<u64>>;
+
+    async fn build_buy(&self, order: &BuyOrder) -> Result<SwapTx>;
+
+    async fn build_sell(&self, order: &SellOrder) -> Result<SwapTx>;
+}
+
+/// Sign as the first signer (fee payer), optionally replacing the blockhash first
+/// Fails if the venue made someone else the fee payer
+pub fn sign_transaction(mut transaction: VersionedTransaction, wallet: &Keypair, recent_blockhash: Option<Hash>) -> Result<VersionedTransaction, SpectreError> {
+    let fee_payer = transaction.message.static_account_keys().first().copied();
+    if fee_payer != Some(wallet.pubkey()) {
+        return Err(SpectreError::SignFailed(format!(
+            "fee payer is {}, not our wallet",
+            fee_payer.map(|p| p.to_string()).unwrap_or_else(|| "missing".to_string())
+        )));
+    }
+
+    if let Some(recent_blockhash) = recent_blockhash {
+        match &mut transaction.message {
+            VersionedMessage::Legacy(msg) => msg.recent_blockhash = recent_blockhash,
+            VersionedMessage::V0(msg) => msg.recent_blockhash = recent_blockhash,
+        }
+    }
+
+    let signature = wallet.sign_message(&transaction.message.serialize());
+    if transaction.signatures.is_empty() {
+        transaction.signatures.push(signature);
+    } else {
+        transaction.signatures[0] = signature;
+    }
+
+    Ok(transaction)
+}
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+use std::collections::HashMap;
+use std::str::FromStr;
+use tracing::{debug, warn};
+
+use crate::jupiter::SOL_MINT;
+
+// Token-2022 program (newer pump.fun mints use it instead of the classic SPL token program)
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// getTransaction lookups right after confirmation can miss - retry a few times
+const TX_META_ATTEMPTS: u32 = 3;
+const TX_META_RETRY_MS: u64 = 500;
+
+/// SPL token balance held by the wallet (summed over all token accounts of a mint)
+#[derive(Debug, Clone)]
+pub struct WalletHolding {
+    pub token_mint: String,
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+impl WalletHolding {
+    /// Amount in whole tokens (what DexScreener/PumpPortal prices refer to)
+    pub fn ui_amount(&self) -> f64 {
+        self.amount as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+/// On-chain wallet state the trader reads fills and balances from
+#[async_trait]
+pub trait WalletReader: Send + Sync {
+    /// SOL balance of `owner`
+    async fn sol_balance(&self, owner: &Pubkey) -> Result<f64>;
+
+    /// Balance of a single mint (None if no token account / zero)
+    async fn token_holding(&self, owner: &Pubkey, token_mint: &str) -> Result<Option<WalletHolding>>;
+
+    /// All non-zero SPL token balances (classic + Token-2022)
+    async fn token_holdings(&self, owner: &Pubkey) -> Result<Vec<WalletHolding>>;
+
+    /// Decimals of a mint
+    async fn mint_decimals(&self, token_mint: &str) -> Result<u8>;
+
+    /// Net SOL change of the fee payer in a confirmed transaction (negative for buys)
+    /// Includes network fees and priority fees, so it's the real cost / proceeds
+    async fn sol_change(&self, signature: &str) -> Result<Option<f64>>;
+}
+
+#[async_trait]
+impl WalletReader for RpcClient {
+    async fn sol_balance(&self, owner: &Pubkey) -> Result<f64> {
+        let balance = self.get_balance(owner).await?;
+        Ok(balance as f64 / 1e9)
+    }
+
+    async fn token_holding(&self, owner: &Pubkey, token_mint: &str) -> Result<Option<WalletHolding>> {
+        let mint = Pubkey::from_str(token_mint)?;
+        let accounts = self
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(mint))
+            .await?;
+
+        let mut holding: Option<WalletHolding> = None;
+        for keyed in accounts {
+            let data = serde_json::to_value(&keyed.account.data)?;
+            let token_amount = &data["parsed"]["info"]["tokenAmount"];
+            let amount: u64 = token_amount["amount"]
+                .as_str()
+                .and_then(|a| a.parse().ok())
+                .unwrap_or(0);
+            let decimals = token_amount["decimals"].as_u64().unwrap_or(0) as u8;
+
+            match holding {
+                Some(ref mut h) => h.amount += amount,
+                None => {
+                    holding = Some(WalletHolding {
+                        token_mint: token_mint.to_string(),
+                        amount,
+                        decimals,
+                    })
+                }
+            }
+        }
+
+        Ok(holding.filter(|h| h.amount > 0))
+    }
+
+    async fn token_holdings(&self, owner: &Pubkey) -> Result<Vec<WalletHolding>> {
+        let programs = [spl_token::id(), Pubkey::from_str(TOKEN_2022_PROGRAM_ID)?];
+
+        let mut holdings: HashMap<String, WalletHolding> = HashMap::new();
+
+        for program_id in programs {
+            let accounts = self
+                .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program_id))
+                .await?;
+
+            for keyed in accounts {
+                // jsonParsed layout: { parsed: { info: { mint, tokenAmount: { amount, decimals } } } }
+                let data = serde_json::to_value(&keyed.account.data)?;
+                let info = &data["parsed"]["info"];
+
+                let Some(mint) = info["mint"].as_str() else { continue };
+                let amount: u64 = info["tokenAmount"]["amount"]
+                    .as_str()
+                    .and_then(|a| a.parse().ok())
+                    .unwrap_or(0);
+                let decimals = info["tokenAmount"]["decimals"].as_u64().unwrap_or(0) as u8;
+
+                if amount == 0 || mint == SOL_MINT {
+                    continue;
+                }
+
+                holdings
+                    .entry(mint.to_string())
+                    .and_modify(|h| h.amount += amount)
+                    .or_insert(WalletHolding {
+                        token_mint: mint.to_string(),
+                        amount,
+                        decimals,
+                    });
+            }
+        }
+
+        Ok(holdings.into_values().collect())
+    }
+
+    /// From the parsed mint account
+    async fn mint_decimals(&self, token_mint: &str) -> Result<u8> {
+        let mint = Pubkey::from_str(token_mint)?;
+        let supply = self.get_token_supply(&mint).await?;
+        Ok(supply.decimals)
+    }
+
+    async fn sol_change(&self, signature: &str) -> Result<Option<f64>> {
+        let signature = Signature::from_str(signature)?;
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Json),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+
+        for attempt in 1..=TX_META_ATTEMPTS {
+            match self.get_transaction_with_config(&signature, config).await {
+                Ok(tx) => {
+                    // Account 0 is the fee payer = our wallet
+                    let Some(meta) = tx.transaction.meta else { return Ok(None) };
+                    let (Some(pre), Some(post)) = (meta.pre_balances.first(), meta.post_balances.first()) else {
+                        return Ok(None);
+                    };
+                    return Ok(Some((*post as i64 - *pre as i64) as f64 / 1e9));
+                }
+                Err(e) if attempt < TX_META_ATTEMPTS => {
+                    debug!("getTransaction {} not available yet: {}", signature, e);
+                    tokio::time::sleep(tokio::time::Duration::from_millis(TX_META_RETRY_MS)).await;
+                }
+                Err(e) => warn!("⚠️ Failed to read SOL change for {}: {}", signature, e),
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,858 @@
+//! Local stand-ins for the RPC, Jito, PumpPortal, Jupiter and Redis, plus a trader wired to them
+//!
+//! `Chain` is the shared "on-chain" state: transactions land when they reach Jito or the RPC,
+//! and each landed transaction applies the next scripted `Fill` to the wallet.
+
+#![allow(dead_code)]
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use solana_sdk::{
+    hash::Hash,
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    system_instruction,
+    transaction::VersionedTransaction,
+};
+use spectre::advisory::{AdvisoryPolicy, AdvisoryResponse, CopyExitPolicy};
+use spectre::config::Config;
+use spectre::pumpportal::TradeEvent;
+use spectre::redis::{ResultPublisher, SpectreSignal, TradeResult};
+use spectre::sizing::SizingPolicy;
+use spectre::strategy::ExitStrategies;
+use spectre::trader::SpectreTrader;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// pump.fun mints use 6 decimals, so do all test mints
+pub const DECIMALS: u8 = 6;
+
+/// Starting wallet balance
+const WALLET_LAMPORTS: u64 = 10_000_000_000;
+
+/// Upper bound for anything a test waits on
+pub const WAIT: Duration = Duration::from_secs(15);
+
+/// Wallet state after a transaction lands
+#[derive(Debug, Clone)]
+pub struct Fill {
+    pub token_mint: String,
+    /// Raw token balance of `token_mint` after the transaction
+    pub token_balance: u64,
+    /// Net SOL change of the fee payer (negative for buys)
+    pub sol_delta_lamports: i64,
+}
+
+/// Which path a transaction reached the chain through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    Jito,
+    Rpc,
+}
+
+#[derive(Default)]
+struct ChainState {
+    fills: VecDeque<Fill>,
+    holdings: HashMap<String, u64>,
+    landed: HashMap<String, i64>,
+    routes: Vec<Route>,
+    tx_error: Option<Value>,
+}
+
+/// Shared ledger behind the RPC and Jito mocks
+#[derive(Default)]
+pub struct Chain {
+    state: Mutex<ChainState>,
+    reject_bundles: AtomicBool,
+}
+
+impl Chain {
+    /// Queue the wallet state for the next transaction that lands
+    pub fn script_fill(&self, token_mint: &str, token_balance: u64, sol_delta_lamports: i64) {
+        self.state.lock().unwrap().fills.push_back(Fill {
+            token_mint: token_mint.to_string(),
+            token_balance,
+            sol_delta_lamports,
+        });
+    }
+
+    /// Make Jito answer every bundle with an error (RPC fallback path)
+    pub fn reject_bundles(&self, reject: bool) {
+        self.reject_bundles.store(reject, Ordering::SeqCst);
+    }
+
+    /// Land every following transaction with this `TransactionError` (JSON form)
+    pub fn fail_transactions(&self, error: Value) {
+        self.state.lock().unwrap().tx_error = Some(error);
+    }
+
+    /// Paths of every landed transaction, in order
+    pub fn routes(&self) -> Vec<Route> {
+        self.state.lock().unwrap().routes.clone()
+    }
+
+    /// Put tokens in the wallet without a transaction (airdrops, transfers in)
+    pub fn set_holding(&self, token_mint: &str, amount: u64) {
+        self.state.lock().unwrap().holdings.insert(token_mint.to_string(), amount);
+    }
+
+    pub fn token_balance(&self, token_mint: &str) -> u64 {
+        self.state.lock().unwrap().holdings.get(token_mint).copied().unwrap_or(0)
+    }
+
+    fn land(&self, transaction: &VersionedTransaction, route: Route) -> String {
+        let signature = transaction.signatures.first().copied().unwrap_or_default().to_string();
+        let mut state = self.state.lock().unwrap();
+
+        if state.landed.contains_key(&signature) {
+            return signature;
+        }
+
+        let delta = match state.fills.pop_front() {
+            Some(fill) => {
+                state.holdings.insert(fill.token_mint, fill.token_balance);
+                fill.sol_delta_lamports
+            }
+            None => -5_000,
+        };
+        state.landed.insert(signature.clone(), delta);
+        state.routes.push(route);
+        signature
+    }
+
+    fn landed_delta(&self, signature: &str) -> Option<i64> {
+        self.state.lock().unwrap().landed.get(signature).copied()
+    }
+
+    fn tx_error(&self) -> Value {
+        self.state.lock().unwrap().tx_error.clone().unwrap_or(Value::Null)
+    }
+
+    fn token_accounts(&self, owner: &str, mint: Option<&str>) -> Vec<Value> {
+        let state = self.state.lock().unwrap();
+        state
+            .holdings
+            .iter()
+            .filter(|(m, amount)| **amount > 0 && mint.is_none_or(|wanted| wanted == m.as_str()))
+            .map(|(m, amount)| token_account(owner, m, *amount))
+            .collect()
+    }
+}
+
+fn token_account(owner: &str, mint: &str, amount: u64) -> Value {
+    let ui_amount = amount as f64 / 10f64.powi(DECIMALS as i32);
+    json!({
+        "pubkey": Pubkey::new_unique().to_string(),
+        "account": {
+            "data": {
+                "program": "spl-token",
+                "parsed": {
+                    "info": {
+                        "isNative": false,
+                        "mint": mint,
+                        "owner": owner,
+                        "state": "initialized",
+                        "tokenAmount": {
+                            "amount": amount.to_string(),
+                            "decimals": DECIMALS,
+                            "uiAmount": ui_amount,
+                            "uiAmountString": ui_amount.to_string(),
+                        }
+                    },
+                    "type": "account"
+                },
+                "space": 165
+            },
+            "executable": false,
+            "lamports": 2_039_280,
+            "owner": spl_token::id().to_string(),
+            "rentEpoch": 0,
+            "space": 165
+        }
+    })
+}
+
+/// Unsigned transaction with `payer` as fee payer, unique per `nonce`
+pub fn unsigned_transaction(payer: &Pubkey, nonce: u64) -> VersionedTransaction {
+    let instruction = system_instruction::transfer(payer, &Pubkey::new_unique(), nonce + 1);
+    let message = Message::new_with_blockhash(&[instruction], Some(payer), &Hash::new_unique());
+    VersionedTransaction {
+        signatures: vec![Signature::default()],
+        message: VersionedMessage::Legacy(message),
+    }
+}
+
+fn rpc_context(value: Value) -> Value {
+    json!({ "context": { "slot": 1 }, "value": value })
+}
+
+/// JSON-RPC node backed by `Chain`
+struct RpcResponder {
+    chain: Arc<Chain>,
+}
+
+impl Respond for RpcResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: Value = serde_json::from_slice(&request.body).expect("JSON-RPC request");
+        let params = &body["params"];
+
+        let result = match body["method"].as_str().unwrap_or_default() {
+            "getVersion" => json!({ "solana-core": "1.18.26", "feature-set": 4215500110u64 }),
+            "getLatestBlockhash" => rpc_context(json!({
+                "blockhash": Hash::new_unique().to_string(),
+                "lastValidBlockHeight": 1_000,
+            })),
+            "isBlockhashValid" => rpc_context(json!(true)),
+            "getBalance" => rpc_context(json!(WALLET_LAMPORTS)),
+            "getTokenSupply" => rpc_context(json!({
+                "amount": "1000000000000000",
+                "decimals": DECIMALS,
+                "uiAmount": 1_000_000_000.0,
+                "uiAmountString": "1000000000",
+            })),
+            "sendTransaction" => {
+                let encoded = params[0].as_str().unwrap_or_default();
+                let bytes = base64::engine::general_purpose::STANDARD.decode(encoded).expect("base64 tx");
+                let transaction: VersionedTransaction = bincode::deserialize(&bytes).expect("bincode tx");
+                json!(self.chain.land(&transaction, Route::Rpc))
+            }
+            "getSignatureStatuses" => {
+                let statuses: Vec<Value> = params[0]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|sig| match self.chain.landed_delta(sig.as_str().unwrap_or_default()) {
+                        Some(_) => json!({
+                            "slot": 1,
+                            "confirmations": null,
+                            "err": self.chain.tx_error(),
+                            "status": { "Ok": null },
+                            "confirmationStatus": "finalized",
+                        }),
+                        None => Value::Null,
+                    })
+                    .collect();
+                rpc_context(json!(statuses))
+            }
+            "getTransaction" => match self.chain.landed_delta(params[0].as_str().unwrap_or_default()) {
+                Some(delta) => {
+                    let pre = WALLET_LAMPORTS;
+                    let post = (pre as i64 + delta) as u64;
+                    json!({
+                        "slot": 1,
+                        "blockTime": null,
+                        "transaction": ["", "base64"],
+                        "meta": {
+                            "err": null,
+                            "status": { "Ok": null },
+                            "fee": 5_000,
+                            "preBalances": [pre],
+                            "postBalances": [post],
+                        }
+                    })
+                }
+                None => Value::Null,
+            },
+            "getTokenAccountsByOwner" => {
+                let owner = params[0].as_str().unwrap_or_default();
+                let accounts = match (params[1]["mint"].as_str(), params[1]["programId"].as_str()) {
+                    (Some(mint), _) => self.chain.token_accounts(owner, Some(mint)),
+                    (None, Some(program)) if program == spl_token::id().to_string() => {
+                        self.chain.token_accounts(owner, None)
+                    }
+                    _ => Vec::new(),
+                };
+                rpc_context(json!(accounts))
+            }
+            other => {
+                return ResponseTemplate::new(200).set_body_json(json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "error": { "code": -32601, "message": format!("Method not found: {}", other) },
+                }))
+            }
+        };
+
+        ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": body["id"], "result": result }))
+    }
+}
+
+/// Jito block engine backed by `Chain`
+struct JitoResponder {
+    chain: Arc<Chain>,
+}
+
+impl Respond for JitoResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: Value = serde_json::from_slice(&request.body).expect("Jito request");
+
+        let reply = match body["method"].as_str().unwrap_or_default() {
+            "sendBundle" if self.chain.reject_bundles.load(Ordering::SeqCst) => json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "error": { "code": -32602, "message": "bundle rejected: simulation failed" },
+            }),
+            "sendBundle" => {
+                let encoded = body["params"][0][0].as_str().unwrap_or_default();
+                let bytes = bs58::decode(encoded).into_vec().expect("base58 tx");
+                let transaction: VersionedTransaction = bincode::deserialize(&bytes).expect("bincode tx");
+                let signature = self.chain.land(&transaction, Route::Jito);
+                json!({ "jsonrpc": "2.0", "id": body["id"], "result": format!("bundle-{}", &signature[..8]) })
+            }
+            _ => json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "result": { "context": { "slot": 1 }, "value": [{ "status": "Landed", "landed_slot": 1 }] },
+            }),
+        };
+
+        ResponseTemplate::new(200).set_body_json(reply)
+    }
+}
+
+/// PumpPortal trade-local: raw serialized transaction for the requested wallet
+struct PumpPortalResponder {
+    built: AtomicU64,
+}
+
+impl Respond for PumpPortalResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: Value = serde_json::from_slice(&request.body).expect("PumpPortal request");
+        let payer: Pubkey = body["publicKey"].as_str().unwrap_or_default().parse().expect("publicKey");
+        let nonce = self.built.fetch_add(1, Ordering::SeqCst);
+
+        let transaction = unsigned_transaction(&payer, nonce);
+        ResponseTemplate::new(200).set_body_bytes(bincode::serialize(&transaction).unwrap())
+    }
+}
+
+/// Jupiter /swap: base64 transaction for `userPublicKey`
+struct JupiterSwapResponder {
+    built: AtomicU64,
+}
+
+impl Respond for JupiterSwapResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: Value = serde_json::from_slice(&request.body).expect("Jupiter swap request");
+        let payer: Pubkey = body["userPublicKey"].as_str().unwrap_or_default().parse().expect("userPublicKey");
+        let nonce = 1_000 + self.built.fetch_add(1, Ordering::SeqCst);
+
+        let transaction = unsigned_transaction(&payer, nonce);
+        ResponseTemplate::new(200).set_body_json(json!({
+            "swapTransaction": base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap()),
+            "lastValidBlockHeight": 1_000,
+            "prioritizationFeeLamports": 0,
+        }))
+    }
+}
+
+/// Jupiter quote echoing the requested mints, with a fixed output amount
+pub struct JupiterQuote {
+    pub out_amount: u64,
+}
+
+impl Respond for JupiterQuote {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let query: HashMap<String, String> = request.url.query_pairs().into_owned().collect();
+        let input_mint = query.get("inputMint").cloned().unwrap_or_default();
+        let output_mint = query.get("outputMint").cloned().unwrap_or_default();
+        let amount = query.get("amount").cloned().unwrap_or_default();
+
+        ResponseTemplate::new(200).set_body_json(json!({
+            "inputMint": input_mint,
+            "inAmount": amount,
+            "outputMint": output_mint,
+            "outAmount": self.out_amount.to_string(),
+            "otherAmountThreshold": self.out_amount.to_string(),
+            "swapMode": "ExactIn",
+            "slippageBps": 1500,
+            "priceImpactPct": "0.01",
+            "routePlan": [{
+                "swapInfo": {
+                    "ammKey": Pubkey::new_unique().to_string(),
+                    "label": "Raydium",
+                    "inputMint": input_mint,
+                    "outputMint": output_mint,
+                    "inAmount": amount,
+                    "outAmount": self.out_amount.to_string(),
+                },
+                "percent": 100
+            }]
+        }))
+    }
+}
+
+/// Wraps a responder and holds its answers back (keeps a request in flight)
+pub struct Delayed<R>(pub R, pub Duration);
+
+impl<R: Respond> Respond for Delayed<R> {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        self.0.respond(request).set_delay(self.1)
+    }
+}
+
+/// Jupiter's answer when no pool can route the pair
+pub fn no_route() -> ResponseTemplate {
+    ResponseTemplate::new(400).set_body_json(json!({
+        "error": "Could not find any route",
+        "errorCode": "COULD_NOT_FIND_ANY_ROUTE",
+    }))
+}
+
+/// Replace the Jupiter quote endpoint's behaviour
+pub async fn mount_jupiter_quote(server: &MockServer, responder: impl Respond + 'static) {
+    server.reset().await;
+    Mock::given(method("GET")).and(path("/quote")).respond_with(responder).mount(server).await;
+    Mock::given(method("POST"))
+        .and(path("/swap"))
+        .respond_with(JupiterSwapResponder { built: AtomicU64::new(0) })
+        .mount(server)
+        .await;
+}
+
+/// Collects published trade results
+pub struct ChannelPublisher(pub mpsc::UnboundedSender<TradeResult>);
+
+#[async_trait]
+impl ResultPublisher for ChannelPublisher {
+    async fn publish_trade_result(&self, result: &TradeResult) -> anyhow::Result<()> {
+        let _ = self.0.send(result.clone());
+        Ok(())
+    }
+}
+
+/// Every mock service plus a live-mode config pointing at them
+pub struct Harness {
+    pub chain: Arc<Chain>,
+    pub rpc: MockServer,
+    pub jito: MockServer,
+    pub pumpportal: MockServer,
+    pub jupiter: MockServer,
+    pub config: Config,
+    _dir: TempDir,
+}
+
+impl Harness {
+    pub async fn start() -> Self {
+        let chain = Arc::new(Chain::default());
+
+        let rpc = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(RpcResponder { chain: chain.clone() })
+            .mount(&rpc)
+            .await;
+
+        let jito = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(JitoResponder { chain: chain.clone() })
+            .mount(&jito)
+            .await;
+
+        let pumpportal = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/trade-local"))
+            .respond_with(PumpPortalResponder { built: AtomicU64::new(0) })
+            .mount(&pumpportal)
+            .await;
+
+        let jupiter = MockServer::start().await;
+        mount_jupiter_quote(&jupiter, JupiterQuote { out_amount: 1_000_000_000_000 }).await;
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let config = Config {
+            rpc_url: rpc.uri(),
+            jito_block_engine_url: jito.uri(),
+            wallet: Arc::new(Keypair::new()),
+            trade_amount_sol: 0.1,
+            sizing: SizingPolicy::fixed(0.1),
+            slippage_bps: 1500,
+            stop_loss_percent: 25.0,
+            take_profit_percent: 100000.0,
+            max_open_positions: 0,
+            max_deployed_sol: 0.0,
+            daily_loss_limit_sol: 0.0,
+            min_sol_reserve: 0.0,
+            exit_strategies_file: None,
+            exit_strategies: ExitStrategies::builtin(),
+            jito_tip_lamports: 700_000,
+            jito_tip_sell_lamports: 250_000,
+            redis_url: "redis://127.0.0.1:1".to_string(),
+            redis_channel: "spectre_test".to_string(),
+            redis_max_downtime_secs: 120,
+            control_queue: "spectre_test_commands".to_string(),
+            control_reply_key: "spectre_test_command_replies".to_string(),
+            advisory_queue: "spectre_test_exit_advisories".to_string(),
+            advisory_policy: AdvisoryPolicy::default(),
+            copy_exit: CopyExitPolicy { min_wallets: 0, response: AdvisoryResponse::Full },
+            jupiter_api_key: None,
+            jupiter_api_url: Some(jupiter.uri()),
+            birdeye_api_key: None,
+            pumpportal_api_url: format!("{}/api/trade-local", pumpportal.uri()),
+            pumpportal_ws_url: "ws://127.0.0.1:1".to_string(),
+            dexscreener_api_url: "http://127.0.0.1:1".to_string(),
+            birdeye_api_url: "http://127.0.0.1:1".to_string(),
+            max_concurrent_signals: 4,
+            max_signal_age_secs: 30,
+            max_signal_age_by_type: HashMap::new(),
+            position_check_interval_secs: 3600,
+            positions_file: dir.path().join("positions.jsonl").display().to_string(),
+            journal_db: dir.path().join("journal.db").display().to_string(),
+            paper_mode: false,
+            paper_slippage_bps: 100,
+            paper_fee_bps: 100,
+            capture_dir: None,
+            reconcile_adopt_orphans: true,
+            reconcile_min_value_usd: 1.0,
+            reconcile_interval_secs: 0,
+        };
+
+        Self { chain, rpc, jito, pumpportal, jupiter, config, _dir: dir }
+    }
+
+    /// Live trader on the mock services
+    pub fn trader(&self) -> Arc<SpectreTrader> {
+        Arc::new(SpectreTrader::new(self.config.clone()).expect("trader"))
+    }
+}
+
+/// Signal for a fresh random mint
+pub fn signal(signal_type: &str, entry_price_usd: Option<f64>) -> SpectreSignal {
+    SpectreSignal {
+        signal_type: signal_type.to_string(),
+        token_symbol: "TEST".to_string(),
+        token_mint: Pubkey::new_unique().to_string(),
+        market_cap_usd: Some(15_000.0),
+        liquidity_usd: Some(5_000.0),
+        entry_price_usd,
+        stop_loss_percent: 25.0,
+        take_profit_percent: 100000.0,
+        strength: "STRONG".to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        wallets: Vec::new(),
+        priority_fee_lamports: None,
+        amount_sol: None,
+    }
+}
+
+/// Bonding-curve trade event priced at `price_sol` SOL per whole token
+pub fn trade_event(token_mint: &str, price_sol: f64) -> TradeEvent {
+    let virtual_token_reserves = 1_000_000_000.0;
+    TradeEvent {
+        signature: None,
+        mint: token_mint.to_string(),
+        sol_amount: Some(0.5),
+        token_amount: Some(0.5 / price_sol),
+        is_buy: Some(true),
+        user: None,
+        timestamp: None,
+        virtual_sol_reserves: Some(virtual_token_reserves * price_sol),
+        virtual_token_reserves: Some(virtual_token_reserves),
+        market_cap_sol: None,
+        trader_public_key: None,
+        tx_type: None,
+    }
+}
+
+/// One client connection to `MockPumpPortalWs`; dropping it closes the socket
+pub struct WsConnection {
+    incoming: mpsc::UnboundedReceiver<String>,
+    outgoing: mpsc::UnboundedSender<WsMessage>,
+}
+
+impl WsConnection {
+    /// Next text frame sent by the client
+    pub async fn next_text(&mut self) -> Value {
+        let text = tokio::time::timeout(WAIT, self.incoming.recv())
+            .await
+            .expect("timed out waiting for client message")
+            .expect("client disconnected");
+        serde_json::from_str(&text).expect("client sent JSON")
+    }
+
+    pub fn send_trade(&self, event: &TradeEvent) {
+        let json = serde_json::to_string(event).unwrap();
+        self.outgoing.send(WsMessage::Text(json)).expect("connection open");
+    }
+}
+
+/// PumpPortal data WebSocket stand-in, hands out each accepted connection
+pub struct MockPumpPortalWs {
+    pub url: String,
+    connections: mpsc::UnboundedReceiver<WsConnection>,
+}
+
+impl MockPumpPortalWs {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (conn_tx, connections) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let Ok(ws) = tokio_tungstenite::accept_async(stream).await else { continue };
+                let (mut write, mut read) = ws.split();
+                let (in_tx, incoming) = mpsc::unbounded_channel();
+                let (outgoing, mut out_rx) = mpsc::unbounded_channel::<WsMessage>();
+
+                tokio::spawn(async move {
+                    while let Some(Ok(msg)) = read.next().await {
+                        if let WsMessage::Text(text) = msg {
+                            let _ = in_tx.send(text);
+                        }
+                    }
+                });
+
+                tokio::spawn(async move {
+                    while let Some(msg) = out_rx.recv().await {
+                        if write.send(msg).await.is_err() {
+                            return;
+                        }
+                    }
+                    let _ = write.close().await;
+                });
+
+                if conn_tx.send(WsConnection { incoming, outgoing }).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { url, connections }
+    }
+
+    /// Wait for the client to (re)connect
+    pub async fn accept(&mut self) -> WsConnection {
+        tokio::time::timeout(WAIT, self.connections.recv())
+            .await
+            .expect("timed out waiting for client connection")
+            .expect("server stopped")
+    }
+}
+
+type Lists = Arc<Mutex<HashMap<String, VecDeque<String>>>>;
+type Refused = Arc<Mutex<Vec<String>>>;
+
+/// Redis stand-in speaking RESP, with just the list commands spectre uses
+/// (LPUSH, LLEN, LRANGE, LREM, LMOVE, BLMOVE). `stop`/`restart` simulate an outage
+pub struct MockRedis {
+    pub url: String,
+    addr: std::net::SocketAddr,
+    lists: Lists,
+    pushed: Arc<tokio::sync::Notify>,
+    refused: Refused,
+    tasks: Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>>,
+}
+
+impl MockRedis {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().unwrap();
+        let redis = Self {
+            url: format!("redis://{}/", addr),
+            addr,
+            lists: Arc::new(Mutex::new(HashMap::new())),
+            pushed: Arc::new(tokio::sync::Notify::new()),
+            refused: Arc::new(Mutex::new(Vec::new())),
+            tasks: Arc::new(Mutex::new(Vec::new())),
+        };
+        redis.serve(listener);
+        redis
+    }
+
+    /// Drop every connection and stop accepting new ones (the data is kept)
+    pub fn stop(&self) {
+        for task in self.tasks.lock().unwrap().drain(..) {
+            task.abort();
+        }
+    }
+
+    /// Accept connections again on the same address
+    pub async fn restart(&self) {
+        // The aborted accept task may not have released the port yet
+        let listener = tokio::time::timeout(WAIT, async {
+            loop {
+                match TcpListener::bind(self.addr).await {
+                    Ok(listener) => return listener,
+                    Err(_) => tokio::time::sleep(Duration::from_millis(20)).await,
+                }
+            }
+        })
+        .await
+        .expect("rebind");
+        self.serve(listener);
+    }
+
+    /// Answer a command with `ERR unknown command`, as a Redis too old to have it would
+    pub fn refuse(&self, command: &str) {
+        self.refused.lock().unwrap().push(command.to_uppercase());
+    }
+
+    /// LPUSH, as the backend does
+    pub fn push(&self, key: &str, payload: &str) {
+        self.lists.lock().unwrap().entry(key.to_string()).or_default().push_front(payload.to_string());
+        self.pushed.notify_waiters();
+    }
+
+    /// List contents, head (LEFT) first
+    pub fn list(&self, key: &str) -> Vec<String> {
+        self.lists.lock().unwrap().get(key).map(|l| l.iter().cloned().collect()).unwrap_or_default()
+    }
+
+    /// Wait until a list has `len` items
+    pub async fn wait_for_len(&self, key: &str, len: usize) -> Vec<String> {
+        tokio::time::timeout(WAIT, async {
+            loop {
+                let list = self.list(key);
+                if list.len() == len {
+                    return list;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("{} never reached {} item(s): {:?}", key, len, self.list(key)))
+    }
+
+    fn serve(&self, listener: TcpListener) {
+        let lists = self.lists.clone();
+        let pushed = self.pushed.clone();
+        let refused = self.refused.clone();
+        let tasks = self.tasks.clone();
+        let accept = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let connection = tokio::spawn(Self::connection(stream, lists.clone(), pushed.clone(), refused.clone()));
+                tasks.lock().unwrap().push(connection);
+            }
+        });
+        self.tasks.lock().unwrap().push(accept);
+    }
+
+    async fn connection(stream: tokio::net::TcpStream, lists: Lists, pushed: Arc<tokio::sync::Notify>, refused: Refused) {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let (read, mut write) = stream.into_split();
+        let mut read = BufReader::new(read);
+        loop {
+            // *<argc>\r\n then $<len>\r\n<arg>\r\n per argument
+            let mut line = String::new();
+            if read.read_line(&mut line).await.unwrap_or(0) == 0 {
+                return;
+            }
+            let argc: usize = line.trim().trim_start_matches('*').parse().unwrap_or(0);
+            let mut args = Vec::with_capacity(argc);
+            for _ in 0..argc {
+                line.clear();
+                if read.read_line(&mut line).await.unwrap_or(0) == 0 {
+                    return;
+                }
+                let len: usize = line.trim().trim_start_matches('$').parse().unwrap_or(0);
+                let mut arg = vec![0; len + 2];
+                if read.read_exact(&mut arg).await.is_err() {
+                    return;
+                }
+                arg.truncate(len);
+                args.push(String::from_utf8_lossy(&arg).into_owned());
+            }
+
+            let command = args.first().map(|c| c.to_uppercase()).unwrap_or_default();
+            let reply = if refused.lock().unwrap().contains(&command) {
+                format!("-ERR unknown command '{}', with args beginning with: \r\n", command)
+            } else {
+                Self::execute(&args, &lists, &pushed).await
+            };
+            if write.write_all(reply.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    async fn execute(args: &[String], lists: &Lists, pushed: &tokio::sync::Notify) -> String {
+        fn bulk(value: Option<String>) -> String {
+            match value {
+                Some(v) => format!("${}\r\n{}\r\n", v.len(), v),
+                None => "$-1\r\n".to_string(),
+            }
+        }
+        fn pop(lists: &Lists, key: &str, side: &str) -> Option<String> {
+            let mut lists = lists.lock().unwrap();
+            let list = lists.get_mut(key)?;
+            if side.eq_ignore_ascii_case("LEFT") { list.pop_front() } else { list.pop_back() }
+        }
+        fn push(lists: &Lists, key: &str, side: &str, value: String) {
+            let mut lists = lists.lock().unwrap();
+            let list = lists.entry(key.to_string()).or_default();
+            if side.eq_ignore_ascii_case("LEFT") { list.push_front(value) } else { list.push_back(value) }
+        }
+
+        match args[0].to_uppercase().as_str() {
+            "LPUSH" => {
+                for value in &args[2..] {
+                    push(lists, &args[1], "LEFT", value.clone());
+                }
+                pushed.notify_waiters();
+                format!(":{}\r\n", lists.lock().unwrap()[&args[1]].len())
+            }
+            "LLEN" => format!(":{}\r\n", lists.lock().unwrap().get(&args[1]).map_or(0, |l| l.len())),
+            "LRANGE" => {
+                let items: Vec<String> = lists.lock().unwrap().get(&args[1]).map(|l| l.iter().cloned().collect()).unwrap_or_default();
+                let mut reply = format!("*{}\r\n", items.len());
+                for item in items {
+                    reply.push_str(&bulk(Some(item)));
+                }
+                reply
+            }
+            "LREM" => {
+                let mut lists = lists.lock().unwrap();
+                let removed = match lists.get_mut(&args[1]).and_then(|l| Some((l.iter().position(|v| *v == args[3])?, l))) {
+                    Some((index, list)) => {
+                        list.remove(index);
+                        1
+                    }
+                    None => 0,
+                };
+                format!(":{}\r\n", removed)
+            }
+            "LMOVE" => {
+                let moved = pop(lists, &args[1], &args[3]);
+                if let Some(ref value) = moved {
+                    push(lists, &args[2], &args[4], value.clone());
+                }
+                bulk(moved)
+            }
+            "BLMOVE" => {
+                // Nil once the block timeout passes with nothing pushed (0 = block forever)
+                let block_secs: u64 = args[5].parse().unwrap_or(0);
+                let deadline = (block_secs > 0).then(|| tokio::time::Instant::now() + Duration::from_secs(block_secs));
+                loop {
+                    let notified = pushed.notified();
+                    if let Some(value) = pop(lists, &args[1], &args[3]) {
+                        push(lists, &args[2], &args[4], value.clone());
+                        break bulk(Some(value));
+                    }
+                    match deadline {
+                        Some(deadline) => {
+                            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                                break bulk(None);
+                            }
+                        }
+                        None => notified.await,
+                    }
+                }
+            }
+            "PING" => "+PONG\r\n".to_string(),
+            _ => "+OK\r\n".to_string(),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Operator commands: partial/full manual sells, pause/resume, unsellable flag, SL/TP moves, state dump
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{ChannelPublisher, Harness};
+use spectre::control::{ControlCommand, ControlHandler, ControlRequest};
+use spectre::error::ErrorCode;
+use spectre::position::Position;
+use spectre::trader::SpectreTrader;
+use tokio::sync::mpsc;
+
+async fn open_position(trader: &SpectreTrader, amount_tokens: u64) -> String {
+    let mint = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let mut position = Position::new(
+        mint.clone(),
+        "CMD".to_string(),
+        0.001,
+        amount_tokens,
+        0.1,
+        25.0,
+        50.0,
+        "entry".to_string(),
+        false,
+    );
+    position.price_synced = true;
+    trader.position_manager().add_position(position).await;
+    mint
+}
+
+fn request(json: serde_json::Value) -> ControlRequest {
+    serde_json::from_value(json).expect("valid command")
+}
+
+#[test]
+fn commands_parse_from_the_wire_format() {
+    let sell = request(serde_json::json!({ "id": "1", "command": "sell", "tokenMint": "MINT", "percent": 25 }));
+    assert!(matches!(sell.command, ControlCommand::Sell { ref token_mint, percent: Some(p) } if token_mint == "MINT" && p == 25.0));
+    assert!(sell.reply_to.is_none());
+
+    let exits = request(serde_json::json!({
+        "id": "2", "replyTo": "dash", "command": "set_exits", "tokenMint": "MINT", "stopLossPercent": 10
+    }));
+    assert_eq!(exits.reply_to.as_deref(), Some("dash"));
+    assert!(matches!(exits.command, ControlCommand::SetExits { stop_loss_percent: Some(_), take_profit_percent: None, .. }));
+
+    assert!(serde_json::from_str::<ControlRequest>(r#"{"id": "3", "command": "self_destruct"}"#).is_err());
+
+    // Sells are acked before they run (never replayed after a crash)
+    assert!(sell.command.is_destructive());
+    assert!(ControlCommand::CloseAll.is_destructive());
+    assert!(!exits.command.is_destructive());
+    assert!(!ControlCommand::Pause.is_destructive());
+}
+
+#[tokio::test]
+async fn partial_then_full_manual_sell() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let handler = ControlHandler::new(trader.clone(), Arc::new(ChannelPublisher(results_tx)));
+    let mint = open_position(&trader, 4_000_000_000).await;
+
+    // Half of it - the wallet keeps the other half
+    harness.chain.script_fill(&mint, 2_000_000_000, 60_000_000);
+    let reply = handler.handle(&request(serde_json::json!({ "id": "s1", "command": "sell", "tokenMint": mint, "percent": 50 }))).await;
+    assert!(reply.ok, "sell failed: {}", reply.message);
+    assert_eq!(reply.id, "s1");
+    assert_eq!(reply.data.as_ref().unwrap()["exitReason"], "manual");
+
+    let position = trader.position_manager().get_position(&mint).await.expect("position kept");
+    assert_eq!(position.amount_tokens, 2_000_000_000);
+    let published = results_rx.try_recv().expect("sell published");
+    assert_eq!(published.amount_tokens, Some(2_000_000_000.0));
+
+    // The rest closes it
+    harness.chain.script_fill(&mint, 0, 60_000_000);
+    let reply = handler.handle(&request(serde_json::json!({ "id": "s2", "command": "sell", "tokenMint": mint }))).await;
+    assert!(reply.ok, "sell failed: {}", reply.message);
+    assert!(!trader.position_manager().has_position(&mint).await);
+
+    // Nothing left to sell
+    let reply = handler.handle(&request(serde_json::json!({ "id": "s3", "command": "sell", "tokenMint": mint }))).await;
+    assert!(!reply.ok);
+    assert!(reply.message.contains("No position"));
+
+    let reply = handler.handle(&request(serde_json::json!({ "id": "s4", "command": "sell", "tokenMint": mint, "percent": 150 }))).await;
+    assert!(!reply.ok);
+    assert!(reply.message.contains("percent"));
+}
+
+#[tokio::test]
+async fn close_all_sells_every_position() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, _results_rx) = mpsc::unbounded_channel();
+    let handler = ControlHandler::new(trader.clone(), Arc::new(ChannelPublisher(results_tx)));
+    open_position(&trader, 1_000_000_000).await;
+    open_position(&trader, 1_000_000_000).await;
+
+    let reply = handler.handle(&request(serde_json::json!({ "id": "c1", "command": "close_all" }))).await;
+    assert!(reply.ok, "close-all failed: {}", reply.message);
+    assert_eq!(reply.message, "Closed 2 position(s)");
+    assert_eq!(trader.position_manager().position_count().await, 0);
+}
+
+#[tokio::test]
+async fn pause_skips_new_entries_until_resumed() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, _results_rx) = mpsc::unbounded_channel();
+    let handler = ControlHandler::new(trader.clone(), Arc::new(ChannelPublisher(results_tx)));
+
+    let reply = handler.handle(&request(serde_json::json!({ "id": "p1", "command": "pause" }))).await;
+    assert!(reply.ok);
+    assert!(trader.is_paused());
+
+    let signal = common::signal("consensus", None);
+    let result = trader.execute_buy(&signal).await.unwrap();
+    assert!(!result.success);
+    assert_eq!(result.error_code, Some(ErrorCode::Paused));
+    assert!(harness.jupiter.received_requests().await.unwrap().is_empty(), "nothing was quoted");
+
+    let reply = handler.handle(&request(serde_json::json!({ "id": "p2", "command": "resume" }))).await;
+    assert_eq!(reply.message, "New entries resumed");
+
+    harness.chain.script_fill(&signal.token_mint, 900_000_000_000, -100_500_000);
+    let result = trader.execute_buy(&signal).await.unwrap();
+    assert!(result.success, "buy failed: {:?}", result.error);
+}
+
+#[tokio::test]
+async fn unsellable_flag_exit_levels_and_state_dump() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, _results_rx) = mpsc::unbounded_channel();
+    let handler = ControlHandler::new(trader.clone(), Arc::new(ChannelPublisher(results_tx)));
+    let mint = open_position(&trader, 1_000_000_000).await;
+
+    let reply = handler.handle(&request(serde_json::json!({ "id": "u1", "command": "mark_unsellable", "tokenMint": mint }))).await;
+    assert!(reply.ok);
+    assert!(trader.position_manager().get_position(&mint).await.unwrap().is_unsellable);
+
+    trader.position_manager().increment_failed_sell(&mint).await;
+    let reply = handler.handle(&request(serde_json::json!({ "id": "u2", "command": "unmark_unsellable", "tokenMint": mint }))).await;
+    assert!(reply.ok);
+    let position = trader.position_manager().get_position(&mint).await.unwrap();
+    assert!(!position.is_unsellable);
+    assert_eq!(position.failed_sell_attempts, 0);
+
+    let reply = handler.handle(&request(serde_json::json!({
+        "id": "x1", "command": "set_exits", "tokenMint": mint, "stopLossPercent": 10, "takeProfitPercent": 200
+    }))).await;
+    assert!(reply.ok, "set_exits failed: {}", reply.message);
+    let position = trader.position_manager().get_position(&mint).await.unwrap();
+    assert!((position.stop_loss_price - 0.0009).abs() < 1e-12);
+    assert!((position.take_profit_price - 0.003).abs() < 1e-12);
+
+    let reply = handler.handle(&request(serde_json::json!({ "id": "x2", "command": "set_exits", "tokenMint": mint }))).await;
+    assert!(!reply.ok, "needs at least one level");
+
+    for (id, sl, tp) in [("x3", 0.0, 200.0), ("x4", 100.0, 200.0), ("x5", 10.0, -5.0)] {
+        let reply = handler.handle(&request(serde_json::json!({
+            "id": id, "command": "set_exits", "tokenMint": mint, "stopLossPercent": sl, "takeProfitPercent": tp
+        }))).await;
+        assert!(!reply.ok, "SL {} / TP {} accepted", sl, tp);
+    }
+
+    // Loosening the stop needs allowLower
+    let reply = handler.handle(&request(serde_json::json!({
+        "id": "x6", "command": "set_exits", "tokenMint": mint, "stopLossPercent": 30
+    }))).await;
+    assert!(!reply.ok, "stop lowered without allowLower");
+    assert!((trader.position_manager().get_position(&mint).await.unwrap().stop_loss_price - 0.0009).abs() < 1e-12);
+    let reply = handler.handle(&request(serde_json::json!({
+        "id": "x7", "command": "set_exits", "tokenMint": mint, "stopLossPercent": 30, "allowLower": true
+    }))).await;
+    assert!(reply.ok, "set_exits failed: {}", reply.message);
+    assert!((trader.position_manager().get_position(&mint).await.unwrap().stop_loss_price - 0.0007).abs() < 1e-12);
+
+    trader.set_paused(true);
+    let reply = handler.handle(&request(serde_json::json!({ "id": "d1", "command": "dump_state" }))).await;
+    assert!(reply.ok);
+    let data = reply.data.unwrap();
+    assert_eq!(data["paused"], true);
+    assert_eq!(data["positions"].as_array().unwrap().len(), 1);
+    assert_eq!(data["positions"][0]["token_mint"], mint.as_str());
+}
@@ -0,0 +1,109 @@
+//! Copy-exits: the position follows its trigger wallets out once N of them sold
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{ChannelPublisher, Harness};
+use spectre::advisory::{handle_wallet_sell, AdvisoryAction, AdvisoryResponse, CopyExitPolicy};
+use spectre::position::{ExitReason, Position};
+use spectre::pumpportal::WalletSell;
+use spectre::trader::SpectreTrader;
+use tokio::sync::mpsc;
+
+async fn open_position(trader: &SpectreTrader, trigger_wallets: &[String]) -> String {
+    let mint = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let mut position = Position::new(
+        mint.clone(),
+        "COPY".to_string(),
+        0.001,
+        4_000_000_000,
+        0.1,
+        25.0,
+        50.0,
+        "entry".to_string(),
+        false,
+    )
+    .with_trigger_wallets(trigger_wallets.to_vec());
+    position.price_synced = true;
+    trader.position_manager().add_position(position).await;
+    mint
+}
+
+fn wallets(count: usize) -> Vec<String> {
+    (0..count).map(|_| solana_sdk::pubkey::Pubkey::new_unique().to_string()).collect()
+}
+
+fn sell(wallet: &str, token_mint: &str) -> WalletSell {
+    WalletSell {
+        wallet: wallet.to_string(),
+        token_mint: token_mint.to_string(),
+        signature: None,
+        sol_amount: Some(1.0),
+        token_amount: Some(1_000_000.0),
+        timestamp: chrono::Utc::now().timestamp(),
+    }
+}
+
+#[tokio::test]
+async fn exits_once_n_of_m_trigger_wallets_sold() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let publisher = Arc::new(ChannelPublisher(results_tx));
+    let policy = CopyExitPolicy { min_wallets: 2, response: AdvisoryResponse::Full };
+    let trigger = wallets(3);
+    let mint = open_position(&trader, &trigger).await;
+
+    // First trigger wallet out - recorded, no exit yet
+    let action = handle_wallet_sell(&trader, publisher.as_ref(), &policy, &sell(&trigger[0], &mint)).await;
+    assert_eq!(action, AdvisoryAction::None);
+    // Repeated sells by the same wallet and sells by strangers don't count
+    handle_wallet_sell(&trader, publisher.as_ref(), &policy, &sell(&trigger[0], &mint)).await;
+    handle_wallet_sell(&trader, publisher.as_ref(), &policy, &sell(&wallets(1)[0], &mint)).await;
+    let position = trader.position_manager().get_position(&mint).await.unwrap();
+    assert_eq!(position.trigger_wallets_sold, vec![trigger[0].clone()]);
+
+    // Second one reaches 2 of 3
+    let action = handle_wallet_sell(&trader, publisher.as_ref(), &policy, &sell(&trigger[2], &mint)).await;
+    assert_eq!(action, AdvisoryAction::Exit(ExitReason::SmartWalletExit { sell_percent: 100.0 }));
+    assert!(!trader.position_manager().has_position(&mint).await);
+    let result = results_rx.try_recv().expect("sell published");
+    assert!(result.success);
+    assert_eq!(result.exit_reason.as_deref(), Some("smart_wallet_exit"));
+}
+
+#[tokio::test]
+async fn threshold_is_capped_at_the_trigger_wallet_count() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, _results_rx) = mpsc::unbounded_channel();
+    let publisher = Arc::new(ChannelPublisher(results_tx));
+    let policy = CopyExitPolicy { min_wallets: 3, response: AdvisoryResponse::Partial(50.0) };
+    let trigger = wallets(1);
+    let mint = open_position(&trader, &trigger).await;
+
+    harness.chain.script_fill(&mint, 2_000_000_000, 60_000_000);
+    let action = handle_wallet_sell(&trader, publisher.as_ref(), &policy, &sell(&trigger[0], &mint)).await;
+
+    assert_eq!(action, AdvisoryAction::Exit(ExitReason::SmartWalletExit { sell_percent: 50.0 }));
+    assert_eq!(trader.position_manager().get_position(&mint).await.unwrap().amount_tokens, 2_000_000_000);
+}
+
+#[tokio::test]
+async fn disabled_policy_only_records_sells() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let publisher = Arc::new(ChannelPublisher(results_tx));
+    let policy = CopyExitPolicy { min_wallets: 0, response: AdvisoryResponse::Full };
+    let trigger = wallets(1);
+    let mint = open_position(&trader, &trigger).await;
+
+    let action = handle_wallet_sell(&trader, publisher.as_ref(), &policy, &sell(&trigger[0], &mint)).await;
+
+    assert_eq!(action, AdvisoryAction::None);
+    let position = trader.position_manager().get_position(&mint).await.expect("position kept");
+    assert_eq!(position.trigger_wallets_sold.len(), 1);
+    assert!(results_rx.try_recv().is_err());
+}
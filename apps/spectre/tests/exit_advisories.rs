@@ -0,0 +1,137 @@
+//! Backend exit advisories: full/partial exits as SmartWalletExit / ConsensusReversed, or a tighter stop
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{ChannelPublisher, Harness};
+use spectre::advisory::{handle_advisory, AdvisoryAction, AdvisoryPolicy, AdvisoryResponse, ExitAdvisory};
+use spectre::position::{ExitReason, Position};
+use spectre::trader::SpectreTrader;
+use tokio::sync::mpsc;
+
+async fn open_position(trader: &SpectreTrader) -> String {
+    let mint = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let mut position = Position::new(
+        mint.clone(),
+        "ADV".to_string(),
+        0.001,
+        4_000_000_000,
+        0.1,
+        25.0,
+        50.0,
+        "entry".to_string(),
+        false,
+    );
+    position.price_synced = true;
+    trader.position_manager().add_position(position).await;
+    mint
+}
+
+fn advisory(token_mint: &str, kind: &str, recommendation: Option<&str>) -> ExitAdvisory {
+    serde_json::from_value(serde_json::json!({
+        "tokenMint": token_mint,
+        "type": kind,
+        "recommendation": recommendation,
+        "walletsExitedCount": 2,
+        "walletsHoldingCount": 1,
+        "triggerReason": "2 of 3 trigger wallets sold",
+    }))
+    .expect("valid advisory")
+}
+
+#[test]
+fn responses_parse_from_config() {
+    assert_eq!("full".parse::<AdvisoryResponse>().unwrap(), AdvisoryResponse::Full);
+    assert_eq!("partial:50".parse::<AdvisoryResponse>().unwrap(), AdvisoryResponse::Partial(50.0));
+    assert_eq!("Tighten:10".parse::<AdvisoryResponse>().unwrap(), AdvisoryResponse::TightenStop(10.0));
+    assert_eq!("recommended".parse::<AdvisoryResponse>().unwrap(), AdvisoryResponse::Recommended);
+    assert!("partial".parse::<AdvisoryResponse>().is_err());
+    assert!("partial:0".parse::<AdvisoryResponse>().is_err());
+    assert!("dump".parse::<AdvisoryResponse>().is_err());
+}
+
+#[tokio::test]
+async fn wallet_exit_sells_the_position() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let publisher = Arc::new(ChannelPublisher(results_tx));
+    let mint = open_position(&trader).await;
+
+    let action = handle_advisory(&trader, publisher.as_ref(), &AdvisoryPolicy::default(), &advisory(&mint, "wallet_exit", None)).await;
+
+    assert_eq!(action, AdvisoryAction::Exit(ExitReason::SmartWalletExit { sell_percent: 100.0 }));
+    assert!(!trader.position_manager().has_position(&mint).await);
+    let result = results_rx.try_recv().expect("sell published");
+    assert!(result.success);
+    assert_eq!(result.exit_reason.as_deref(), Some("smart_wallet_exit"));
+}
+
+#[tokio::test]
+async fn recommended_partial_exit_keeps_the_rest() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, _results_rx) = mpsc::unbounded_channel();
+    let publisher = Arc::new(ChannelPublisher(results_tx));
+    let mint = open_position(&trader).await;
+    let policy = AdvisoryPolicy { consensus_reversed: AdvisoryResponse::Recommended, ..AdvisoryPolicy::default() };
+
+    // Hold recommendation - nothing happens
+    let action = handle_advisory(&trader, publisher.as_ref(), &policy, &advisory(&mint, "consensus_reversed", Some("hold"))).await;
+    assert_eq!(action, AdvisoryAction::None);
+
+    // Out-of-range percents are treated as hold
+    for recommendation in ["partial_exit_0", "partial_exit_150", "partial_exit_-20", "partial_exit_NaN"] {
+        let action = handle_advisory(&trader, publisher.as_ref(), &policy, &advisory(&mint, "consensus_reversed", Some(recommendation))).await;
+        assert_eq!(action, AdvisoryAction::None, "{}", recommendation);
+    }
+
+    harness.chain.script_fill(&mint, 1_000_000_000, 60_000_000);
+    let action = handle_advisory(&trader, publisher.as_ref(), &policy, &advisory(&mint, "consensus_reversed", Some("partial_exit_75"))).await;
+    assert_eq!(action, AdvisoryAction::Exit(ExitReason::ConsensusReversed { sell_percent: 75.0 }));
+    let position = trader.position_manager().get_position(&mint).await.expect("remainder kept");
+    assert_eq!(position.amount_tokens, 1_000_000_000);
+}
+
+#[tokio::test]
+async fn consensus_reversal_tightens_the_stop() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let publisher = Arc::new(ChannelPublisher(results_tx));
+    let mint = open_position(&trader).await;
+    trader.position_manager().record_price(&mint, 0.002).await;
+
+    let action = handle_advisory(&trader, publisher.as_ref(), &AdvisoryPolicy::default(), &advisory(&mint, "consensus_reversed", None)).await;
+
+    // 10% below the last price (was 25% below entry)
+    let AdvisoryAction::StopTightened(old, new) = action else { panic!("unexpected action: {:?}", action) };
+    assert!((old - 0.00075).abs() < 1e-12);
+    assert!((new - 0.0018).abs() < 1e-12);
+    let position = trader.position_manager().get_position(&mint).await.unwrap();
+    assert!((position.stop_loss_price - 0.0018).abs() < 1e-12);
+    assert!(results_rx.try_recv().is_err(), "nothing was sold");
+
+    // Never loosens
+    trader.position_manager().record_price(&mint, 0.0015).await;
+    let action = handle_advisory(&trader, publisher.as_ref(), &AdvisoryPolicy::default(), &advisory(&mint, "consensus_reversed", None)).await;
+    assert_eq!(action, AdvisoryAction::None);
+}
+
+#[tokio::test]
+async fn unhandled_types_and_unknown_mints_are_ignored() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let (results_tx, _results_rx) = mpsc::unbounded_channel();
+    let publisher = Arc::new(ChannelPublisher(results_tx));
+    let mint = open_position(&trader).await;
+
+    let action = handle_advisory(&trader, publisher.as_ref(), &AdvisoryPolicy::default(), &advisory(&mint, "momentum_loss", Some("full_exit"))).await;
+    assert_eq!(action, AdvisoryAction::None);
+
+    let other_mint = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let action = handle_advisory(&trader, publisher.as_ref(), &AdvisoryPolicy::default(), &advisory(&other_mint, "wallet_exit", None)).await;
+    assert_eq!(action, AdvisoryAction::None);
+    assert!(trader.position_manager().has_position(&mint).await);
+}
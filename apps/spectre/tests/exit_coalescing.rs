@@ -0,0 +1,103 @@
+//! One exit per position at a time: repeated triggers coalesce, a stop-loss takes over a partial exit
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{ChannelPublisher, Harness};
+use spectre::monitor::execute_exit;
+use spectre::position::{ExitReason, Position};
+use spectre::trader::SpectreTrader;
+use tokio::sync::mpsc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+async fn open_position(trader: &SpectreTrader) -> String {
+    let mint = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let mut position = Position::new(
+        mint.clone(),
+        "DUP".to_string(),
+        0.001,
+        5_000_000_000,
+        0.1,
+        25.0,
+        50.0,
+        "entry".to_string(),
+        false,
+    );
+    position.price_synced = true;
+    trader.position_manager().add_position(position).await;
+    mint
+}
+
+async fn quote_requests(harness: &Harness) -> usize {
+    harness
+        .jupiter
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == "/quote")
+        .count()
+}
+
+#[tokio::test]
+async fn concurrent_triggers_sell_once() {
+    let harness = Harness::start().await;
+    common::mount_jupiter_quote(&harness.jupiter, common::no_route().set_delay(Duration::from_millis(300))).await;
+    let trader = harness.trader();
+    let mint = open_position(&trader).await;
+
+    let (results_tx, _results_rx) = mpsc::unbounded_channel();
+    let publisher = Arc::new(ChannelPublisher(results_tx));
+    tokio::join!(
+        execute_exit(&trader, publisher.as_ref(), &mint, ExitReason::StopLoss),
+        execute_exit(&trader, publisher.as_ref(), &mint, ExitReason::StopLoss),
+    );
+
+    assert_eq!(quote_requests(&harness).await, 1, "second trigger is coalesced into the running exit");
+    let position = trader.position_manager().get_position(&mint).await.unwrap();
+    assert_eq!(position.failed_sell_attempts, 1);
+    assert!(trader.position_manager().pending_exit(&mint).await.is_none());
+}
+
+#[tokio::test]
+async fn stop_loss_supersedes_running_partial_exit() {
+    let harness = Harness::start().await;
+    common::mount_jupiter_quote(&harness.jupiter, common::no_route()).await;
+    // The partial exit's first quote is slow and fails retryably
+    Mock::given(method("GET"))
+        .and(path("/quote"))
+        .respond_with(ResponseTemplate::new(500).set_delay(Duration::from_millis(300)))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&harness.jupiter)
+        .await;
+    let trader = harness.trader();
+    let mint = open_position(&trader).await;
+
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let publisher = Arc::new(ChannelPublisher(results_tx));
+    let take_profit = ExitReason::ScaledTakeProfit { stage: 1, sell_percent: 80.0, trigger_percent: 30.0 };
+
+    let partial = {
+        let (trader, publisher, mint) = (trader.clone(), publisher.clone(), mint.clone());
+        tokio::spawn(async move { execute_exit(&trader, publisher.as_ref(), &mint, take_profit).await })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Queued behind the partial exit, returns right away
+    execute_exit(&trader, publisher.as_ref(), &mint, ExitReason::StopLoss).await;
+    assert!(trader.position_manager().pending_exit(&mint).await.is_some_and(|r| r.is_partial()));
+    assert!(trader.position_manager().is_exit_superseded(&mint).await);
+
+    tokio::time::timeout(common::WAIT, partial).await.unwrap().unwrap();
+
+    // Partial exit gave up after its first attempt, the stop-loss then ran on its own quote
+    assert_eq!(quote_requests(&harness).await, 2);
+    let position = trader.position_manager().get_position(&mint).await.unwrap();
+    assert_eq!(position.failed_sell_attempts, 1, "only the stop-loss hit the missing route");
+    assert!(trader.position_manager().pending_exit(&mint).await.is_none());
+    assert!(results_rx.try_recv().is_err(), "nothing was sent, nothing to publish");
+}
@@ -0,0 +1,117 @@
+//! Quote errors, Jito rejections and unroutable sells against local mock services
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{ChannelPublisher, Harness, Route};
+use spectre::error::ErrorCode;
+use spectre::monitor::execute_exit;
+use spectre::position::{ExitReason, Position};
+use tokio::sync::mpsc;
+use wiremock::ResponseTemplate;
+
+#[tokio::test]
+async fn jupiter_quote_error_fails_buy_without_sending() {
+    let harness = Harness::start().await;
+    common::mount_jupiter_quote(
+        &harness.jupiter,
+        ResponseTemplate::new(500).set_body_string("upstream timeout"),
+    )
+    .await;
+    let trader = harness.trader();
+
+    let signal = common::signal("consensus", None);
+    let result = trader.execute_buy(&signal).await.unwrap();
+
+    assert!(!result.success);
+    assert_eq!(result.error_code, Some(ErrorCode::VenueHttp));
+    let error = result.error.unwrap();
+    assert!(error.contains("Jupiter quote failed"), "unexpected error: {}", error);
+    assert!(error.contains("upstream timeout"));
+    assert!(trader.position_manager().get_position(&signal.token_mint).await.is_none());
+    assert!(harness.chain.routes().is_empty(), "nothing may be submitted without a quote");
+}
+
+#[tokio::test]
+async fn no_route_buy_is_not_retried() {
+    let harness = Harness::start().await;
+    common::mount_jupiter_quote(&harness.jupiter, common::no_route()).await;
+    let trader = harness.trader();
+
+    let result = trader.execute_buy(&common::signal("consensus", None)).await.unwrap();
+
+    assert!(!result.success);
+    assert_eq!(result.error_code, Some(ErrorCode::NoRoute));
+    assert_eq!(result.attempt_number, 1);
+    assert_eq!(harness.jupiter.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn landed_but_failed_buy_reports_slippage() {
+    let harness = Harness::start().await;
+    // pump.fun TooMuchSolRequired
+    harness.chain.fail_transactions(serde_json::json!({ "InstructionError": [3, { "Custom": 6002 }] }));
+    let trader = harness.trader();
+
+    let signal = common::signal("ninja", Some(0.00002));
+    let result = trader.execute_buy(&signal).await.unwrap();
+
+    assert!(!result.success);
+    assert_eq!(result.error_code, Some(ErrorCode::SlippageExceeded));
+    assert!(result.tx_signature.is_some());
+    assert_eq!(result.attempt_number, 2, "slippage failures are retried");
+    assert!(!trader.position_manager().has_position(&signal.token_mint).await);
+}
+
+#[tokio::test]
+async fn jito_rejection_falls_back_to_rpc() {
+    let harness = Harness::start().await;
+    harness.chain.reject_bundles(true);
+    let trader = harness.trader();
+
+    let signal = common::signal("ninja", Some(0.00002));
+    harness.chain.script_fill(&signal.token_mint, 1_000_000_000_000, -100_500_000);
+
+    let result = trader.execute_buy(&signal).await.unwrap();
+
+    assert!(result.success, "buy failed: {:?}", result.error);
+    assert!(result.bundle_id.is_none(), "bundle was rejected");
+    assert!(result.tx_signature.is_some());
+    assert_eq!(result.attempt_number, 1);
+    assert_eq!(harness.chain.routes(), vec![Route::Rpc]);
+    assert!(trader.position_manager().has_position(&signal.token_mint).await);
+}
+
+#[tokio::test]
+async fn no_route_sell_counts_as_failed_sell() {
+    let harness = Harness::start().await;
+    common::mount_jupiter_quote(&harness.jupiter, common::no_route()).await;
+    let trader = harness.trader();
+
+    let mint = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let mut position = Position::new(
+        mint.clone(),
+        "RUG".to_string(),
+        0.001,
+        5_000_000_000,
+        0.1,
+        25.0,
+        50.0,
+        "entry".to_string(),
+        false,
+    );
+    position.price_synced = true;
+    trader.position_manager().add_position(position).await;
+
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let publisher = Arc::new(ChannelPublisher(results_tx));
+    execute_exit(&trader, publisher.as_ref(), &mint, ExitReason::StopLoss).await;
+
+    let position = trader.position_manager().get_position(&mint).await.expect("position kept");
+    assert_eq!(position.failed_sell_attempts, 1);
+    assert_eq!(harness.jupiter.received_requests().await.unwrap().len(), 1, "no route is not retried");
+    assert!(!position.is_unsellable);
+    assert!(results_rx.try_recv().is_err(), "nothing was sent, nothing to publish");
+    assert!(harness.chain.routes().is_empty());
+}
@@ -0,0 +1,167 @@
+//! Buy size from the sizing policy, carried into the venue order, position and result
+
+mod common;
+
+use common::Harness;
+use spectre::redis::{SignalWallet, SpectrePreSignal};
+use spectre::sizing::{SizingInput, SizingMode, SizingPolicy};
+
+fn wallet(score: f64) -> SignalWallet {
+    SignalWallet { address: solana_sdk::pubkey::Pubkey::new_unique().to_string(), label: None, score: Some(score) }
+}
+
+/// `amount` (lamports) of the Jupiter quote requests
+async fn quoted_lamports(harness: &Harness) -> Vec<u64> {
+    harness
+        .jupiter
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == "/quote")
+        .filter_map(|r| r.url.query_pairs().find(|(k, _)| k == "amount").and_then(|(_, v)| v.parse().ok()))
+        .collect()
+}
+
+#[test]
+fn score_modes_scale_the_base_size() {
+    let policy = SizingPolicy { mode: SizingMode::ScoreMean, ..SizingPolicy::fixed(0.1) };
+    let input = SizingInput { wallet_scores: vec![20.0, 80.0], ..Default::default() };
+    assert!((policy.size(&input).amount_sol - 0.1).abs() < 1e-12);
+
+    let policy = SizingPolicy { mode: SizingMode::ScoreMax, ..policy };
+    assert!((policy.size(&input).amount_sol - 0.16).abs() < 1e-12);
+
+    // Nothing to size by - base size
+    let size = policy.size(&SizingInput::default());
+    assert_eq!(size.amount_sol, 0.1);
+    assert_eq!(size.basis, "fixed (no wallet scores)");
+}
+
+#[tokio::test]
+async fn strength_tier_sizes_the_jupiter_buy_and_position() {
+    let mut harness = Harness::start().await;
+    harness.config.sizing = SizingPolicy { mode: SizingMode::Strength, strong_sol: 0.2, ..SizingPolicy::fixed(0.1) };
+    let trader = harness.trader();
+
+    let signal = common::signal("consensus", None);
+    harness.chain.script_fill(&signal.token_mint, 900_000_000_000, -200_500_000);
+    let result = trader.execute_buy(&signal).await.unwrap();
+
+    assert!(result.success, "buy failed: {:?}", result.error);
+    assert_eq!(result.amount_sol, 0.2);
+    assert_eq!(quoted_lamports(&harness).await, vec![200_000_000]);
+    let position = trader.position_manager().get_position(&signal.token_mint).await.unwrap();
+    assert_eq!(position.amount_sol_invested, 0.2);
+}
+
+#[tokio::test]
+async fn wallet_score_size_is_capped() {
+    let mut harness = Harness::start().await;
+    harness.config.sizing = SizingPolicy { mode: SizingMode::ScoreMax, max_sol: 0.15, ..SizingPolicy::fixed(0.1) };
+    let trader = harness.trader();
+
+    // Best wallet 90 -> 0.18 SOL, capped at 0.15
+    let mut signal = common::signal("consensus", None);
+    signal.wallets = vec![wallet(20.0), wallet(90.0)];
+    harness.chain.script_fill(&signal.token_mint, 900_000_000_000, -150_500_000);
+    let result = trader.execute_buy(&signal).await.unwrap();
+
+    assert!(result.success, "buy failed: {:?}", result.error);
+    assert_eq!(result.amount_sol, 0.15);
+    assert_eq!(quoted_lamports(&harness).await, vec![150_000_000]);
+}
+
+#[tokio::test]
+async fn signal_amount_overrides_the_policy() {
+    let mut harness = Harness::start().await;
+    harness.config.sizing = SizingPolicy { mode: SizingMode::Strength, strong_sol: 0.2, ..SizingPolicy::fixed(0.1) };
+    let trader = harness.trader();
+
+    let mut signal = common::signal("consensus", None);
+    signal.amount_sol = Some(0.07);
+    harness.chain.script_fill(&signal.token_mint, 900_000_000_000, -70_500_000);
+    let result = trader.execute_buy(&signal).await.unwrap();
+
+    assert!(result.success, "buy failed: {:?}", result.error);
+    assert_eq!(result.amount_sol, 0.07);
+    assert_eq!(quoted_lamports(&harness).await, vec![70_000_000]);
+    assert_eq!(trader.position_manager().get_position(&signal.token_mint).await.unwrap().amount_sol_invested, 0.07);
+}
+
+#[test]
+fn overrides_need_a_max_size() {
+    // Capped at the default max (3x the base)
+    let policy = SizingPolicy::fixed(0.1);
+    let input = SizingInput { amount_sol: Some(5.0), ..Default::default() };
+    assert!((policy.size(&input).amount_sol - 0.3).abs() < 1e-12);
+
+    // Uncapped - the payload can't pick the size
+    let policy = SizingPolicy { max_sol: 0.0, ..policy };
+    let size = policy.size(&input);
+    assert_eq!(size.amount_sol, 0.1);
+    assert!(size.basis.contains("ignored"), "{}", size.basis);
+}
+
+#[test]
+fn caps_must_be_consistent() {
+    assert!(SizingPolicy::fixed(0.1).validate().is_ok());
+    assert!(SizingPolicy { min_sol: 0.5, max_sol: 0.2, ..SizingPolicy::fixed(0.1) }.validate().is_err());
+    assert!(SizingPolicy { min_sol: 0.5, max_sol: 0.0, ..SizingPolicy::fixed(0.1) }.validate().is_ok());
+    assert!(SizingPolicy { min_sol: -0.1, ..SizingPolicy::fixed(0.1) }.validate().is_err());
+}
+
+/// pump.fun transactions built so far (prepared + at buy time)
+async fn pumpportal_builds(harness: &Harness) -> usize {
+    harness.pumpportal.received_requests().await.unwrap().len()
+}
+
+fn pre_signal(token_mint: &str, strength: Option<&str>) -> SpectrePreSignal {
+    serde_json::from_value(serde_json::json!({
+        "tokenMint": token_mint,
+        "tokenSymbol": "FAST",
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "firstWallet": { "address": solana_sdk::pubkey::Pubkey::new_unique().to_string(), "score": 60.0 },
+        "strength": strength,
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn prepared_tx_is_reused_under_strength_sizing() {
+    let mut harness = Harness::start().await;
+    harness.config.sizing = SizingPolicy { mode: SizingMode::Strength, strong_sol: 0.2, ..SizingPolicy::fixed(0.1) };
+    let trader = harness.trader();
+
+    let mut signal = common::signal("ninja", Some(0.00002));
+    signal.strength = "STRONG".to_string();
+    trader.prepare_tx_for_presignal(&pre_signal(&signal.token_mint, Some("STRONG"))).await;
+    assert_eq!(trader.prepared_tx_cache().get(&signal.token_mint).await.unwrap().amount_sol, 0.2);
+
+    harness.chain.script_fill(&signal.token_mint, 900_000_000_000, -200_500_000);
+    let result = trader.execute_buy(&signal).await.unwrap();
+
+    assert!(result.success, "buy failed: {:?}", result.error);
+    assert_eq!(result.amount_sol, 0.2);
+    assert_eq!(pumpportal_builds(&harness).await, 1, "the prepared TX was sent");
+}
+
+#[tokio::test]
+async fn prepared_tx_of_another_size_is_rebuilt() {
+    let mut harness = Harness::start().await;
+    harness.config.sizing = SizingPolicy { mode: SizingMode::Strength, strong_sol: 0.2, ..SizingPolicy::fixed(0.1) };
+    let trader = harness.trader();
+
+    // Strength unknown at pre-signal time - prepared at the base size
+    let mut signal = common::signal("ninja", Some(0.00002));
+    signal.strength = "STRONG".to_string();
+    trader.prepare_tx_for_presignal(&pre_signal(&signal.token_mint, None)).await;
+
+    harness.chain.script_fill(&signal.token_mint, 900_000_000_000, -200_500_000);
+    let result = trader.execute_buy(&signal).await.unwrap();
+
+    assert!(result.success, "buy failed: {:?}", result.error);
+    assert_eq!(result.amount_sol, 0.2);
+    assert_eq!(pumpportal_builds(&harness).await, 2);
+    assert!(trader.prepared_tx_cache().get(&signal.token_mint).await.is_none());
+}
@@ -0,0 +1,77 @@
+//! PumpPortal WebSocket client against a local server: reconnects, wallet (account trade) subscriptions
+
+mod common;
+
+use common::{MockPumpPortalWs, WAIT};
+use solana_sdk::pubkey::Pubkey;
+use spectre::pumpportal::{PumpPortalClient, TradeEvent};
+
+const SOL_USD: f64 = 150.0;
+
+#[tokio::test]
+async fn reconnects_and_resubscribes_after_disconnect() {
+    let mut ws = MockPumpPortalWs::start().await;
+    let mut client = PumpPortalClient::with_ws_url(&ws.url);
+    let mut price_rx = client.start(SOL_USD).await.unwrap();
+
+    let mint = Pubkey::new_unique().to_string();
+    let mut conn = ws.accept().await;
+    client.subscribe_token(&mint).await.unwrap();
+
+    let subscribe = conn.next_text().await;
+    assert_eq!(subscribe["method"], "subscribeTokenTrade");
+    assert_eq!(subscribe["keys"], serde_json::json!([mint]));
+
+    conn.send_trade(&common::trade_event(&mint, 0.000001));
+    let update = tokio::time::timeout(WAIT, price_rx.recv()).await.unwrap().unwrap();
+    assert_eq!(update.token_mint, mint);
+    assert!((update.price_usd - 0.000001 * SOL_USD).abs() < 1e-12);
+
+    // Server goes away - the client reconnects and subscribes again on its own
+    drop(conn);
+    let mut conn = ws.accept().await;
+
+    let resubscribe = conn.next_text().await;
+    assert_eq!(resubscribe["method"], "subscribeTokenTrade");
+    assert_eq!(resubscribe["keys"], serde_json::json!([mint]));
+
+    conn.send_trade(&common::trade_event(&mint, 0.000002));
+    let update = tokio::time::timeout(WAIT, price_rx.recv()).await.unwrap().unwrap();
+    assert!((update.price_usd - 0.000002 * SOL_USD).abs() < 1e-12);
+    assert_eq!(client.get_price(&mint).await, Some(update.price_usd));
+}
+
+#[tokio::test]
+async fn surfaces_sells_by_subscribed_wallets() {
+    let mut ws = MockPumpPortalWs::start().await;
+    let mut client = PumpPortalClient::with_ws_url(&ws.url);
+    let mut price_rx = client.start(SOL_USD).await.unwrap();
+    let mut sell_rx = client.take_wallet_sells().expect("wallet sells");
+
+    let wallet = Pubkey::new_unique().to_string();
+    let mut conn = ws.accept().await;
+    client.subscribe_accounts(std::slice::from_ref(&wallet)).await.unwrap();
+
+    let subscribe = conn.next_text().await;
+    assert_eq!(subscribe["method"], "subscribeAccountTrade");
+    assert_eq!(subscribe["keys"], serde_json::json!([wallet]));
+
+    // The wallet buys then sells a token we never subscribed to
+    let mint = Pubkey::new_unique().to_string();
+    let mut buy = common::trade_event(&mint, 0.000001);
+    buy.trader_public_key = Some(wallet.clone());
+    buy.tx_type = Some("buy".to_string());
+    conn.send_trade(&buy);
+    let sell = TradeEvent { tx_type: Some("sell".to_string()), signature: Some("sig".to_string()), ..buy };
+    conn.send_trade(&sell);
+
+    let surfaced = tokio::time::timeout(WAIT, sell_rx.recv()).await.unwrap().unwrap();
+    assert_eq!(surfaced.wallet, wallet);
+    assert_eq!(surfaced.token_mint, mint);
+    assert_eq!(surfaced.signature.as_deref(), Some("sig"));
+    assert!(sell_rx.try_recv().is_err(), "the buy is not surfaced");
+
+    // Not a subscribed token - no price
+    assert!(price_rx.try_recv().is_err());
+    assert_eq!(client.get_price(&mint).await, None);
+}
@@ -0,0 +1,120 @@
+//! Wallet reconciliation: dropped positions are closed in the journal, currency mints are never adopted
+
+mod common;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use common::Harness;
+use spectre::jupiter::SOL_MINT;
+use spectre::position::{ExitReason, Position};
+use spectre::price::PriceSource;
+use spectre::trader::SpectreTrader;
+
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+/// Every token is worth $1
+struct FlatPrice;
+
+#[async_trait]
+impl PriceSource for FlatPrice {
+    async fn get_price(&self, _token_mint: &str) -> Result<f64> {
+        Ok(1.0)
+    }
+}
+
+/// Tracked position old enough to be reconciled
+async fn open_position(trader: &SpectreTrader) -> String {
+    let mint = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let mut position = Position::new(
+        mint.clone(),
+        "GONE".to_string(),
+        0.001,
+        4_000_000_000,
+        0.1,
+        25.0,
+        50.0,
+        "entry".to_string(),
+        false,
+    );
+    position.entry_time = chrono::Utc::now() - chrono::Duration::minutes(10);
+    trader.position_manager().add_position(position).await;
+    mint
+}
+
+#[tokio::test]
+async fn zero_balance_positions_are_closed_in_the_journal() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let mint = open_position(&trader).await;
+
+    let report = trader.reconcile_positions(&FlatPrice).await.expect("reconcile");
+
+    assert_eq!(report.dropped, vec![mint.clone()]);
+    assert!(!trader.position_manager().has_position(&mint).await);
+
+    trader.journal().flush().await;
+    let db = rusqlite::Connection::open(&harness.config.journal_db).expect("journal");
+    let (reason, proceeds, pnl): (String, f64, f64) = db
+        .query_row(
+            "SELECT reason, proceeds_sol, realized_pnl_sol FROM exits WHERE token_mint = ?1",
+            [&mint],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .expect("exit recorded");
+    assert_eq!(reason, "reconciled_zero_balance");
+    assert_eq!(proceeds, 0.0);
+    assert!((pnl + 0.1).abs() < 1e-12, "whole cost basis lost: {}", pnl);
+    let open: i64 = db
+        .query_row("SELECT COUNT(*) FROM positions WHERE token_mint = ?1 AND closed_at IS NULL", [&mint], |row| row.get(0))
+        .unwrap();
+    assert_eq!(open, 0);
+}
+
+#[tokio::test]
+async fn currency_mints_are_never_adopted() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    harness.chain.set_holding(USDC_MINT, 500_000_000);
+    harness.chain.set_holding(SOL_MINT, 2_000_000_000);
+    let meme = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    harness.chain.set_holding(&meme, 50_000_000);
+
+    let report = trader.reconcile_positions(&FlatPrice).await.expect("reconcile");
+
+    let adopted: Vec<_> = report.adopted.iter().map(|p| p.token_mint.clone()).collect();
+    assert_eq!(adopted, vec![meme]);
+    assert!(!trader.position_manager().has_position(USDC_MINT).await);
+    assert!(!trader.position_manager().has_position(SOL_MINT).await);
+    assert!(report.flagged.is_empty());
+}
+
+#[tokio::test]
+async fn positions_with_a_pending_exit_are_left_alone() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    let sold_out = open_position(&trader).await;
+    let selling = open_position(&trader).await;
+
+    // Sells confirming: one balance already at zero, the other part-way down
+    harness.chain.set_holding(&selling, 1_000_000_000);
+    trader.position_manager().begin_exit(&sold_out, &ExitReason::StopLoss).await;
+    trader.position_manager().begin_exit(&selling, &ExitReason::TakeProfit).await;
+
+    let report = trader.reconcile_positions(&FlatPrice).await.expect("reconcile");
+
+    assert!(report.dropped.is_empty(), "dropped mid-exit: {:?}", report.dropped);
+    assert!(report.resynced.is_empty(), "resized mid-exit: {:?}", report.resynced);
+    assert!(report.adopted.is_empty());
+    assert!(trader.position_manager().has_position(&sold_out).await);
+    assert_eq!(trader.position_manager().get_position(&selling).await.unwrap().amount_tokens, 4_000_000_000);
+
+    trader.journal().flush().await;
+    let db = rusqlite::Connection::open(&harness.config.journal_db).expect("journal");
+    let exits: i64 = db.query_row("SELECT COUNT(*) FROM exits", [], |row| row.get(0)).unwrap();
+    assert_eq!(exits, 0, "the exit path journals the close");
+
+    // Once the exit is done (here: it failed and released the slot) reconcile takes over again
+    trader.position_manager().finish_exit(&sold_out).await;
+    let report = trader.reconcile_positions(&FlatPrice).await.expect("reconcile");
+    assert_eq!(report.dropped, vec![sold_out]);
+}
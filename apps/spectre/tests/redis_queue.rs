@@ -0,0 +1,126 @@
+//! Reliable Redis queues against a local RESP server: ack after handling, redelivery of
+//! unacked items on restart, results buffered while Redis is down
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockRedis, WAIT};
+use spectre::control::CommandReply;
+use spectre::redis::{processing_key, RedisListener, ResultPublisher, TradeResult};
+
+fn result(token_symbol: &str) -> TradeResult {
+    serde_json::from_value(serde_json::json!({
+        "success": true,
+        "tokenMint": "MINT",
+        "tokenSymbol": token_symbol,
+        "action": "buy",
+        "amountSol": 0.1,
+        "latencyMs": 100,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "attemptNumber": 1,
+    }))
+    .unwrap()
+}
+
+fn signal_payload(token_symbol: &str) -> String {
+    let mut signal = common::signal("consensus", None);
+    signal.token_symbol = token_symbol.to_string();
+    serde_json::to_string(&signal).unwrap()
+}
+
+#[tokio::test]
+async fn signals_stay_in_the_processing_list_until_acked() {
+    let redis = MockRedis::start().await;
+    let listener = RedisListener::new(&redis.url, "signals").unwrap();
+    let mut signal_rx = listener.subscribe().await.unwrap();
+
+    redis.push("signals", &signal_payload("ACK"));
+    let signal = tokio::time::timeout(WAIT, signal_rx.recv()).await.unwrap().unwrap();
+    assert_eq!(signal.token_symbol, "ACK");
+    assert!(redis.list("signals").is_empty());
+    assert_eq!(redis.list(&processing_key("signals")).len(), 1, "held until acked");
+
+    signal.ack().await;
+    assert!(redis.list(&processing_key("signals")).is_empty());
+}
+
+#[tokio::test]
+async fn unacked_items_are_redelivered_in_order_on_restart() {
+    let redis = MockRedis::start().await;
+    // A previous run popped OLD then NEW and never acked them; FRESH arrived since
+    redis.push(&processing_key("signals"), &signal_payload("OLD"));
+    redis.push(&processing_key("signals"), &signal_payload("NEW"));
+    redis.push("signals", &signal_payload("FRESH"));
+
+    let listener = RedisListener::new(&redis.url, "signals").unwrap();
+    let mut signal_rx = listener.subscribe().await.unwrap();
+
+    let mut symbols = Vec::new();
+    for _ in 0..3 {
+        let signal = tokio::time::timeout(WAIT, signal_rx.recv()).await.unwrap().unwrap();
+        symbols.push(signal.token_symbol.clone());
+        signal.ack().await;
+    }
+    assert_eq!(symbols, ["OLD", "NEW", "FRESH"]);
+    assert!(redis.list(&processing_key("signals")).is_empty());
+}
+
+#[tokio::test]
+async fn invalid_commands_are_rejected_and_dropped() {
+    let redis = MockRedis::start().await;
+    let listener = RedisListener::new(&redis.url, "signals").unwrap();
+    let _command_rx = listener.subscribe_commands("commands", "replies").await.unwrap();
+
+    redis.push("commands", r#"{"id": "bad", "command": "self_destruct"}"#);
+
+    let replies = redis.wait_for_len("replies", 1).await;
+    let reply: CommandReply = serde_json::from_str(&replies[0]).unwrap();
+    assert_eq!(reply.id, "bad");
+    assert!(!reply.ok);
+    redis.wait_for_len(&processing_key("commands"), 0).await;
+}
+
+#[tokio::test]
+async fn results_are_buffered_while_redis_is_down() {
+    let redis = MockRedis::start().await;
+    let listener = RedisListener::new(&redis.url, "signals").unwrap();
+    let outbox = listener.outbox();
+    tokio::spawn(outbox.clone().run());
+
+    outbox.publish_trade_result(&result("FIRST")).await.unwrap();
+    redis.wait_for_len("spectre_trade_results", 1).await;
+
+    redis.stop();
+    // Publishing only queues - it returns right away even with Redis gone
+    tokio::time::timeout(Duration::from_millis(100), outbox.publish_trade_result(&result("SECOND")))
+        .await
+        .expect("publishing waited on Redis")
+        .unwrap();
+    assert_eq!(outbox.pending(), 1);
+
+    redis.restart().await;
+    redis.wait_for_len("spectre_trade_results", 2).await;
+    assert_eq!(outbox.pending(), 0);
+
+    let published: Vec<String> = redis
+        .list("spectre_trade_results")
+        .iter()
+        .map(|r| serde_json::from_str::<TradeResult>(r).unwrap().token_symbol)
+        .collect();
+    assert_eq!(published, ["SECOND", "FIRST"]);
+}
+
+#[tokio::test]
+async fn command_replies_go_out_without_a_queue_connection() {
+    let redis = MockRedis::start().await;
+    let listener = RedisListener::new(&redis.url, "signals").unwrap();
+    let outbox = listener.outbox();
+
+    let reply = CommandReply::rejected("cmd-1", "nope");
+    outbox.publish_command_reply("replies", &reply).unwrap();
+    assert_eq!(outbox.flush().await.unwrap(), 1);
+
+    let replies = redis.list("replies");
+    assert_eq!(serde_json::from_str::<CommandReply>(&replies[0]).unwrap().id, "cmd-1");
+}
@@ -0,0 +1,135 @@
+//! Supervised Redis listeners: reconnect with backoff, report their state, give up after the max downtime
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockRedis, WAIT};
+use spectre::redis::{ConnectionState, RedisHealth, RedisListener};
+
+fn signal_payload(token_symbol: &str) -> String {
+    let mut signal = common::signal("consensus", None);
+    signal.token_symbol = token_symbol.to_string();
+    serde_json::to_string(&signal).unwrap()
+}
+
+async fn wait_for_state(health: &RedisHealth, queue_name: &str, matches: impl Fn(&ConnectionState) -> bool) {
+    tokio::time::timeout(WAIT, async {
+        while !health.states().get(queue_name).is_some_and(&matches) {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("{} never reached the expected state: {:?}", queue_name, health.states()));
+}
+
+#[tokio::test]
+async fn listener_reconnects_after_an_outage() {
+    let redis = MockRedis::start().await;
+    let listener = RedisListener::new(&redis.url, "signals").unwrap();
+    let health = listener.health();
+    let mut signal_rx = listener.subscribe().await.unwrap();
+    wait_for_state(&health, "signals", |state| *state == ConnectionState::Connected).await;
+
+    redis.stop();
+    wait_for_state(&health, "signals", |state| matches!(state, ConnectionState::Reconnecting { .. })).await;
+    assert!(!health.is_healthy());
+
+    redis.restart().await;
+    wait_for_state(&health, "signals", |state| *state == ConnectionState::Connected).await;
+    assert!(health.is_healthy());
+
+    redis.push("signals", &signal_payload("BACK"));
+    let signal = tokio::time::timeout(WAIT, signal_rx.recv()).await.unwrap().unwrap();
+    assert_eq!(signal.token_symbol, "BACK");
+    signal.ack().await;
+    redis.wait_for_len("signals:processing", 0).await;
+}
+
+#[tokio::test]
+async fn fresh_listener_is_connecting_until_a_blmove_is_answered() {
+    let redis = MockRedis::start().await;
+    let listener = RedisListener::new(&redis.url, "signals").unwrap();
+    let health = listener.health();
+    let mut signal_rx = listener.subscribe().await.unwrap();
+
+    // Connected and blocked in BLMOVE on the empty queue - nothing answered yet
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(health.states().get("signals"), Some(&ConnectionState::Connecting));
+    assert!(!health.is_healthy());
+
+    redis.push("signals", &signal_payload("FIRST"));
+    let signal = tokio::time::timeout(WAIT, signal_rx.recv()).await.unwrap().unwrap();
+    assert_eq!(signal.token_symbol, "FIRST");
+    wait_for_state(&health, "signals", |state| *state == ConnectionState::Connected).await;
+    assert!(health.is_healthy());
+}
+
+#[tokio::test]
+async fn listener_started_while_redis_is_down_connects_later() {
+    let redis = MockRedis::start().await;
+    redis.stop();
+
+    // Building the listener needs no Redis
+    let listener = RedisListener::new(&redis.url, "signals").unwrap();
+    let health = listener.health();
+    let mut signal_rx = listener.subscribe().await.unwrap();
+    wait_for_state(&health, "signals", |state| matches!(state, ConnectionState::Reconnecting { attempts, .. } if *attempts >= 1)).await;
+
+    redis.push("signals", &signal_payload("LATE"));
+    redis.restart().await;
+    let signal = tokio::time::timeout(WAIT, signal_rx.recv()).await.unwrap().unwrap();
+    assert_eq!(signal.token_symbol, "LATE");
+}
+
+#[tokio::test]
+async fn gives_up_once_redis_stays_down_past_the_max_downtime() {
+    let redis = MockRedis::start().await;
+    let listener = RedisListener::new(&redis.url, "signals")
+        .unwrap()
+        .with_max_downtime(Some(Duration::from_secs(1)));
+    let health = listener.health();
+    let _signal_rx = listener.subscribe().await.unwrap();
+    wait_for_state(&health, "signals", |state| *state == ConnectionState::Connected).await;
+
+    redis.stop();
+    let reason = tokio::time::timeout(WAIT, health.fatal()).await.expect("never gave up");
+    assert!(reason.contains("Redis unavailable"), "{}", reason);
+    assert!(!health.states().contains_key("signals"), "the listener is gone");
+}
+
+#[tokio::test]
+async fn refused_blmove_is_an_outage() {
+    let redis = MockRedis::start().await;
+    redis.refuse("BLMOVE");
+    let listener = RedisListener::new(&redis.url, "signals")
+        .unwrap()
+        .with_max_downtime(Some(Duration::from_secs(1)));
+    let health = listener.health();
+    let _signal_rx = listener.subscribe().await.unwrap();
+
+    // Connections are made, but no BLMOVE is ever answered - never reported healthy
+    let sampled = health.clone();
+    let sampler = tokio::spawn(async move {
+        let mut seen = Vec::new();
+        loop {
+            match sampled.states().get("signals") {
+                Some(state) => seen.push(state.clone()),
+                None if seen.is_empty() => {} // listener not started yet
+                None => return seen,          // gave up
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    });
+
+    wait_for_state(&health, "signals", |state| {
+        matches!(state, ConnectionState::Reconnecting { error, .. } if error.contains("Redis 6.2+ required"))
+    })
+    .await;
+    assert!(!health.is_healthy());
+
+    let reason = tokio::time::timeout(WAIT, health.fatal()).await.expect("never gave up");
+    assert!(reason.contains("Redis unavailable"), "{}", reason);
+    let seen = sampler.await.unwrap();
+    assert!(!seen.contains(&ConnectionState::Connected), "{:?}", seen);
+}
@@ -0,0 +1,147 @@
+//! Portfolio risk limits refusing buys before anything is quoted or sent
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{ChannelPublisher, Harness};
+use spectre::dispatch::SignalDispatcher;
+use spectre::error::ErrorCode;
+use spectre::position::{ExitReason, Position};
+use spectre::pumpportal::PumpPortalClient;
+use spectre::trader::SpectreTrader;
+use tokio::sync::mpsc;
+
+fn open_position(mint: &str, sol: f64) -> Position {
+    let mut position = Position::new(mint.to_string(), "HELD".to_string(), 0.0001, 1_000_000_000, sol, 25.0, 50.0, "entry".to_string(), false);
+    position.price_synced = true;
+    position
+}
+
+#[tokio::test]
+async fn max_open_positions_rejection_is_published() {
+    let mut harness = Harness::start().await;
+    harness.config.max_open_positions = 1;
+    let trader = harness.trader();
+    let held = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    trader.position_manager().add_position(open_position(&held, 0.1)).await;
+
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let pumpportal = Arc::new(PumpPortalClient::with_ws_url("ws://127.0.0.1:1"));
+    let dispatcher = SignalDispatcher::new(trader.clone(), pumpportal, Arc::new(ChannelPublisher(results_tx)), 4);
+
+    let signal = common::signal("consensus", None);
+    dispatcher.dispatch(signal.clone()).await;
+    dispatcher.drain().await;
+
+    let result = results_rx.try_recv().expect("rejection published");
+    assert!(!result.success);
+    assert_eq!(result.token_mint, signal.token_mint);
+    assert_eq!(result.error_code, Some(ErrorCode::RiskRejected));
+    let error = result.error.unwrap();
+    assert!(error.contains("max open positions reached (1/1)"), "unexpected error: {}", error);
+    assert!(harness.jupiter.received_requests().await.unwrap().is_empty(), "rejected before quoting");
+    assert!(harness.chain.routes().is_empty());
+}
+
+#[tokio::test]
+async fn max_deployed_sol_counts_remaining_cost() {
+    let mut harness = Harness::start().await;
+    harness.config.max_deployed_sol = 0.25;
+    let trader = harness.trader();
+
+    // 0.3 SOL in, 2/3 sold -> 0.1 SOL still deployed, a 0.1 SOL buy fits
+    let held = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let mut position = open_position(&held, 0.3);
+    position.amount_tokens /= 3;
+    trader.position_manager().add_position(position).await;
+
+    let signal = common::signal("consensus", None);
+    harness.chain.script_fill(&signal.token_mint, 900_000_000_000, -100_500_000);
+    let result = trader.execute_buy(&signal).await.unwrap();
+    assert!(result.success, "buy failed: {:?}", result.error);
+
+    // 0.2 SOL deployed now - another 0.1 SOL would exceed 0.25
+    let result = trader.execute_buy(&common::signal("consensus", None)).await.unwrap();
+    assert_eq!(result.error_code, Some(ErrorCode::RiskRejected));
+    assert!(result.error.unwrap().contains("max deployed SOL exceeded"));
+}
+
+/// Buys of `count` fresh mints started together; returns how many went through
+async fn concurrent_buys(trader: &Arc<SpectreTrader>, count: usize) -> usize {
+    let buys = (0..count).map(|_| {
+        let trader = trader.clone();
+        tokio::spawn(async move { trader.execute_buy(&common::signal("consensus", None)).await.unwrap() })
+    });
+    let results = futures::future::join_all(buys).await;
+
+    let mut admitted = 0;
+    for result in results.into_iter().map(|r| r.unwrap()) {
+        match result.success {
+            true => admitted += 1,
+            false => assert_eq!(result.error_code, Some(ErrorCode::RiskRejected), "{:?}", result.error),
+        }
+    }
+    admitted
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_buys_never_exceed_max_open_positions() {
+    let mut harness = Harness::start().await;
+    // Paper fills: every buy completes quickly, with no chain state to share
+    harness.config.paper_mode = true;
+    harness.config.max_open_positions = 2;
+    let trader = harness.trader();
+    let held = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    trader.position_manager().add_position(open_position(&held, 0.1)).await;
+
+    assert_eq!(concurrent_buys(&trader, 6).await, 1);
+    assert_eq!(trader.position_manager().position_count().await, 2);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_buys_never_exceed_max_deployed_sol() {
+    let mut harness = Harness::start().await;
+    harness.config.paper_mode = true;
+    harness.config.max_deployed_sol = 0.35;
+    let trader = harness.trader();
+
+    // 0.1 SOL each - three fit under 0.35
+    assert_eq!(concurrent_buys(&trader, 8).await, 3);
+    assert_eq!(trader.position_manager().position_count().await, 3);
+}
+
+#[tokio::test]
+async fn sol_reserve_blocks_buy_that_would_drain_the_wallet() {
+    let mut harness = Harness::start().await;
+    // Mock wallet holds 10 SOL, the buy is 0.1 SOL
+    harness.config.min_sol_reserve = 9.95;
+    let trader = harness.trader();
+
+    let result = trader.execute_buy(&common::signal("ninja", Some(0.00002))).await.unwrap();
+
+    assert_eq!(result.error_code, Some(ErrorCode::RiskRejected));
+    assert!(result.error.unwrap().contains("SOL reserve too low"));
+    assert!(harness.pumpportal.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn daily_loss_limit_halts_new_entries() {
+    let mut harness = Harness::start().await;
+    harness.config.daily_loss_limit_sol = 0.05;
+    let trader = harness.trader();
+
+    let signal = common::signal("consensus", None);
+    let mint = signal.token_mint.clone();
+    harness.chain.script_fill(&mint, 900_000_000_000, -100_500_000);
+    assert!(trader.execute_buy(&signal).await.unwrap().success);
+
+    // Dumped for 0.01 SOL - about -0.09 SOL realized
+    harness.chain.script_fill(&mint, 0, 10_000_000);
+    let sell = trader.execute_sell(&mint, ExitReason::StopLoss).await.unwrap();
+    assert!(sell.success, "sell failed: {:?}", sell.error);
+
+    let result = trader.execute_buy(&common::signal("consensus", None)).await.unwrap();
+    assert_eq!(result.error_code, Some(ErrorCode::RiskRejected));
+    assert!(result.error.unwrap().contains("daily loss limit hit"));
+}
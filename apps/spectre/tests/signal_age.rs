@@ -0,0 +1,65 @@
+//! Stale signals are skipped before execution; fresh ones report signal -> submit latency
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{ChannelPublisher, Harness};
+use spectre::dispatch::SignalDispatcher;
+use spectre::error::ErrorCode;
+use spectre::pumpportal::PumpPortalClient;
+use spectre::redis::SpectreSignal;
+use tokio::sync::mpsc;
+
+fn aged_signal(signal_type: &str, entry_price_usd: Option<f64>, age_secs: i64) -> SpectreSignal {
+    SpectreSignal {
+        timestamp: (chrono::Utc::now() - chrono::Duration::seconds(age_secs)).to_rfc3339(),
+        ..common::signal(signal_type, entry_price_usd)
+    }
+}
+
+#[tokio::test]
+async fn stale_signal_is_skipped_and_reported_with_its_age() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let pumpportal = Arc::new(PumpPortalClient::with_ws_url("ws://127.0.0.1:1"));
+    let dispatcher = SignalDispatcher::new(trader.clone(), pumpportal, Arc::new(ChannelPublisher(results_tx)), 4);
+
+    // Drained from a backlog a minute after it was generated (limit 30s)
+    let signal = aged_signal("ninja", Some(0.00002), 60);
+    dispatcher.dispatch(signal.clone()).await;
+    dispatcher.drain().await;
+
+    let result = results_rx.try_recv().expect("skip published");
+    assert!(!result.success);
+    assert_eq!(result.error_code, Some(ErrorCode::StaleSignal));
+    assert!(result.signal_age_ms.unwrap() >= 60_000);
+    let error = result.error.unwrap();
+    assert!(error.contains("Stale signal: 60.") && error.contains("max 30s for ninja"), "unexpected error: {}", error);
+    assert!(result.signal_to_submit_ms.is_none(), "nothing was submitted");
+    assert!(harness.pumpportal.received_requests().await.unwrap().is_empty());
+    assert!(!trader.position_manager().has_position(&signal.token_mint).await);
+}
+
+#[tokio::test]
+async fn max_age_is_per_signal_type() {
+    let mut harness = Harness::start().await;
+    harness.config.max_signal_age_by_type.insert("ninja".to_string(), 5);
+    let trader = harness.trader();
+
+    let ninja = aged_signal("ninja", Some(0.00002), 10);
+    let result = trader.execute_buy(&ninja).await.unwrap();
+    assert_eq!(result.error_code, Some(ErrorCode::StaleSignal));
+    assert!(result.error.unwrap().contains("max 5s for ninja"));
+
+    // Same age, but consensus signals keep the 30s default
+    let consensus = aged_signal("consensus", None, 10);
+    harness.chain.script_fill(&consensus.token_mint, 900_000_000_000, -100_500_000);
+    let result = trader.execute_buy(&consensus).await.unwrap();
+    assert!(result.success, "buy failed: {:?}", result.error);
+    assert!(result.signal_age_ms.unwrap() >= 10_000);
+    let to_submit = result.signal_to_submit_ms.expect("submit latency measured");
+    assert!(to_submit >= result.signal_age_ms.unwrap(), "submitted after the buy started");
+}
@@ -0,0 +1,112 @@
+//! Concurrent signal execution: slots, per-mint ordering and duplicate signals
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{ChannelPublisher, Delayed, Harness, JupiterQuote};
+use spectre::dispatch::SignalDispatcher;
+use spectre::pumpportal::PumpPortalClient;
+use spectre::redis::SpectreSignal;
+use tokio::sync::mpsc;
+
+const QUOTE_DELAY: Duration = Duration::from_millis(400);
+
+async fn quote_requests(harness: &Harness) -> usize {
+    harness
+        .jupiter
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == "/quote")
+        .count()
+}
+
+#[tokio::test]
+async fn buys_for_different_mints_run_concurrently() {
+    let harness = Harness::start().await;
+    common::mount_jupiter_quote(&harness.jupiter, Delayed(JupiterQuote { out_amount: 900_000_000_000 }, QUOTE_DELAY)).await;
+    let trader = harness.trader();
+
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let pumpportal = Arc::new(PumpPortalClient::with_ws_url("ws://127.0.0.1:1"));
+    let dispatcher = SignalDispatcher::new(trader.clone(), pumpportal, Arc::new(ChannelPublisher(results_tx)), 2);
+
+    let first = common::signal("consensus", None);
+    let second = common::signal("consensus", None);
+    for signal in [&first, &second] {
+        harness.chain.script_fill(&signal.token_mint, 900_000_000_000, -100_500_000);
+    }
+
+    assert!(dispatcher.dispatch(first.clone()).await);
+    assert!(dispatcher.dispatch(second.clone()).await);
+
+    // Both quotes are requested before the first one is answered
+    tokio::time::sleep(QUOTE_DELAY / 2).await;
+    assert_eq!(quote_requests(&harness).await, 2);
+
+    tokio::time::timeout(common::WAIT, dispatcher.drain()).await.expect("buys never finished");
+    assert_eq!(dispatcher.busy_mints().await, 0);
+
+    let mut published: Vec<String> = (0..2).map(|_| results_rx.try_recv().expect("result published").token_mint).collect();
+    published.sort();
+    let mut expected = vec![first.token_mint.clone(), second.token_mint.clone()];
+    expected.sort();
+    assert_eq!(published, expected);
+    assert!(trader.position_manager().has_position(&first.token_mint).await);
+    assert!(trader.position_manager().has_position(&second.token_mint).await);
+}
+
+#[tokio::test]
+async fn single_slot_waits_for_the_running_buy() {
+    let harness = Harness::start().await;
+    common::mount_jupiter_quote(&harness.jupiter, Delayed(common::no_route(), QUOTE_DELAY)).await;
+    let trader = harness.trader();
+
+    let (results_tx, _results_rx) = mpsc::unbounded_channel();
+    let pumpportal = Arc::new(PumpPortalClient::with_ws_url("ws://127.0.0.1:1"));
+    let dispatcher = SignalDispatcher::new(trader, pumpportal, Arc::new(ChannelPublisher(results_tx)), 1);
+
+    assert!(dispatcher.dispatch(common::signal("consensus", None)).await);
+    let started = std::time::Instant::now();
+    assert!(dispatcher.dispatch(common::signal("consensus", None)).await);
+
+    assert!(started.elapsed() >= QUOTE_DELAY / 2, "second mint got a slot while the first was still running");
+    tokio::time::timeout(common::WAIT, dispatcher.drain()).await.expect("buys never finished");
+    assert_eq!(quote_requests(&harness).await, 2);
+}
+
+#[tokio::test]
+async fn same_mint_signals_are_deduplicated_and_run_in_order() {
+    let harness = Harness::start().await;
+    common::mount_jupiter_quote(&harness.jupiter, Delayed(JupiterQuote { out_amount: 900_000_000_000 }, QUOTE_DELAY)).await;
+    let trader = harness.trader();
+
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let pumpportal = Arc::new(PumpPortalClient::with_ws_url("ws://127.0.0.1:1"));
+    let dispatcher = SignalDispatcher::new(trader.clone(), pumpportal, Arc::new(ChannelPublisher(results_tx)), 4);
+
+    let consensus = common::signal("consensus", None);
+    let mint = consensus.token_mint.clone();
+    harness.chain.script_fill(&mint, 900_000_000_000, -100_500_000);
+
+    assert!(dispatcher.dispatch(consensus.clone()).await);
+    assert!(!dispatcher.dispatch(consensus.clone()).await, "repeat of the running signal is dropped");
+
+    // A different signal for the same mint waits for the running buy
+    let ninja = SpectreSignal { signal_type: "ninja".to_string(), entry_price_usd: Some(0.00002), ..consensus.clone() };
+    assert!(dispatcher.dispatch(ninja.clone()).await);
+    assert!(!dispatcher.dispatch(ninja).await, "repeat of a queued signal is dropped");
+
+    tokio::time::timeout(common::WAIT, dispatcher.drain()).await.expect("buys never finished");
+
+    // Consensus ran first and opened the position, the queued ninja buy found it and skipped
+    let position = trader.position_manager().get_position(&mint).await.expect("position opened");
+    assert!(!position.is_pumpfun);
+    assert_eq!(quote_requests(&harness).await, 1);
+    assert!(harness.pumpportal.received_requests().await.unwrap().is_empty());
+    assert!(results_rx.try_recv().unwrap().success);
+    assert!(results_rx.try_recv().is_err(), "the skipped buy sent nothing");
+}
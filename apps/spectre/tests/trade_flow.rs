@@ -0,0 +1,167 @@
+//! Buy -> position -> price tick -> scaled exit -> sell against local mock services
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{ChannelPublisher, Harness, MockPumpPortalWs, Route, DECIMALS, WAIT};
+use spectre::monitor::position_monitor;
+use spectre::price::PriceSource;
+use spectre::pumpportal::PumpPortalClient;
+use spectre::redis::ResultPublisher;
+use tokio::sync::{broadcast, mpsc};
+
+const SOL_USD: f64 = 200.0;
+
+#[tokio::test]
+async fn ninja_buy_then_scaled_take_profit_on_price_tick() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    trader.set_sol_price(SOL_USD).await;
+
+    let mut ws = MockPumpPortalWs::start().await;
+    let mut pumpportal = PumpPortalClient::with_ws_url(&ws.url);
+    let price_rx = pumpportal.start(SOL_USD).await.unwrap();
+    let mut conn = ws.accept().await;
+
+    // Buy 1M tokens for 0.1 SOL -> $0.00002 per token
+    let signal = common::signal("ninja", Some(0.00002));
+    let mint = signal.token_mint.clone();
+    let bought: u64 = 1_000_000 * 10u64.pow(DECIMALS as u32);
+    harness.chain.script_fill(&mint, bought, -100_500_000);
+
+    let result = trader.execute_buy(&signal).await.unwrap();
+    assert!(result.success, "buy failed: {:?}", result.error);
+    assert!(result.tx_signature.is_some());
+    assert!(result.bundle_id.is_some());
+    assert_eq!(result.amount_tokens, Some(bought as f64));
+
+    let position = trader.position_manager().get_position(&mint).await.expect("position opened");
+    assert!(position.is_pumpfun);
+    assert_eq!(position.amount_tokens, bought);
+    assert_eq!(position.token_decimals, Some(DECIMALS));
+    assert!(!position.needs_price_sync(), "on-chain fill sets the entry price");
+    assert!((position.entry_price - 0.00002).abs() < 1e-12);
+
+    // Monitor the position from the PumpPortal feed
+    let pumpportal = Arc::new(pumpportal);
+    pumpportal.subscribe_token(&mint).await.unwrap();
+    let subscribe = conn.next_text().await;
+    assert_eq!(subscribe["method"], "subscribeTokenTrade");
+    assert_eq!(subscribe["keys"][0], mint.as_str());
+
+    let (results_tx, mut results_rx) = mpsc::unbounded_channel();
+    let publisher: Arc<dyn ResultPublisher> = Arc::new(ChannelPublisher(results_tx));
+    let prices: Arc<dyn PriceSource> = pumpportal.clone();
+    let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+    let monitor = tokio::spawn(position_monitor(
+        trader.clone(),
+        prices.clone(),
+        prices,
+        publisher,
+        harness.config.position_check_interval_secs,
+        shutdown_rx,
+        price_rx,
+    ));
+
+    // +40% crosses TP#1 of the NINJA ladder (+30% -> sell 80%)
+    let remaining = bought / 5;
+    harness.chain.script_fill(&mint, remaining, 110_000_000);
+    conn.send_trade(&common::trade_event(&mint, 0.00002 * 1.4 / SOL_USD));
+
+    let sell = tokio::time::timeout(WAIT, results_rx.recv())
+        .await
+        .expect("no sell published")
+        .unwrap();
+    assert!(sell.success, "sell failed: {:?}", sell.error);
+    assert_eq!(sell.action, "sell");
+    assert_eq!(sell.exit_reason.as_deref(), Some("scaled_take_profit"));
+    assert_eq!(sell.amount_tokens, Some((bought - remaining) as f64));
+    assert!((sell.amount_sol - 0.11).abs() < 1e-9, "SOL received comes from the transaction meta");
+
+    // Fill was read on-chain, so PumpPortal gets the exact token amount rather than a percent
+    let requests = harness.pumpportal.received_requests().await.unwrap();
+    let sell_request: serde_json::Value = requests
+        .iter()
+        .map(|r| serde_json::from_slice::<serde_json::Value>(&r.body).unwrap())
+        .find(|body| body["action"] == "sell")
+        .expect("sell sent to PumpPortal");
+    assert_eq!(sell_request["amount"], "800000.000000");
+    assert_eq!(sell_request["denominatedInSol"], "false");
+
+    let position = trader.position_manager().get_position(&mint).await.expect("remainder still open");
+    assert_eq!(position.scaled_exit_stage, 1);
+    assert_eq!(position.amount_tokens, remaining);
+    assert_eq!(harness.chain.routes(), vec![Route::Jito, Route::Jito]);
+
+    shutdown_tx.send(()).unwrap();
+    monitor.await.unwrap();
+}
+
+#[tokio::test]
+async fn consensus_buy_routes_through_jupiter() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+
+    let signal = common::signal("consensus", None);
+    let mint = signal.token_mint.clone();
+    harness.chain.script_fill(&mint, 900_000_000_000, -100_500_000);
+
+    let result = trader.execute_buy(&signal).await.unwrap();
+    assert!(result.success, "buy failed: {:?}", result.error);
+
+    let position = trader.position_manager().get_position(&mint).await.expect("position opened");
+    assert!(!position.is_pumpfun);
+    assert_eq!(position.amount_tokens, 900_000_000_000);
+
+    let quotes = harness.jupiter.received_requests().await.unwrap();
+    assert!(quotes.iter().any(|r| r.url.path() == "/quote"));
+    assert!(quotes.iter().any(|r| r.url.path() == "/swap"));
+    assert!(harness.pumpportal.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn jupiter_buy_without_signal_price_or_visible_fill_prices_from_the_quote() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    trader.set_sol_price(SOL_USD).await;
+
+    // Lands, but the wallet never shows the tokens; no price on the signal
+    let signal = common::signal("consensus", None);
+    let mint = signal.token_mint.clone();
+
+    let result = trader.execute_buy(&signal).await.unwrap();
+    assert!(result.success, "buy failed: {:?}", result.error);
+
+    // Quote: 1M whole tokens (decimals from the mint account) for 0.1 SOL at $200 -> $0.00002
+    let position = trader.position_manager().get_position(&mint).await.expect("position opened");
+    assert!(!position.is_pumpfun);
+    assert_eq!(position.amount_tokens, 1_000_000_000_000);
+    assert!((position.entry_price - 0.00002).abs() < 1e-12, "entry {}", position.entry_price);
+    assert!(position.stop_loss_price > 0.0 && position.stop_loss_price < position.entry_price);
+    assert!(position.take_profit_price > position.entry_price);
+    assert_eq!(position.check_exit(position.entry_price), None, "not dumped on the first tick");
+}
+
+#[tokio::test]
+async fn tokens_held_before_the_buy_are_not_part_of_the_fill() {
+    let harness = Harness::start().await;
+    let trader = harness.trader();
+    trader.set_sol_price(SOL_USD).await;
+
+    // Dust from an earlier trade of the same mint
+    let signal = common::signal("ninja", Some(0.00002));
+    let mint = signal.token_mint.clone();
+    let dust: u64 = 3 * 10u64.pow(DECIMALS as u32);
+    let bought: u64 = 1_000_000 * 10u64.pow(DECIMALS as u32);
+    harness.chain.set_holding(&mint, dust);
+    harness.chain.script_fill(&mint, dust + bought, -100_500_000);
+
+    let result = trader.execute_buy(&signal).await.unwrap();
+    assert!(result.success, "buy failed: {:?}", result.error);
+    assert_eq!(result.amount_tokens, Some(bought as f64));
+
+    let position = trader.position_manager().get_position(&mint).await.unwrap();
+    assert_eq!(position.amount_tokens, bought);
+    assert!((position.entry_price - 0.00002).abs() < 1e-12);
+}